@@ -1,29 +1,56 @@
 use std::collections::BTreeMap;
+use std::fmt;
+use std::cell::OnceCell;
 use serde::{Serialize, Deserialize};
 use crate::{allowable_result, allowable_result_fallback};
 
-use nvapi::{self,
+use nvapi::{self, Status, sys,
     ClockTable, VfpCurve, VfpEntry, Sensor, Cooler, ThermalInfo, PowerInfoEntry,
-    ClockFrequencyType, ClockEntry,
-    BaseVoltage, PStates, ClockRange, ThermalLimit,
+    ClockFrequencyType, ClockEntry, PowerTopologyEntry, PowerTopologyChannel,
+    BaseVoltage, PStates, ClockRange, ThermalLimit, ConnectedIdsFlags,
 };
 pub use nvapi::{
-    PhysicalGpu,
-    Vendor, SystemType, RamType, RamMaker, Foundry,
+    PhysicalGpu, LogicalGpu,
+    Vendor, SystemType, GpuType, RamType, RamMaker, Foundry, Architecture, ArchInfo, ArchitectureId, ChipRevision,
     ClockFrequencies, ClockDomain, VoltageDomain, UtilizationDomain, Utilizations, ClockLockMode, ClockLockEntry,
-    CoolerType, CoolerController, CoolerControl, CoolerPolicy, CoolerTarget, CoolerLevel,
+    CoolerType, CoolerController, CoolerControl, CoolerPolicy, CoolerTarget, CoolerLevel, FanCoolerControlMode,
     VoltageStatus, VoltageTable,
-    PerfInfo, PerfStatus,
+    EccStatus, EccErrors, EccErrorCounts, EccConfiguration, EccConfigurationInfo,
+    PerfInfo, PerfStatus, PowerTopologyRail,
     ThermalController, ThermalTarget,
-    MemoryInfo, PciIdentifiers, DriverModel,
+    GpuHardwareInfo, MemoryInfo, PciIdentifiers, PciLocation, BusInfo, BusType, DriverModel, VbiosVersion, BoardSerial, GpuIdentity, Luid,
     Percentage, Celsius,
     Range,
-    Kibibytes, Microvolts, MicrovoltsDelta, Kilohertz, KilohertzDelta,
+    Kibibytes, Milliwatts, Microvolts, MicrovoltsDelta, Kilohertz, KilohertzDelta,
     PState,
 };
 
+#[derive(Debug)]
 pub struct Gpu {
     gpu: PhysicalGpu,
+    /// `PhysicalGpu::thermal_sensors_mask()`, probed on first use
+    sensors_mask: OnceCell<u32>,
+}
+
+/// The physical GPUs linked (SLI) into a single logical GPU.
+#[derive(Debug)]
+pub struct GpuGroup {
+    pub logical: LogicalGpu,
+    pub gpus: Vec<Gpu>,
+}
+
+impl GpuGroup {
+    /// Every logical GPU along with its physical GPUs.
+    pub fn enumerate() -> nvapi::Result<Vec<Self>> {
+        LogicalGpu::enumerate()?.into_iter().map(|logical| logical.physical_gpus().map(|gpus| GpuGroup {
+            logical,
+            gpus: gpus.into_iter().map(Gpu::new).collect(),
+        })).collect()
+    }
+
+    pub fn is_linked(&self) -> bool {
+        self.gpus.len() > 1
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -31,12 +58,25 @@ pub struct Gpu {
 pub struct GpuInfo {
     pub name: String,
     pub codename: String,
+    pub architecture: Architecture,
+    pub chip_revision: ChipRevision,
+    /// Only reported by newer drivers
+    pub hardware: Option<GpuHardwareInfo>,
     pub bios_version: String,
+    /// `bios_version` in a form that can be compared
+    pub vbios: Option<VbiosVersion>,
     pub driver_model: DriverModel,
     pub vendor: Vendor,
     pub pci: PciIdentifiers,
+    pub bus: Option<BusInfo>,
+    pub identity: GpuIdentity,
+    /// The OS adapter, for matching against DXGI, CUDA or NVML devices
+    ///
+    /// NVAPI doesn't expose the GPU UUID, so there is no `uuid` alongside it.
+    pub adapter_luid: Option<Luid>,
     pub memory: MemoryInfo,
     pub system_type: SystemType,
+    pub class: GpuClass,
     pub ram_type: RamType,
     pub ram_maker: RamMaker,
     pub ram_bus_width: u32,
@@ -54,7 +94,7 @@ pub struct GpuInfo {
     pub sensor_limits: Vec<SensorLimit>,
     pub power_limits: Vec<PowerLimit>,
     pub pstate_limits: BTreeMap<PState, BTreeMap<ClockDomain, PStateLimit>>,
-    // TODO: pstate base_voltages
+    pub pstate_voltage_limits: BTreeMap<PState, Vec<OvervoltLimit>>,
     pub overvolt_limits: Vec<OvervoltLimit>,
     pub vfp_limits: BTreeMap<ClockDomain, VfpRange>,
     pub vfp_locks: Vec<usize>,
@@ -82,16 +122,20 @@ pub struct GpuStatus {
     pub pstate: PState,
     pub clocks: ClockFrequencies,
     pub memory: MemoryInfo,
+    /// The current PCIe lane count
+    pub pcie_width: Option<u32>,
     pub voltage: Option<Microvolts>,
     pub voltage_domains: Option<VoltageStatus>,
     pub voltage_step: Option<VoltageStatus>,
     pub voltage_table: Option<VoltageTable>,
     pub tachometer: Option<u32>,
     pub utilization: Utilizations,
-    pub power: Vec<Percentage>,
+    pub power: Vec<PowerReading>,
     pub sensors: Vec<(SensorDesc, Celsius)>,
     pub coolers: Vec<(CoolerDesc, CoolerStatus)>,
     pub perf: PerfStatus,
+    /// Memory error counts, on boards with ECC enabled
+    pub ecc: Option<EccErrors>,
     pub vfp: Option<VfpTable>,
     pub vfp_locks: BTreeMap<usize, Microvolts>,
 }
@@ -109,10 +153,99 @@ pub struct GpuSettings {
     pub vfp_locks: BTreeMap<usize, ClockLockEntry>,
 }
 
+/// The kind of system a GPU is deployed in, for choosing suitable defaults.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum GpuClass {
+    /// Shares power and cooling with the CPU
+    Integrated,
+    Laptop,
+    /// A Quadro in a laptop
+    MobileWorkstation,
+    Desktop,
+    /// A Quadro in a desktop
+    Workstation,
+    Unknown,
+}
+
+impl GpuClass {
+    pub fn new(gpu_type: GpuType, system_type: SystemType, quadro: bool) -> Self {
+        match (gpu_type, system_type, quadro) {
+            (GpuType::Integrated, ..) => GpuClass::Integrated,
+            (_, SystemType::Laptop, true) => GpuClass::MobileWorkstation,
+            (_, SystemType::Laptop, false) => GpuClass::Laptop,
+            (_, _, true) => GpuClass::Workstation,
+            (_, SystemType::Desktop, false) => GpuClass::Desktop,
+            (_, SystemType::Unknown, false) => GpuClass::Unknown,
+        }
+    }
+
+    /// Whether power and thermal headroom is constrained by a portable chassis
+    pub fn is_mobile(&self) -> bool {
+        matches!(self, GpuClass::Integrated | GpuClass::Laptop | GpuClass::MobileWorkstation)
+    }
+
+    pub fn is_workstation(&self) -> bool {
+        matches!(self, GpuClass::Workstation | GpuClass::MobileWorkstation)
+    }
+}
+
+/// The outcome of probing one family of related getters and setters.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct Capability {
+    /// The status returned when reading the current state.
+    pub get: Status,
+    /// Whether the state read reports anything that can be changed, or why it couldn't be read.
+    /// The setter itself is never called.
+    pub set: Status,
+}
+
+impl Capability {
+    fn probe<T, F: FnOnce(&T) -> bool>(get: nvapi::Result<T>, editable: F) -> Self {
+        match get {
+            Ok(ref value) => Capability {
+                get: Status::Ok,
+                set: if editable(value) { Status::Ok } else { Status::NotSupported },
+            },
+            Err(e) => Capability {
+                get: e,
+                set: e,
+            },
+        }
+    }
+
+    pub fn readable(&self) -> bool {
+        self.get == Status::Ok
+    }
+
+    pub fn writable(&self) -> bool {
+        self.readable() && self.set == Status::Ok
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct GpuCapabilities {
+    pub pstates: Capability,
+    pub vfp_curve: Capability,
+    pub vfp_locks: Capability,
+    pub cooler_policy_tables: Capability,
+    pub thermal_policies: Capability,
+    pub power_policies: Capability,
+    /// The driver reports no editable flag for the voltage boost, so `set` is inferred
+    /// from the boosted rail's voltage also being readable.
+    pub voltage_rails: Capability,
+    /// Reads from the EDID of the first connected display. Writes could change the
+    /// state of a device and are never attempted, so `set` is always `NotSupported`.
+    pub i2c: Capability,
+}
+
 impl Gpu {
     pub fn new(gpu: PhysicalGpu) -> Self {
         Gpu {
             gpu: gpu,
+            sensors_mask: OnceCell::new(),
         }
     }
 
@@ -128,8 +261,52 @@ impl Gpu {
         PhysicalGpu::enumerate().map(|v| v.into_iter().map(Gpu::new).collect())
     }
 
+    pub fn class(&self) -> nvapi::Result<GpuClass> {
+        Ok(GpuClass::new(
+            allowable_result_fallback(self.gpu.gpu_type(), GpuType::Unknown)?,
+            allowable_result_fallback(self.gpu.system_type(), SystemType::Unknown)?,
+            allowable_result_fallback(self.gpu.is_quadro(), false)?,
+        ))
+    }
+
+    /// Falls back to the legacy pstate API on GPUs older than Kepler.
+    fn pstates(&self) -> nvapi::Result<PStates> {
+        match self.gpu.pstates() {
+            Err(Status::NotSupported) | Err(Status::NoImplementation) => self.gpu.pstates_legacy(),
+            pstates => pstates,
+        }
+    }
+
+    /// The sensors supported by `PhysicalGpu::thermal_sensors()`, or `0` if there are none
+    fn sensors_mask(&self) -> nvapi::Result<u32> {
+        if let Some(&mask) = self.sensors_mask.get() {
+            return Ok(mask)
+        }
+
+        let mask = allowable_result_fallback(self.gpu.thermal_sensors_mask(), 0)?;
+        Ok(*self.sensors_mask.get_or_init(|| mask))
+    }
+
+    /// The named sensors supported by `PhysicalGpu::thermal_sensors()`
+    fn named_sensors(&self) -> nvapi::Result<Vec<(SensorDesc, Celsius)>> {
+        let mask = self.sensors_mask()?;
+        if SensorKind::values().all(|kind| mask & (1 << kind.index()) == 0) {
+            return Ok(Default::default())
+        }
+
+        // the driver expects the full contiguous mask rather than just the named bits
+        let sensors = match allowable_result(self.gpu.thermal_sensors(mask))? {
+            Ok(sensors) => sensors,
+            Err(..) => return Ok(Default::default()),
+        };
+        Ok(sensors.into_iter()
+            .filter_map(|(index, temperature)| SensorKind::from_index(index).map(|kind| (From::from(kind), temperature.into())))
+            .collect()
+        )
+    }
+
     pub fn info(&self) -> nvapi::Result<GpuInfo> {
-        let pstates = allowable_result(self.gpu.pstates())?;
+        let pstates = allowable_result(self.pstates())?;
         let (pstates, ov) = match pstates {
             Ok(PStates { editable: _editable, pstates, overvolt }) => (pstates, overvolt),
             Err(..) => (Default::default(), Default::default()),
@@ -139,12 +316,23 @@ impl Gpu {
         Ok(GpuInfo {
             name: self.gpu.full_name()?,
             codename: self.gpu.short_name()?,
+            architecture: self.gpu.architecture()?,
+            chip_revision: match allowable_result(self.gpu.arch_info())? {
+                Ok(info) => info.revision,
+                Err(..) => ChipRevision::Unknown,
+            },
+            hardware: allowable_result(self.gpu.gpu_info())?.ok(),
             bios_version: self.gpu.vbios_version_string()?,
+            vbios: allowable_result(self.gpu.vbios_version())?.ok(),
             driver_model: self.gpu.driver_model()?,
             vendor: allowable_result_fallback(pci.vendor().map_err(From::from), Vendor::Unknown)?,
             pci: pci,
+            bus: allowable_result(self.gpu.bus_info())?.ok(),
+            identity: self.gpu.identity()?,
+            adapter_luid: allowable_result(self.gpu.adapter_luid())?.ok(),
             memory: self.gpu.memory_info()?,
             system_type: allowable_result_fallback(self.gpu.system_type(), SystemType::Unknown)?,
+            class: self.class()?,
             ram_type: allowable_result_fallback(self.gpu.ram_type(), RamType::Unknown)?,
             ram_maker: allowable_result_fallback(self.gpu.ram_maker(), RamMaker::Unknown)?,
             ram_bus_width: allowable_result_fallback(self.gpu.ram_bus_width(), 0)?,
@@ -173,6 +361,7 @@ impl Gpu {
                 Ok(p) => p.entries.into_iter().map(From::from).collect(),
                 Err(..) => Default::default(),
             },
+            pstate_voltage_limits: pstates.iter().map(|p| (p.id, p.base_voltages.iter().map(|&v| v.into()).collect())).collect(),
             pstate_limits: pstates.into_iter().map(|p| (p.id, p.clocks.into_iter().map(|p| (p.domain(), p.into())).collect())).collect(),
             overvolt_limits: ov.into_iter().map(From::from).collect(),
             vfp_limits: match allowable_result(self.gpu.vfp_ranges())? {
@@ -191,24 +380,34 @@ impl Gpu {
 
         Ok(GpuStatus {
             pstate: self.gpu.current_pstate()?,
-            clocks: self.gpu.clock_frequencies(ClockFrequencyType::Current)?,
+            clocks: match allowable_result(self.gpu.clock_frequencies(ClockFrequencyType::Current))? {
+                Ok(clocks) => clocks,
+                Err(..) => self.gpu.all_clocks()?.current,
+            },
             memory: self.gpu.memory_info()?,
+            pcie_width: allowable_result(self.gpu.pcie_downstream_width())?.ok(),
             voltage: allowable_result(self.gpu.core_voltage())?.ok(),
             voltage_domains: allowable_result(self.gpu.voltage_domains_status())?.ok(),
             voltage_step: allowable_result(self.gpu.voltage_step())?.ok(),
             voltage_table: allowable_result(self.gpu.voltage_table())?.ok(),
             tachometer: allowable_result(self.gpu.tachometer())?.ok(),
             utilization: self.gpu.dynamic_pstates_info()?,
-            power: self.gpu.power_usage()?.into_iter().map(From::from).collect(),
+            power: {
+                let channels = allowable_result(self.gpu.power_topology_info())?.unwrap_or_default();
+                self.gpu.power_topology()?.into_iter().enumerate()
+                    .map(|(i, entry)| PowerReading::new(entry, channels.get(i)))
+                    .collect()
+            },
             sensors: match allowable_result(self.gpu.thermal_settings(None))? {
                 Ok(s) => s.into_iter().map(|s| (From::from(s), s.current_temperature)).collect(),
-                Err(..) => Default::default(),
-            },
+                Err(..) => Vec::new(),
+            }.into_iter().chain(self.named_sensors()?).collect(),
             coolers: match allowable_result(self.gpu.cooler_settings(None))? {
                 Ok(c) => c.into_iter().map(|c| (From::from(c), From::from(c))).collect(),
                 Err(..) => Default::default(),
             },
             perf: self.gpu.perf_status()?,
+            ecc: allowable_result(self.gpu.ecc_errors())?.ok(),
             vfp: match mask {
                 Ok(mask) => allowable_result(self.gpu.vfp_curve(mask.mask))?.map(From::from).ok(),
                 Err(..) => None,
@@ -226,7 +425,7 @@ impl Gpu {
 
     pub fn settings(&self) -> nvapi::Result<GpuSettings> {
         let mask = allowable_result(self.gpu.vfp_mask())?;
-        let pstates = allowable_result(self.gpu.pstates())?;
+        let pstates = allowable_result(self.pstates())?;
         let (pstates, ov) = match pstates {
             Ok(PStates { editable: _editable, pstates, overvolt }) => (pstates, overvolt),
             Err(..) => (Default::default(), Default::default()),
@@ -262,10 +461,65 @@ impl Gpu {
         })
     }
 
+    /// Probes which controls the board supports, without modifying any settings.
+    pub fn capabilities(&self) -> GpuCapabilities {
+        GpuCapabilities {
+            pstates: Capability::probe(self.pstates(), |pstates| pstates.editable && (
+                pstates.pstates.iter().filter(|p| p.editable)
+                    .any(|p| p.clocks.iter().any(ClockEntry::editable) || p.base_voltages.iter().any(|v| v.editable))
+                || pstates.overvolt.iter().any(|v| v.editable)
+            )),
+            vfp_curve: Capability::probe(
+                self.gpu.vfp_mask().and_then(|mask| self.gpu.vfp_curve(mask.mask).map(|_| mask))
+                    .and_then(|mask| self.gpu.vfp_ranges().map(|ranges| (mask, ranges))),
+                |(mask, ranges)| mask.mask.iter().any(|&m| m != 0) && ranges.iter().any(|r| r.range.min < r.range.max)
+            ),
+            vfp_locks: Capability::probe(self.gpu.vfp_locks(), |locks| !locks.is_empty()),
+            cooler_policy_tables: Capability::probe(
+                self.gpu.cooler_settings(None).and_then(|coolers| match coolers.first() {
+                    Some(cooler) => self.gpu.cooler_policy_table(0, cooler.default_policy).map(|table| (coolers, table)),
+                    None => Err(Status::NotSupported),
+                }),
+                |(coolers, table)| coolers.iter().any(|c| c.control == CoolerControl::Variable) && !table.levels.is_empty()
+            ),
+            thermal_policies: Capability::probe(
+                self.gpu.thermal_limit_info().and_then(|(_, info)| self.gpu.thermal_limit().map(|_| info)),
+                |info| info.iter().any(|t| t.temperature_range.min < t.temperature_range.max)
+            ),
+            power_policies: Capability::probe(
+                self.gpu.power_limit_info().and_then(|info| self.gpu.power_limit().map(|_| info)),
+                |info| info.entries.iter().any(|p| p.range.min < p.range.max)
+            ),
+            // the boost applies to the rails reported alongside it
+            voltage_rails: Capability::probe(self.gpu.core_voltage_boost(), |_| self.gpu.core_voltage().is_ok()),
+            i2c: {
+                let read = self.gpu.display_ids_connected(ConnectedIdsFlags::empty()).and_then(|ids| match ids.first() {
+                    Some(display) => self.gpu.display_output_id(display.display_id)
+                        .and_then(|output| self.gpu.i2c_read(output, None, true, 0x50, &[0], &mut [0u8; 1], sys::i2c::I2cSpeed::Default)),
+                    None => Err(Status::NotSupported),
+                });
+                Capability::probe(read, |_| false)
+            },
+        }
+    }
+
     pub fn set_voltage_boost(&self, boost: Percentage) -> nvapi::Result<()> {
         self.gpu.set_core_voltage_boost(boost)
     }
 
+    /// Sets the deltas of the editable `GpuInfo::overvolt_limits`, in the same order as `GpuSettings::overvolt`.
+    pub fn set_overvolt(&self, overvolt: Vec<MicrovoltsDelta>) -> nvapi::Result<()> {
+        let limits: Vec<_> = self.pstates()?.overvolt.into_iter().filter(|v| v.editable).collect();
+        if overvolt.len() > limits.len() {
+            return Err(Status::ArgumentExceedMaxSize)
+        }
+        if overvolt.iter().zip(&limits).any(|(delta, limit)| !limit.voltage_delta.range.contains(delta)) {
+            return Err(Status::InvalidArgument)
+        }
+
+        self.gpu.set_overvolt(limits.into_iter().zip(overvolt).map(|(limit, delta)| (limit.voltage_domain, delta)))
+    }
+
     pub fn set_power_limits<I: Iterator<Item=Percentage>>(&self, limits: I) -> nvapi::Result<()> {
         // TODO: match against power_limit_info, use range.min/max from there if it matches (can get fraction of a percent!)
         self.gpu.set_power_limit(limits.map(From::from))
@@ -378,6 +632,28 @@ impl From<PowerInfoEntry> for PowerLimit {
     }
 }
 
+/// The power draw of a channel.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct PowerReading {
+    /// The measured rail, if the driver names it
+    pub channel: Option<PowerTopologyRail>,
+    /// Power draw in percent of TDP
+    pub percent: Percentage,
+    /// Absolute power draw, if the driver reports what 100% corresponds to
+    pub watts: Option<Milliwatts>,
+}
+
+impl PowerReading {
+    pub fn new(entry: PowerTopologyEntry, channel: Option<&PowerTopologyChannel>) -> Self {
+        PowerReading {
+            channel: channel.and_then(PowerTopologyChannel::rail),
+            percent: entry.power.into(),
+            watts: channel.and_then(|channel| channel.watts(entry.power)),
+        }
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub struct SensorLimit {
@@ -401,7 +677,10 @@ impl From<ThermalInfo> for SensorLimit {
 pub struct SensorDesc {
     pub controller: ThermalController,
     pub target: ThermalTarget,
+    /// Empty for named sensors, whose range isn't reported
     pub range: Range<Celsius>,
+    /// Set for sensors read through `PhysicalGpu::thermal_sensors()`
+    pub kind: Option<SensorKind>,
 }
 
 impl From<Sensor> for SensorDesc {
@@ -410,6 +689,62 @@ impl From<Sensor> for SensorDesc {
             controller: sensor.controller,
             target: sensor.target,
             range: sensor.default_temperature_range,
+            kind: None,
+        }
+    }
+}
+
+impl From<SensorKind> for SensorDesc {
+    fn from(kind: SensorKind) -> Self {
+        SensorDesc {
+            controller: ThermalController::GpuInternal,
+            target: kind.target(),
+            range: Default::default(),
+            kind: Some(kind),
+        }
+    }
+}
+
+/// Sensors that `PhysicalGpu::thermal_settings()` doesn't report, identified by
+/// their `PhysicalGpu::thermal_sensors()` mask bit.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum SensorKind {
+    /// The hottest point of the GPU die
+    Hotspot,
+    /// GDDR6X memory junction
+    MemoryJunction,
+}
+
+impl SensorKind {
+    pub fn values() -> impl Iterator<Item=Self> {
+        [SensorKind::Hotspot, SensorKind::MemoryJunction].into_iter()
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        SensorKind::values().find(|kind| kind.index() == index)
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            SensorKind::Hotspot => 1,
+            SensorKind::MemoryJunction => 9,
+        }
+    }
+
+    pub fn target(&self) -> ThermalTarget {
+        match self {
+            SensorKind::Hotspot => ThermalTarget::Gpu,
+            SensorKind::MemoryJunction => ThermalTarget::Memory,
+        }
+    }
+}
+
+impl fmt::Display for SensorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SensorKind::Hotspot => write!(f, "Hotspot"),
+            SensorKind::MemoryJunction => write!(f, "Memory Junction"),
         }
     }
 }
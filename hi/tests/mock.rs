@@ -0,0 +1,258 @@
+// Exercises the high level API against the simulated driver from `nvapi_sys::mock`.
+
+extern crate nvapi_hi;
+
+use std::io;
+use nvapi_hi::{Gpu, GpuGroup, GpuClass, Architecture, ChipRevision, SensorKind, Celsius, PState, ClockDomain, CoolerLevel, CoolerPolicy, FanCoolerControlMode, Kilohertz, Percentage, Milliwatts, PowerReading, PowerTopologyRail, KilohertzDelta, MicrovoltsDelta, Status, sys};
+use nvapi_hi::nvapi::sys::mock::{Mock, MockGpu};
+
+#[test]
+fn info() {
+    let _mock = Mock::default().install();
+    let gpus = Gpu::enumerate().unwrap();
+    assert_eq!(gpus.len(), 1);
+
+    let info = gpus[0].info().unwrap();
+    assert_eq!(info.name, "GeForce GTX 1080");
+    assert_eq!(info.codename, "GP104");
+    assert_eq!(info.bios_version, "86.04.17.00.01");
+    assert_eq!(info.vbios.unwrap().to_string(), info.bios_version);
+    assert_eq!((info.architecture, info.chip_revision), (Architecture::Pascal, ChipRevision::A01));
+    assert_eq!(info.hardware.unwrap().tensor_cores, Some(0));
+    assert_eq!(info.bus.unwrap().location.unwrap().bus, 1);
+    assert_eq!(info.adapter_luid.unwrap().as_u64(), 0xd1c4);
+
+    let status = gpus[0].status().unwrap();
+    assert_eq!(status.pcie_width, Some(16));
+    assert_eq!(status.power, vec![
+        PowerReading { channel: Some(PowerTopologyRail::TotalBoard), percent: Percentage(42), watts: Some(Milliwatts(75600)) },
+    ]);
+    gpus[0].settings().unwrap();
+}
+
+#[test]
+fn settings() {
+    let _mock = Mock::default().install();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+
+    gpu.set_voltage_boost(Percentage(50)).unwrap();
+    gpu.set_vfp(vec![(2, KilohertzDelta(50000))].into_iter(), vec![(0, KilohertzDelta(-100000))].into_iter()).unwrap();
+
+    let settings = gpu.settings().unwrap();
+    assert_eq!(settings.voltage_boost, Some(Percentage(50)));
+    let vfp = settings.vfp.unwrap();
+    assert_eq!(vfp.graphics[&2], KilohertzDelta(50000));
+    assert!(vfp.memory.contains_key(&80));
+
+    gpu.reset_vfp().unwrap();
+    gpu.set_vfp_lock(nvapi_hi::Microvolts(900000)).unwrap();
+    gpu.reset_vfp_lock().unwrap();
+}
+
+#[test]
+fn overvolt() {
+    let mut gpu = MockGpu::default();
+    gpu.add_overvolt(sys::gpu::pstate::NVAPI_GPU_PERF_VOLTAGE_INFO_DOMAIN_CORE, 1050000, 50000);
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+
+    let info = gpu.info().unwrap();
+    assert_eq!(info.overvolt_limits[0].range.unwrap().max, MicrovoltsDelta(50000));
+    assert!(info.pstate_voltage_limits[&PState::P0][0].range.is_some());
+    assert!(info.pstate_voltage_limits[&PState::P8][0].range.is_none());
+
+    gpu.set_overvolt(vec![MicrovoltsDelta(25000)]).unwrap();
+    assert_eq!(gpu.settings().unwrap().overvolt, vec![MicrovoltsDelta(25000)]);
+    assert_eq!(gpu.set_overvolt(vec![MicrovoltsDelta(75000)]).unwrap_err(), Status::InvalidArgument);
+    assert_eq!(gpu.set_overvolt(vec![MicrovoltsDelta(0); 2]).unwrap_err(), Status::ArgumentExceedMaxSize);
+    assert_eq!(gpu.settings().unwrap().overvolt, vec![MicrovoltsDelta(25000)]);
+}
+
+#[test]
+fn legacy_pstates() {
+    use nvapi_hi::sys::gpu::clock::{NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, NVAPI_GPU_PUBLIC_CLOCK_MEMORY};
+    use nvapi_hi::sys::gpu::pstate::{NVAPI_GPU_PERF_PSTATE_P0, NVAPI_GPU_PERF_PSTATE_P12};
+
+    let mut gpu = MockGpu {
+        full_name: Some("GeForce GTX 580".into()),
+        pstates: None,
+        ..Default::default()
+    };
+    gpu.add_pstate_legacy(NVAPI_GPU_PERF_PSTATE_P0, true, &[(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, 772000), (NVAPI_GPU_PUBLIC_CLOCK_MEMORY, 2004000)], 1000);
+    gpu.add_pstate_legacy(NVAPI_GPU_PERF_PSTATE_P12, false, &[(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, 51000), (NVAPI_GPU_PUBLIC_CLOCK_MEMORY, 68000)], 900);
+    let mock = Mock::new(vec![gpu]).install();
+    // overclocked by a third party tool
+    mock.update_gpu(0, |gpu| gpu.pstates_legacy.as_mut().unwrap().pstates[0].clocks[0].freq = 822000);
+    let gpu = &Gpu::enumerate().unwrap()[0];
+
+    let info = gpu.info().unwrap();
+    let p0 = &info.pstate_limits[&PState::P0][&ClockDomain::Graphics];
+    assert_eq!(p0.frequency.min, Kilohertz(822000));
+    assert_eq!(info.pstate_limits[&PState::P12].len(), 2);
+
+    let settings = gpu.settings().unwrap();
+    assert_eq!(settings.pstate_deltas[&PState::P0][&ClockDomain::Graphics], KilohertzDelta(50000));
+    assert_eq!(settings.pstate_deltas[&PState::P0][&ClockDomain::Memory], KilohertzDelta(0));
+    assert!(!settings.pstate_deltas.contains_key(&PState::P12));
+    assert_eq!(gpu.capabilities().pstates.get, Status::Ok);
+
+    // deltas are set through the perf clocks, relative to the defaults
+    gpu.inner().set_pstates(vec![(PState::P0, ClockDomain::Graphics, KilohertzDelta(100000))].into_iter()).unwrap();
+    let settings = gpu.settings().unwrap();
+    assert_eq!(settings.pstate_deltas[&PState::P0][&ClockDomain::Graphics], KilohertzDelta(100000));
+    assert_eq!(settings.pstate_deltas[&PState::P0][&ClockDomain::Memory], KilohertzDelta(0));
+    assert_eq!(mock.gpu(0).unwrap().pstates_legacy.unwrap().pstates[0].clocks[0].freq, 872000);
+    assert_eq!(gpu.inner().set_pstates(vec![(PState::P12, ClockDomain::Graphics, KilohertzDelta(1000))].into_iter()).unwrap_err(), Status::InvalidArgument);
+    assert_eq!(gpu.inner().set_pstates(vec![(PState::P8, ClockDomain::Graphics, KilohertzDelta(1000))].into_iter()).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn all_clocks_fallback() {
+    let mut clocks = sys::gpu::clock::private::NV_CLOCKS_INFO::zeroed();
+    clocks.clocks[8] = 2004000;
+    clocks.clocks[30] = 1544000;
+    let gpu = MockGpu {
+        clock_frequencies: [None; 3],
+        all_clocks: Some(clocks),
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+
+    let status = gpu.status().unwrap();
+    assert_eq!(status.clocks[&ClockDomain::Graphics], Kilohertz(772000));
+    assert_eq!(status.clocks[&ClockDomain::Memory], Kilohertz(2004000));
+}
+
+#[test]
+fn fan_coolers() {
+    let mut gpu = MockGpu {
+        full_name: Some("GeForce RTX 2080".into()),
+        cooler_settings: None,
+        ..Default::default()
+    };
+    gpu.add_fan_cooler(1, 3500, (0, 100), 30);
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+
+    gpu.set_cooler_levels(vec![CoolerLevel { level: Percentage(60), policy: CoolerPolicy::Manual }].into_iter()).unwrap();
+    let control = gpu.inner().fan_cooler_control().unwrap();
+    assert_eq!((control[0].level, control[0].mode), (Percentage(60), FanCoolerControlMode::Manual));
+
+    gpu.reset_cooler_levels().unwrap();
+    assert_eq!(gpu.inner().fan_cooler_control().unwrap()[0].mode, FanCoolerControlMode::Auto);
+}
+
+#[test]
+fn named_sensors() {
+    let mut gpu = MockGpu::default();
+    for index in 0..10 {
+        gpu.add_thermal_sensor(index, 50 + index as i32);
+    }
+    let mock = Mock::new(vec![gpu]).install();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+    let named = |gpu: &Gpu| gpu.status().unwrap().sensors.into_iter()
+        .filter_map(|(desc, temperature)| desc.kind.map(|kind| (kind, temperature)))
+        .collect::<Vec<_>>();
+
+    let recording = sys::record::record(io::sink());
+    assert_eq!(named(gpu), vec![(SensorKind::Hotspot, Celsius(51)), (SensorKind::MemoryJunction, Celsius(59))]);
+    named(gpu);
+    let capture = recording.finish().unwrap();
+    // the mask is probed once, up to the first unsupported sensor, then each status reads it
+    let calls = capture.calls.iter().filter(|call| call.api() == Some(sys::Api::NvAPI_GPU_ThermalGetSensors)).count();
+    assert_eq!(calls, 11 + 2);
+
+    mock.update_gpu(0, |gpu| gpu.thermal_sensors = None);
+    assert_eq!(named(gpu), vec![]);
+}
+
+#[test]
+fn capabilities() {
+    let gpu = MockGpu {
+        volt_rails_control: None,
+        ..Default::default()
+    };
+    let mock = Mock::new(vec![gpu]).install();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+
+    let caps = gpu.capabilities();
+    assert!(caps.pstates.writable());
+    assert!(caps.vfp_curve.writable());
+    assert!(caps.power_policies.writable());
+    assert_eq!(caps.voltage_rails.get, Status::NotSupported);
+    assert!(!caps.voltage_rails.writable());
+    assert_eq!(caps.i2c.get, Status::DataNotFound);
+
+    // the EDID is read from the display's output, and writes are never claimed
+    mock.update_gpu(0, |gpu| gpu.i2c_devices.push((0x50, vec![0; 0x80])));
+    let caps = gpu.capabilities();
+    assert!(caps.i2c.readable());
+    assert!(!caps.i2c.writable());
+}
+
+#[test]
+fn capabilities_locked() {
+    let mut gpu = MockGpu::default();
+    gpu.pstates.as_mut().unwrap().v1.bIsEditable.set(false);
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+
+    // readable, but the driver reports nothing that can be changed
+    let caps = gpu.capabilities();
+    assert!(caps.pstates.readable());
+    assert_eq!(caps.pstates.set, Status::NotSupported);
+    assert!(!caps.pstates.writable());
+}
+
+#[test]
+fn topology() {
+    let sli = MockGpu {
+        sli_group: Some(1),
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![MockGpu::default(), sli.clone(), sli]).install();
+
+    let groups = GpuGroup::enumerate().unwrap();
+    assert_eq!(groups.iter().map(|g| g.is_linked()).collect::<Vec<_>>(), [false, true]);
+}
+
+#[test]
+fn class() {
+    let laptop = MockGpu {
+        system_type: Some(sys::gpu::NV_SYSTEM_TYPE_LAPTOP),
+        ..Default::default()
+    };
+    let quadro = MockGpu {
+        quadro_status: Some(1),
+        ..Default::default()
+    };
+    let unknown = MockGpu {
+        system_type: None,
+        gpu_type: None,
+        quadro_status: None,
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![MockGpu::default(), laptop, quadro, unknown]).install();
+    let gpus = Gpu::enumerate().unwrap();
+
+    assert_eq!(gpus[0].info().unwrap().class, GpuClass::Desktop);
+    let laptop = gpus[1].class().unwrap();
+    assert_eq!(laptop, GpuClass::Laptop);
+    assert!(laptop.is_mobile() && !laptop.is_workstation());
+    assert_eq!(gpus[2].class().unwrap(), GpuClass::Workstation);
+    assert_eq!(gpus[3].class().unwrap(), GpuClass::Unknown);
+}
+
+#[test]
+fn ecc() {
+    let mut gpu = MockGpu::default();
+    gpu.add_ecc(sys::gpu::ecc::NV_ECC_CONFIGURATION_IMMEDIATE, true);
+    gpu.add_ecc_errors(0, 2);
+    let _mock = Mock::new(vec![gpu, MockGpu::default()]).install();
+    let gpus = Gpu::enumerate().unwrap();
+
+    let ecc = gpus[0].status().unwrap().ecc.unwrap();
+    assert_eq!(ecc.current.double_bit, 2);
+    assert_eq!(gpus[1].status().unwrap().ecc, None);
+}
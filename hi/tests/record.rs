@@ -0,0 +1,128 @@
+// Replays a session recorded against the simulated driver from `nvapi_sys::mock`.
+
+extern crate nvapi_hi;
+
+use std::io;
+use nvapi_hi::{Gpu, PhysicalGpu};
+use nvapi_hi::nvapi::{Status, DisplayHandle};
+use nvapi_hi::nvapi::sys::mock::{Mock, MockGpu};
+use nvapi_hi::nvapi::sys::i2c::I2cSpeed;
+use nvapi_hi::nvapi::sys::gpu::cooler::private::{NVAPI_COOLER_TYPE_FAN, NVAPI_COOLER_TARGET_ALL, NVAPI_COOLER_POLICY_TEMPERATURE_CONTINUOUS};
+use nvapi_hi::nvapi::sys::record::{self, Capture};
+
+#[test]
+fn replay() {
+    let (info, status, capture) = {
+        let _mock = Mock::default().install();
+        let recording = record::record(io::sink());
+        let gpu = &Gpu::enumerate().unwrap()[0];
+        let info = gpu.info().unwrap();
+        let status = gpu.status().unwrap();
+        (info, status, recording.finish().unwrap())
+    };
+
+    let mut data = Vec::new();
+    capture.write(&mut data).unwrap();
+    let capture = Capture::read(&data[..]).unwrap();
+
+    let _replay = capture.replay();
+    let gpu = &Gpu::enumerate().unwrap()[0];
+    assert_eq!(gpu.info().unwrap(), info);
+    assert_eq!(gpu.status().unwrap(), status);
+    // calls not in the capture
+    assert_eq!(nvapi_hi::nvapi::driver_version().unwrap_err(), Status::NoImplementation);
+}
+
+#[test]
+fn replay_arrays() {
+    let mut gpu = MockGpu::default();
+    let mut display = gpu.display_ids[0];
+    display.displayId += 1;
+    gpu.display_ids.push(display);
+    gpu.add_cooler(NVAPI_COOLER_TYPE_FAN, NVAPI_COOLER_TARGET_ALL, NVAPI_COOLER_POLICY_TEMPERATURE_CONTINUOUS, (0, 100), 30);
+
+    let (displays, capture) = {
+        let _mock = Mock::new(vec![gpu]).install();
+        let recording = record::record(io::sink());
+        let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+        let displays = gpu.display_ids_all().unwrap();
+        gpu.restore_cooler_settings(&[0, 1]).unwrap();
+        (displays, recording.finish().unwrap())
+    };
+    assert_eq!(displays.len(), 2);
+
+    let _replay = capture.replay();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    assert_eq!(gpu.display_ids_all().unwrap(), displays);
+    gpu.restore_cooler_settings(&[0, 1]).unwrap();
+    // only the first element matches the recorded call
+    assert_eq!(gpu.restore_cooler_settings(&[0, 2]).unwrap_err(), Status::NoImplementation);
+}
+
+#[test]
+fn replay_i2c() {
+    let mut gpu = MockGpu::default();
+    gpu.i2c_devices.push((0x50, (0..0x80).collect()));
+
+    let (read, capture) = {
+        let _mock = Mock::new(vec![gpu]).install();
+        let recording = record::record(io::sink());
+        let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+        let mut read = [0u8; 4];
+        gpu.i2c_read(1, None, true, 0x50, &[0x10], &mut read, I2cSpeed::Default).unwrap();
+        gpu.i2c_write(1, None, true, 0x50, &[0x20], &[0xff; 2], I2cSpeed::Default).unwrap();
+        (read, recording.finish().unwrap())
+    };
+    assert_eq!(read, [0x10, 0x11, 0x12, 0x13]);
+
+    let mut data = Vec::new();
+    capture.write(&mut data).unwrap();
+    let capture = Capture::read(&data[..]).unwrap();
+
+    let _replay = capture.replay();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    let mut replayed = [0u8; 4];
+    gpu.i2c_read(1, None, true, 0x50, &[0x10], &mut replayed, I2cSpeed::Default).unwrap();
+    assert_eq!(replayed, read);
+    gpu.i2c_write(1, None, true, 0x50, &[0x20], &[0xff; 2], I2cSpeed::Default).unwrap();
+    // the data behind the struct's pointers is matched as well
+    assert_eq!(gpu.i2c_read(1, None, true, 0x50, &[0x14], &mut replayed, I2cSpeed::Default).unwrap_err(), Status::NoImplementation);
+    assert_eq!(gpu.i2c_write(1, None, true, 0x50, &[0x20], &[0; 2], I2cSpeed::Default).unwrap_err(), Status::NoImplementation);
+}
+
+#[test]
+fn replay_logical_gpu() {
+    let (info, capture) = {
+        let _mock = Mock::default().install();
+        let recording = record::record(io::sink());
+        let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+        let info = gpu.logical_gpu().unwrap().info().unwrap();
+        (format!("{:?}", info), recording.finish().unwrap())
+    };
+
+    let mut data = Vec::new();
+    capture.write(&mut data).unwrap();
+    let capture = Capture::read(&data[..]).unwrap();
+
+    let _replay = capture.replay();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    let replayed = gpu.logical_gpu().unwrap().info().unwrap();
+    assert_eq!(format!("{:?}", replayed), info);
+    assert_ne!(replayed.adapter_luid, Default::default());
+}
+
+#[test]
+fn replay_display_name() {
+    let (handle, capture) = {
+        let _mock = Mock::default().install();
+        let recording = record::record(io::sink());
+        let handle = DisplayHandle::from_name("\\\\.\\DISPLAY1").unwrap();
+        (format!("{:?}", handle), recording.finish().unwrap())
+    };
+
+    let _replay = capture.replay();
+    assert_eq!(format!("{:?}", DisplayHandle::from_name("\\\\.\\DISPLAY1").unwrap()), handle);
+    // only the first character would match if the string were captured as a single byte
+    assert_eq!(DisplayHandle::from_name("\\\\.\\DISPLAY2").unwrap_err(), Status::NoImplementation);
+}
+
@@ -5,11 +5,11 @@ use crate::sys::gpu::{clock, power};
 use crate::sys;
 use serde::{Serialize, Deserialize};
 use log::trace;
-use crate::types::{Kilohertz, Kilohertz2, KilohertzDelta, Kilohertz2Delta, Percentage, Percentage1000, Microvolts, CelsiusShifted, Range, RawConversion};
+use crate::types::{Kilohertz, Kilohertz2, KilohertzDelta, Kilohertz2Delta, Percentage, Percentage1000, Microvolts, Milliwatts, CelsiusShifted, Range, RawConversion};
 
 pub use sys::gpu::clock::PublicClockId as ClockDomain;
 pub use sys::gpu::clock::private::ClockLockMode;
-pub use sys::gpu::power::private::PerfFlags;
+pub use sys::gpu::power::private::{PerfFlags, PowerTopologyDomain, PowerTopologyRail};
 
 impl RawConversion for clock::NV_GPU_CLOCK_FREQUENCIES {
     type Target = BTreeMap<ClockDomain, Kilohertz>;
@@ -26,6 +26,47 @@ impl RawConversion for clock::NV_GPU_CLOCK_FREQUENCIES {
     }
 }
 
+/// The undocumented clock tables reported by `PhysicalGpu::all_clocks()`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllClocks {
+    pub current: BTreeMap<ClockDomain, Kilohertz>,
+    pub default: BTreeMap<ClockDomain, Kilohertz>,
+    pub boost: BTreeMap<ClockDomain, Kilohertz>,
+}
+
+impl RawConversion for clock::private::NV_CLOCKS_INFO_V1 {
+    type Target = AllClocks;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        use crate::sys::gpu::clock::private::*;
+
+        trace!("convert_raw({:#?})", self);
+        let table = |offset: usize| -> BTreeMap<_, _> {
+            let clocks = &self.clocks[offset..offset + NV_CLOCKS_INFO_TABLE_SIZE];
+            let (graphics, processor) = match clocks[NV_CLOCKS_INFO_SHADER] {
+                0 => (clocks[NV_CLOCKS_INFO_GRAPHICS], clocks[NV_CLOCKS_INFO_PROCESSOR]),
+                shader => (shader / 2, shader),
+            };
+            [
+                (ClockDomain::Graphics, graphics),
+                (ClockDomain::Memory, clocks[NV_CLOCKS_INFO_MEMORY]),
+                (ClockDomain::Processor, processor),
+                (ClockDomain::Video, clocks[NV_CLOCKS_INFO_VIDEO]),
+            ].iter().filter(|&&(_, clock)| clock != 0)
+                .map(|&(id, clock)| (id, Kilohertz(clock)))
+                .collect()
+        };
+
+        Ok(AllClocks {
+            current: table(NV_CLOCKS_INFO_CURRENT),
+            default: table(NV_CLOCKS_INFO_DEFAULT),
+            boost: table(NV_CLOCKS_INFO_BOOST),
+        })
+    }
+}
+
 impl RawConversion for clock::private::NV_USAGES_INFO {
     type Target = BTreeMap<crate::pstate::UtilizationDomain, Percentage>;
     type Error = sys::ArgumentRangeError;
@@ -416,8 +457,23 @@ impl RawConversion for power::private::NV_GPU_POWER_INFO {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct PowerTopologyEntry {
+    /// The raw channel id, which may not be a known `PowerTopologyDomain`
+    pub channel: i32,
+    /// Power draw in percent of TDP
+    pub power: Percentage1000,
+}
+
+impl PowerTopologyEntry {
+    pub fn domain(&self) -> Option<PowerTopologyDomain> {
+        PowerTopologyDomain::from_raw(self.channel).ok()
+    }
+}
+
 impl RawConversion for power::private::NV_GPU_POWER_TOPO_ENTRY {
-    type Target = Percentage1000;
+    type Target = PowerTopologyEntry;
     type Error = sys::ArgumentRangeError;
 
     #[allow(non_snake_case)]
@@ -425,15 +481,74 @@ impl RawConversion for power::private::NV_GPU_POWER_TOPO_ENTRY {
         trace!("convert_raw({:#?})", self);
         match *self {
             power::private::NV_GPU_POWER_TOPO_ENTRY {
-                a: unknown, b: 0, power, d: 0
-            } => Ok(Percentage1000(power)),
+                domain, b: 0, power, d: 0
+            } => Ok(PowerTopologyEntry {
+                channel: domain,
+                power: Percentage1000(power),
+            }),
             _ => Err(sys::ArgumentRangeError),
         }
     }
 }
 
 impl RawConversion for power::private::NV_GPU_POWER_TOPO {
-    type Target = Vec<Percentage1000>;
+    type Target = Vec<PowerTopologyEntry>;
+    type Error = sys::ArgumentRangeError;
+
+    #[allow(non_snake_case)]
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        self.entries[..self.count as usize].iter().map(RawConversion::convert_raw).collect()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct PowerTopologyChannel {
+    /// The raw channel id, which may not be a known `PowerTopologyDomain`
+    pub channel: i32,
+    /// The raw rail id, which may not be a known `PowerTopologyRail`
+    pub rail: i32,
+    /// The power draw that corresponds to 100%, if the driver reports one
+    pub reference: Option<Milliwatts>,
+}
+
+impl PowerTopologyChannel {
+    pub fn rail(&self) -> Option<PowerTopologyRail> {
+        PowerTopologyRail::from_raw(self.rail).ok()
+    }
+
+    /// Converts a reading of this channel into absolute power draw.
+    pub fn watts(&self, power: Percentage1000) -> Option<Milliwatts> {
+        self.reference.map(|reference| Milliwatts((reference.0 as u64 * power.0 as u64 / 100000) as u32))
+    }
+}
+
+impl RawConversion for power::private::NV_GPU_POWER_TOPO_INFO_ENTRY {
+    type Target = PowerTopologyChannel;
+    type Error = sys::ArgumentRangeError;
+
+    #[allow(non_snake_case)]
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        match *self {
+            power::private::NV_GPU_POWER_TOPO_INFO_ENTRY {
+                domain, rail, reference_mW, d: 0
+            } => Ok(PowerTopologyChannel {
+                channel: domain,
+                rail,
+                reference: match reference_mW {
+                    0 => None,
+                    mw => Some(Milliwatts(mw)),
+                },
+            }),
+            _ => Err(sys::ArgumentRangeError),
+        }
+    }
+}
+
+impl RawConversion for power::private::NV_GPU_POWER_TOPO_INFO {
+    type Target = Vec<PowerTopologyChannel>;
     type Error = sys::ArgumentRangeError;
 
     #[allow(non_snake_case)]
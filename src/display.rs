@@ -0,0 +1,159 @@
+use std::ffi::CString;
+use log::trace;
+use crate::sys::{self, dispcontrol, handles};
+use crate::types::RawConversion;
+use crate::gpu::PhysicalGpu;
+
+/// An attached display driven by an NVIDIA GPU.
+#[derive(Debug)]
+pub struct DisplayHandle(handles::NvDisplayHandle);
+
+unsafe impl Send for DisplayHandle { }
+
+/// A display rendered by an NVIDIA GPU that isn't attached to the desktop.
+#[derive(Debug)]
+pub struct UnattachedDisplayHandle(handles::NvUnAttachedDisplayHandle);
+
+unsafe impl Send for UnattachedDisplayHandle { }
+
+fn display_name(name: &str) -> sys::Result<CString> {
+    CString::new(name).map_err(|_| sys::Status::InvalidArgument)
+}
+
+impl DisplayHandle {
+    pub fn handle(&self) -> &handles::NvDisplayHandle {
+        &self.0
+    }
+
+    pub fn enumerate() -> DisplayHandles {
+        DisplayHandles {
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// Looks up a display by its OS name, such as `\\.\DISPLAY1`.
+    pub fn from_name(name: &str) -> sys::Result<Self> {
+        trace!("display.from_name({:?})", name);
+        let name = display_name(name)?;
+        let mut handle = Default::default();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetAssociatedNvidiaDisplayHandle(name.as_ptr(), &mut handle) })
+            .map(|_| DisplayHandle(handle))
+    }
+
+    pub fn name(&self) -> sys::Result<String> {
+        trace!("display.name()");
+        let mut str = sys::types::short_string();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetAssociatedNvidiaDisplayName(self.0, &mut str) })
+            .and_then(|_| str.convert_raw().map_err(From::from))
+    }
+
+    /// The GPUs driving this display, starting with the one with the active output.
+    pub fn physical_gpus(&self) -> sys::Result<Vec<PhysicalGpu>> {
+        trace!("display.physical_gpus()");
+        let mut handles = [Default::default(); sys::types::NVAPI_MAX_PHYSICAL_GPUS];
+        let mut len = 0;
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetPhysicalGPUsFromDisplay(self.0, &mut handles, &mut len) })
+            .map(move |_| handles[..len as usize].iter().cloned().map(PhysicalGpu).collect())
+    }
+}
+
+impl UnattachedDisplayHandle {
+    pub fn handle(&self) -> &handles::NvUnAttachedDisplayHandle {
+        &self.0
+    }
+
+    pub fn enumerate() -> UnattachedDisplayHandles {
+        UnattachedDisplayHandles {
+            index: 0,
+            done: false,
+        }
+    }
+
+    pub fn from_name(name: &str) -> sys::Result<Self> {
+        trace!("unattached_display.from_name({:?})", name);
+        let name = display_name(name)?;
+        let mut handle = Default::default();
+        sys::status_result(unsafe { dispcontrol::NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle(name.as_ptr(), &mut handle) })
+            .map(|_| UnattachedDisplayHandle(handle))
+    }
+
+    pub fn name(&self) -> sys::Result<String> {
+        trace!("unattached_display.name()");
+        let mut str = sys::types::short_string();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetUnAttachedAssociatedDisplayName(self.0, &mut str) })
+            .and_then(|_| str.convert_raw().map_err(From::from))
+    }
+
+    /// The GPU rendering this display, which may not be the one driving its output.
+    pub fn physical_gpu(&self) -> sys::Result<PhysicalGpu> {
+        trace!("unattached_display.physical_gpu()");
+        let mut handle = Default::default();
+        sys::status_result(unsafe { dispcontrol::NvAPI_GetPhysicalGPUFromUnAttachedDisplay(self.0, &mut handle) })
+            .map(|_| PhysicalGpu(handle))
+    }
+}
+
+/// Fetches the handle at `index` from an `NvAPI_Enum*` function, advancing on success.
+///
+/// `Status::EndEnumeration` or any other error sets `done`.
+fn enumerate_next<H: Default, F: FnOnce(u32, &mut H) -> sys::NvAPI_Status>(index: &mut u32, done: &mut bool, f: F) -> Option<sys::Result<H>> {
+    if *done {
+        return None
+    }
+
+    let mut handle = Default::default();
+    match sys::status_result(f(*index, &mut handle)) {
+        Ok(()) => {
+            *index += 1;
+            Some(Ok(handle))
+        },
+        Err(e) => {
+            *done = true;
+            match e {
+                sys::Status::EndEnumeration => None,
+                e => Some(Err(e)),
+            }
+        },
+    }
+}
+
+/// Enumerates displays until the driver returns `Status::EndEnumeration`.
+///
+/// Any other error is returned once, and ends the enumeration.
+#[derive(Debug, Clone)]
+pub struct DisplayHandles {
+    index: u32,
+    done: bool,
+}
+
+impl Iterator for DisplayHandles {
+    type Item = sys::Result<DisplayHandle>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        enumerate_next(&mut self.index, &mut self.done, |index, handle| {
+            trace!("display.enumerate({})", index);
+            unsafe { dispcontrol::NvAPI_EnumNvidiaDisplayHandle(index, handle) }
+        }).map(|res| res.map(DisplayHandle))
+    }
+}
+
+/// Enumerates unattached displays until the driver returns `Status::EndEnumeration`.
+///
+/// Any other error is returned once, and ends the enumeration.
+#[derive(Debug, Clone)]
+pub struct UnattachedDisplayHandles {
+    index: u32,
+    done: bool,
+}
+
+impl Iterator for UnattachedDisplayHandles {
+    type Item = sys::Result<UnattachedDisplayHandle>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        enumerate_next(&mut self.index, &mut self.done, |index, handle| {
+            trace!("unattached_display.enumerate({})", index);
+            unsafe { dispcontrol::NvAPI_EnumNvidiaUnAttachedDisplayHandle(index, handle) }
+        }).map(|res| res.map(UnattachedDisplayHandle))
+    }
+}
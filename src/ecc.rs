@@ -0,0 +1,102 @@
+use std::convert::Infallible;
+use log::trace;
+use serde::{Serialize, Deserialize};
+use crate::sys::gpu::ecc;
+use crate::sys;
+use crate::types::RawConversion;
+
+pub use sys::gpu::ecc::EccConfiguration;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccStatus {
+    pub supported: bool,
+    /// How changes made by `set_ecc_configuration()` take effect
+    pub configuration: EccConfiguration,
+    /// Whether ECC is currently active
+    pub enabled: bool,
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_STATUS_INFO {
+    type Target = EccStatus;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(EccStatus {
+            supported: self.isSupported.get(),
+            configuration: EccConfiguration::from_raw(self.configurationOptions)?,
+            enabled: self.isEnabled.get(),
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccErrorCounts {
+    /// Corrected errors
+    pub single_bit: u64,
+    /// Uncorrectable errors
+    pub double_bit: u64,
+}
+
+impl EccErrorCounts {
+    pub fn total(&self) -> u64 {
+        self.single_bit.saturating_add(self.double_bit)
+    }
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_ERROR_INFO_COUNTERS {
+    type Target = EccErrorCounts;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(EccErrorCounts {
+            single_bit: self.singleBitErrors,
+            double_bit: self.doubleBitErrors,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccErrors {
+    /// Errors since the last boot
+    pub current: EccErrorCounts,
+    /// Errors since the counters were last reset
+    pub aggregate: EccErrorCounts,
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_ERROR_INFO {
+    type Target = EccErrors;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(EccErrors {
+            current: self.current.convert_raw()?,
+            aggregate: self.aggregate.convert_raw()?,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct EccConfigurationInfo {
+    /// The configuration stored in non-volatile memory, which may be pending a reboot
+    pub enabled: bool,
+    pub enabled_by_default: bool,
+}
+
+impl RawConversion for ecc::NV_GPU_ECC_CONFIGURATION_INFO {
+    type Target = EccConfigurationInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(EccConfigurationInfo {
+            enabled: self.flag_enabled(),
+            enabled_by_default: self.flag_enabled_by_default(),
+        })
+    }
+}
@@ -0,0 +1,303 @@
+use std::{fmt, error};
+use serde::{Serialize, Deserialize};
+use crate::types::{Kilohertz, Range};
+
+const BLOCK_SIZE: usize = 0x80;
+const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+
+const DESCRIPTOR_SERIAL: u8 = 0xff;
+const DESCRIPTOR_NAME: u8 = 0xfc;
+const DESCRIPTOR_RANGE_LIMITS: u8 = 0xfd;
+
+const EXTENSION_CEA: u8 = 0x02;
+const CEA_DATA_BLOCK_EXTENDED: u8 = 7;
+const CEA_EXTENDED_HDR_STATIC_METADATA: u8 = 6;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EdidError {
+    /// Fewer bytes than the base block and its declared extensions
+    Truncated,
+    /// The fixed 8 byte header pattern is missing
+    InvalidHeader,
+    /// The bytes of a 128 byte block don't sum to zero
+    Checksum {
+        block: usize,
+    },
+}
+
+impl fmt::Display for EdidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EdidError::Truncated => write!(f, "EDID truncated"),
+            EdidError::InvalidHeader => write!(f, "invalid EDID header"),
+            EdidError::Checksum { block } => write!(f, "EDID block {} checksum mismatch", block),
+        }
+    }
+}
+
+impl error::Error for EdidError { }
+
+/// A display mode described by an 18 byte detailed timing descriptor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DetailedTiming {
+    pub pixel_clock: Kilohertz,
+    pub horizontal_active: u16,
+    pub horizontal_blanking: u16,
+    pub horizontal_front_porch: u16,
+    pub horizontal_sync_width: u16,
+    pub vertical_active: u16,
+    pub vertical_blanking: u16,
+    pub vertical_front_porch: u16,
+    pub vertical_sync_width: u16,
+    /// Image size in millimetres
+    pub width_mm: u16,
+    pub height_mm: u16,
+    pub interlaced: bool,
+}
+
+impl DetailedTiming {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let pixel_clock = u16::from_le_bytes([data[0], data[1]]);
+        if pixel_clock == 0 {
+            // a display descriptor rather than a timing
+            return None
+        }
+
+        let low_high = |low: u8, high: u8, shift: u8, mask: u8| low as u16 | (((high >> shift) & mask) as u16) << 8;
+
+        Some(DetailedTiming {
+            pixel_clock: Kilohertz(pixel_clock as u32 * 10),
+            horizontal_active: low_high(data[2], data[4], 4, 0xf),
+            horizontal_blanking: low_high(data[3], data[4], 0, 0xf),
+            horizontal_front_porch: low_high(data[8], data[11], 6, 0x3),
+            horizontal_sync_width: low_high(data[9], data[11], 4, 0x3),
+            vertical_active: low_high(data[5], data[7], 4, 0xf),
+            vertical_blanking: low_high(data[6], data[7], 0, 0xf),
+            vertical_front_porch: (data[10] >> 4) as u16 | (((data[11] >> 2) & 0x3) as u16) << 4,
+            vertical_sync_width: (data[10] & 0xf) as u16 | ((data[11] & 0x3) as u16) << 4,
+            width_mm: low_high(data[12], data[14], 4, 0xf),
+            height_mm: low_high(data[13], data[14], 0, 0xf),
+            interlaced: data[17] & 0x80 != 0,
+        })
+    }
+
+    /// Refresh rate in millihertz
+    pub fn refresh_rate(&self) -> u32 {
+        let total = (self.horizontal_active as u64 + self.horizontal_blanking as u64)
+            * (self.vertical_active as u64 + self.vertical_blanking as u64);
+        match total {
+            0 => 0,
+            total => (self.pixel_clock.0 as u64 * 1000 * 1000 / total) as u32,
+        }
+    }
+}
+
+/// The display range limits descriptor, which also describes the
+/// variable refresh rate range of adaptive sync displays.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RangeLimits {
+    /// Vertical refresh rate in Hz
+    pub vertical: Range<u16>,
+    /// Horizontal scan rate in kHz
+    pub horizontal: Range<u16>,
+    pub max_pixel_clock: Option<Kilohertz>,
+}
+
+impl RangeLimits {
+    fn parse(data: &[u8]) -> Self {
+        // EDID 1.4 offsets for rates above 255
+        let offset = |flags: u8| match flags & 0x3 {
+            0x2 => (0, 255),
+            0x3 => (255, 255),
+            _ => (0, 0),
+        };
+        let (v_min, v_max) = offset(data[4]);
+        let (h_min, h_max) = offset(data[4] >> 2);
+
+        RangeLimits {
+            vertical: Range {
+                min: data[5] as u16 + v_min,
+                max: data[6] as u16 + v_max,
+            },
+            horizontal: Range {
+                min: data[7] as u16 + h_min,
+                max: data[8] as u16 + h_max,
+            },
+            max_pixel_clock: match data[9] {
+                0 => None,
+                clock => Some(Kilohertz(clock as u32 * 10000)),
+            },
+        }
+    }
+}
+
+/// The CEA-861.3 HDR static metadata data block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HdrStaticMetadata {
+    pub eotf_sdr: bool,
+    pub eotf_hdr: bool,
+    /// SMPTE ST 2084
+    pub eotf_pq: bool,
+    /// Hybrid Log-Gamma
+    pub eotf_hlg: bool,
+    /// Supported static metadata descriptor types as a bitmask
+    pub metadata_types: u8,
+    /// Desired content max luminance in cd/m²
+    pub max_luminance: Option<f32>,
+    /// Desired content max frame-average luminance in cd/m²
+    pub max_frame_average_luminance: Option<f32>,
+    /// Desired content min luminance in cd/m²
+    pub min_luminance: Option<f32>,
+}
+
+impl HdrStaticMetadata {
+    /// Parses the payload following the extended tag byte.
+    fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 2 {
+            return None
+        }
+
+        let luminance = |cv: u8| 50.0 * 2f32.powf(cv as f32 / 32.0);
+        let optional = |i: usize| data.get(i).cloned().filter(|&cv| cv != 0);
+        let max_luminance = optional(2).map(luminance);
+
+        Some(HdrStaticMetadata {
+            eotf_sdr: data[0] & 0x01 != 0,
+            eotf_hdr: data[0] & 0x02 != 0,
+            eotf_pq: data[0] & 0x04 != 0,
+            eotf_hlg: data[0] & 0x08 != 0,
+            metadata_types: data[1],
+            max_luminance,
+            max_frame_average_luminance: optional(3).map(luminance),
+            min_luminance: match (max_luminance, optional(4)) {
+                (Some(max), Some(cv)) => Some(max * (cv as f32 / 255.0).powi(2) / 100.0),
+                _ => None,
+            },
+        })
+    }
+}
+
+/// A parsed EDID 1.x base block and its CEA-861 extensions.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edid {
+    /// Three letter PNP ID
+    pub manufacturer: String,
+    pub product_code: u16,
+    pub serial_number: u32,
+    /// Week of manufacture, if specified
+    pub week: Option<u8>,
+    /// Year of manufacture, or the model year if `week` is `None`
+    pub year: u16,
+    /// `(version, revision)`, such as `(1, 4)`
+    pub version: (u8, u8),
+    /// Display product name descriptor
+    pub name: Option<String>,
+    /// Display product serial number descriptor
+    pub serial: Option<String>,
+    /// Detailed timings from the base block followed by those of any extensions,
+    /// the first being the preferred mode
+    pub detailed_timings: Vec<DetailedTiming>,
+    pub range_limits: Option<RangeLimits>,
+    pub hdr_static_metadata: Option<HdrStaticMetadata>,
+}
+
+impl Edid {
+    /// Parses and validates the checksums of the base block and all extensions it declares.
+    pub fn parse(data: &[u8]) -> Result<Self, EdidError> {
+        if data.len() < BLOCK_SIZE {
+            return Err(EdidError::Truncated)
+        }
+        if data[..HEADER.len()] != HEADER {
+            return Err(EdidError::InvalidHeader)
+        }
+
+        let blocks = data[0x7e] as usize + 1;
+        if data.len() < blocks * BLOCK_SIZE {
+            return Err(EdidError::Truncated)
+        }
+        let blocks: Vec<_> = data.chunks(BLOCK_SIZE).take(blocks).collect();
+        for (i, block) in blocks.iter().enumerate() {
+            if block.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) != 0 {
+                return Err(EdidError::Checksum { block: i })
+            }
+        }
+
+        let base = blocks[0];
+        let manufacturer = u16::from_be_bytes([base[8], base[9]]);
+        let mut edid = Edid {
+            manufacturer: [10, 5, 0].iter()
+                .map(|&shift| (b'A' - 1 + ((manufacturer >> shift) & 0x1f) as u8) as char)
+                .collect(),
+            product_code: u16::from_le_bytes([base[10], base[11]]),
+            serial_number: u32::from_le_bytes([base[12], base[13], base[14], base[15]]),
+            week: match base[16] {
+                0 | 0xff => None,
+                week => Some(week),
+            },
+            year: base[17] as u16 + 1990,
+            version: (base[18], base[19]),
+            name: None,
+            serial: None,
+            detailed_timings: Vec::new(),
+            range_limits: None,
+            hdr_static_metadata: None,
+        };
+
+        for descriptor in base[0x36..0x7e].chunks(18) {
+            match DetailedTiming::parse(descriptor) {
+                Some(timing) => edid.detailed_timings.push(timing),
+                None => match descriptor[3] {
+                    DESCRIPTOR_NAME => edid.name = Some(descriptor_string(descriptor)),
+                    DESCRIPTOR_SERIAL => edid.serial = Some(descriptor_string(descriptor)),
+                    DESCRIPTOR_RANGE_LIMITS => edid.range_limits = Some(RangeLimits::parse(descriptor)),
+                    _ => (),
+                },
+            }
+        }
+
+        for block in blocks[1..].iter().filter(|block| block[0] == EXTENSION_CEA) {
+            edid.parse_cea(block);
+        }
+
+        Ok(edid)
+    }
+
+    fn parse_cea(&mut self, block: &[u8]) {
+        // detailed timings start at this offset, following the data block collection
+        let dtd_offset = (block[2] as usize).min(0x7f);
+
+        if block[1] >= 3 && dtd_offset > 4 {
+            let mut data = &block[4..dtd_offset];
+            while let Some((&header, rest)) = data.split_first() {
+                let len = (header & 0x1f) as usize;
+                if len > rest.len() {
+                    break
+                }
+                let (payload, rest) = rest.split_at(len);
+                data = rest;
+
+                if header >> 5 == CEA_DATA_BLOCK_EXTENDED {
+                    if let Some((&CEA_EXTENDED_HDR_STATIC_METADATA, payload)) = payload.split_first() {
+                        self.hdr_static_metadata = HdrStaticMetadata::parse(payload);
+                    }
+                }
+            }
+        }
+
+        if dtd_offset >= 4 {
+            self.detailed_timings.extend(block[dtd_offset..0x7f].chunks_exact(18)
+                .map_while(DetailedTiming::parse)
+            );
+        }
+    }
+}
+
+fn descriptor_string(descriptor: &[u8]) -> String {
+    let text = &descriptor[5..];
+    let text = text.iter().position(|&b| b == b'\n').map(|end| &text[..end]).unwrap_or(text);
+    String::from_utf8_lossy(text).trim_end().into()
+}
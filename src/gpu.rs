@@ -1,20 +1,30 @@
-use std::{ptr, fmt};
+use std::{ptr, fmt, error};
+use std::str::FromStr;
 use std::convert::Infallible;
 use log::trace;
 use serde::{Serialize, Deserialize};
-use crate::sys::gpu::{self, pstate, clock, power, cooler, thermal, display};
+use crate::sys::gpu::{self, pstate, clock, power, cooler, thermal, illum, ecc, display};
 use crate::sys::{self, driverapi, i2c};
-use crate::types::{Kibibytes, KilohertzDelta, Kilohertz2Delta, Microvolts, Percentage, Percentage1000, RawConversion};
-use crate::thermal::CoolerLevel;
-use crate::clock::{ClockDomain, VfpMask};
-use crate::pstate::PState;
+use crate::types::{Kibibytes, KilohertzDelta, Kilohertz2Delta, Microvolts, MicrovoltsDelta, Percentage, Percentage1000, Range, RawConversion, call_versioned, fallback_version, optional};
+use crate::thermal::{CoolerLevel, CoolerPolicy, FanCoolerControl};
+use crate::illum::{IlluminationAttribute, IllumZoneControl};
+use crate::ecc::{EccStatus, EccErrors, EccConfigurationInfo};
+use crate::clock::{AllClocks, ClockDomain, VfpMask};
+use crate::pstate::{PState, PStates, PStateClientLimits, VoltageDomain};
+use crate::identity::{BoardSerial, GpuIdentity, Luid};
 
 #[derive(Debug)]
-pub struct PhysicalGpu(sys::handles::NvPhysicalGpuHandle);
+pub struct PhysicalGpu(pub(crate) sys::handles::NvPhysicalGpuHandle);
 
 unsafe impl Send for PhysicalGpu { }
 
-pub use sys::gpu::{SystemType, PerformanceDecreaseReason};
+/// One or more physical GPUs acting in concert, such as an SLI group.
+#[derive(Debug)]
+pub struct LogicalGpu(sys::handles::NvLogicalGpuHandle);
+
+unsafe impl Send for LogicalGpu { }
+
+pub use sys::gpu::{SystemType, GpuType, BusType, PerformanceDecreaseReason, ArchitectureId, ChipRevision};
 pub use sys::gpu::private::{RamType, RamMaker, Foundry, VendorId as Vendor};
 pub use sys::gpu::clock::ClockFrequencyType;
 pub use sys::gpu::display::{ConnectedIdsFlags, DisplayIdsFlags, MonitorConnectorType};
@@ -36,6 +46,15 @@ impl PhysicalGpu {
         }
     }
 
+    pub fn logical_gpu(&self) -> sys::Result<LogicalGpu> {
+        trace!("gpu.logical_gpu()");
+        let mut handle = Default::default();
+        unsafe {
+            sys::status_result(gpu::NvAPI_GetLogicalGPUFromPhysicalGPU(self.0, &mut handle))
+                .map(|_| LogicalGpu(handle))
+        }
+    }
+
     pub fn tachometer(&self) -> sys::Result<u32> {
         trace!("gpu.tachometer()");
         let mut out = 0;
@@ -54,6 +73,31 @@ impl PhysicalGpu {
         }
     }
 
+    pub fn arch_info(&self) -> sys::Result<ArchInfo> {
+        trace!("gpu.arch_info()");
+        call_versioned(gpu::NV_GPU_ARCH_INFO::zeroed(), |data| unsafe { gpu::NvAPI_GPU_GetArchInfo(self.0, data) })
+            .and_then(|data| data.convert_raw().map_err(Into::into))
+    }
+
+    /// Ray tracing and tensor core counts are only reported by newer drivers.
+    pub fn gpu_info(&self) -> sys::Result<GpuHardwareInfo> {
+        trace!("gpu.gpu_info()");
+        call_versioned(gpu::NV_GPU_INFO_V2::zeroed(), |data| unsafe { gpu::NvAPI_GPU_GetGPUInfo(self.0, data) })
+            .and_then(|data| data.convert_raw().map_err(Into::into))
+            .or_else(fallback_version(|| call_versioned(gpu::NV_GPU_INFO_V1::zeroed(), |data| unsafe {
+                gpu::NvAPI_GPU_GetGPUInfo(self.0, data as *mut gpu::NV_GPU_INFO_V1 as *mut _)
+            }).and_then(|data| data.convert_raw().map_err(Into::into))))
+    }
+
+    /// Falls back to decoding the `short_name()` codename on drivers without `arch_info()`.
+    pub fn architecture(&self) -> sys::Result<Architecture> {
+        trace!("gpu.architecture()");
+        match optional(self.arch_info())? {
+            Some(info) if info.architecture != Architecture::Unknown => Ok(info.architecture),
+            _ => self.short_name().map(|name| Architecture::from_codename(&name)),
+        }
+    }
+
     pub fn full_name(&self) -> sys::Result<String> {
         trace!("gpu.full_name()");
         let mut str = sys::types::short_string();
@@ -72,6 +116,35 @@ impl PhysicalGpu {
         }
     }
 
+    pub fn vbios_revision(&self) -> sys::Result<u32> {
+        trace!("gpu.vbios_revision()");
+        let mut value = 0;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetVbiosRevision(self.0, &mut value))
+                .map(|_| value)
+        }
+    }
+
+    pub fn vbios_oem_revision(&self) -> sys::Result<u32> {
+        trace!("gpu.vbios_oem_revision()");
+        let mut value = 0;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetVbiosOEMRevision(self.0, &mut value))
+                .map(|_| value)
+        }
+    }
+
+    /// Falls back to parsing `vbios_version_string()` if the revisions aren't available,
+    /// failing with `Status::NotSupported` if it can't be parsed.
+    pub fn vbios_version(&self) -> sys::Result<VbiosVersion> {
+        match (optional(self.vbios_revision())?, optional(self.vbios_oem_revision())?) {
+            // only the low byte of the OEM revision is part of the displayed version
+            (Some(revision), Some(oem_revision)) => Ok(VbiosVersion::new(revision, oem_revision as u8)),
+            _ => self.vbios_version_string()?.parse()
+                .map_err(|_| sys::Status::NotSupported),
+        }
+    }
+
     pub fn driver_model(&self) -> sys::Result<DriverModel> {
         trace!("gpu.driver_model()");
         let mut value = 0;
@@ -99,6 +172,93 @@ impl PhysicalGpu {
         }
     }
 
+    pub fn bus_id(&self) -> sys::Result<u32> {
+        trace!("gpu.bus_id()");
+        let mut value = 0;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetBusId(self.0, &mut value))
+                .map(|_| value)
+        }
+    }
+
+    pub fn bus_slot_id(&self) -> sys::Result<u32> {
+        trace!("gpu.bus_slot_id()");
+        let mut value = 0;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetBusSlotId(self.0, &mut value))
+                .map(|_| value)
+        }
+    }
+
+    pub fn bus_type(&self) -> sys::Result<BusType> {
+        trace!("gpu.bus_type()");
+        let mut ty = gpu::NVAPI_GPU_BUS_TYPE_UNDEFINED;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetBusType(self.0, &mut ty))
+                .and_then(|_| gpu::BusType::from_raw(ty).map_err(From::from))
+        }
+    }
+
+    pub fn irq(&self) -> sys::Result<u32> {
+        trace!("gpu.irq()");
+        let mut value = 0;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetIRQ(self.0, &mut value))
+                .map(|_| value)
+        }
+    }
+
+    /// The number of PCIe lanes currently in use, which may drop below the slot width to save power.
+    pub fn pcie_downstream_width(&self) -> sys::Result<u32> {
+        trace!("gpu.pcie_downstream_width()");
+        let mut value = 0;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetCurrentPCIEDownstreamWidth(self.0, &mut value))
+                .map(|_| value)
+        }
+    }
+
+    pub fn pci_location(&self) -> sys::Result<PciLocation> {
+        Ok(PciLocation {
+            domain: 0,
+            bus: self.bus_id()?,
+            slot: self.bus_slot_id()?,
+        })
+    }
+
+    /// Where the GPU is attached, omitting any details the bus or driver doesn't report.
+    pub fn bus_info(&self) -> sys::Result<BusInfo> {
+        let bus_type = optional(self.bus_type())?.unwrap_or(BusType::Undefined);
+
+        Ok(BusInfo {
+            bus_type,
+            location: optional(self.pci_location())?,
+            irq: optional(self.irq())?,
+            link_width: match bus_type {
+                BusType::PciExpress => optional(self.pcie_downstream_width())?,
+                _ => None,
+            },
+        })
+    }
+
+    pub fn board_serial(&self) -> sys::Result<BoardSerial> {
+        self.board_number().map(BoardSerial)
+    }
+
+    pub fn identity(&self) -> sys::Result<GpuIdentity> {
+        trace!("gpu.identity()");
+        GpuIdentity::query(self)
+    }
+
+    /// There is no `uuid()` counterpart: `NvAPI_GPU_GetGPUUUID` has no known interface ID,
+    /// so match CUDA and NVML devices by this LUID or by `bus_info()` instead.
+    pub fn adapter_luid(&self) -> sys::Result<Luid> {
+        trace!("gpu.adapter_luid()");
+        let mut luid = sys::types::LUID::zeroed();
+        sys::status_result(unsafe { gpu::NvAPI_GPU_GetAdapterIdFromPhysicalGpu(self.0, &mut luid) })
+            .map(|_| luid.into())
+    }
+
     pub fn board_number(&self) -> sys::Result<[u8; 0x10]> {
         trace!("gpu.board_number()");
         let mut data = gpu::NV_BOARD_INFO::zeroed();
@@ -118,6 +278,24 @@ impl PhysicalGpu {
         }
     }
 
+    pub fn gpu_type(&self) -> sys::Result<GpuType> {
+        trace!("gpu.gpu_type()");
+        let mut ty = gpu::NV_SYSTEM_TYPE_GPU_UNKNOWN;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetGPUType(self.0, &mut ty))
+                .and_then(|_| gpu::GpuType::from_raw(ty).map_err(From::from))
+        }
+    }
+
+    pub fn is_quadro(&self) -> sys::Result<bool> {
+        trace!("gpu.is_quadro()");
+        let mut value = 0;
+        unsafe {
+            sys::status_result(gpu::NvAPI_GPU_GetQuadroStatus(self.0, &mut value))
+                .map(|_| value != 0)
+        }
+    }
+
     pub fn core_count(&self) -> sys::Result<u32> {
         trace!("gpu.core_count()");
         let mut value = 0;
@@ -201,11 +379,15 @@ impl PhysicalGpu {
 
     pub fn memory_info(&self) -> sys::Result<MemoryInfo> {
         trace!("gpu.memory_info()");
-        let mut data = driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO::zeroed();
-        data.version = driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER;
 
-        sys::status_result(unsafe { driverapi::NvAPI_GPU_GetMemoryInfo(self.0, &mut data) })
-            .and_then(|_| data.convert_raw().map_err(Into::into))
+        call_versioned(driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V3::zeroed(), |data| unsafe { driverapi::NvAPI_GPU_GetMemoryInfo(self.0, data) })
+            .and_then(|data| data.convert_raw().map_err(Into::into))
+            .or_else(fallback_version(|| call_versioned(driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V2::zeroed(), |data| unsafe {
+                driverapi::NvAPI_GPU_GetMemoryInfo(self.0, data as *mut driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V2 as *mut _)
+            }).and_then(|data| data.convert_raw().map_err(Into::into))))
+            .or_else(fallback_version(|| call_versioned(driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V1::zeroed(), |data| unsafe {
+                driverapi::NvAPI_GPU_GetMemoryInfo(self.0, data as *mut driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V1 as *mut _)
+            }).and_then(|data| data.convert_raw().map_err(Into::into))))
     }
 
     pub fn clock_frequencies(&self, clock_type: ClockFrequencyType) -> sys::Result<ClockFrequencies> {
@@ -218,6 +400,18 @@ impl PhysicalGpu {
             .and_then(|_| clocks.convert_raw().map_err(Into::into))
     }
 
+    /// Private and deprecated, use `clock_frequencies()` instead.
+    ///
+    /// The indices of the video clock and of the default and boost tables are unverified.
+    pub fn all_clocks(&self) -> sys::Result<AllClocks> {
+        trace!("gpu.all_clocks()");
+        let mut clocks = clock::private::NV_CLOCKS_INFO::zeroed();
+        clocks.version = clock::private::NV_CLOCKS_INFO_VER;
+
+        sys::status_result(unsafe { clock::private::NvAPI_GPU_GetAllClocks(self.0, &mut clocks) })
+            .and_then(|_| clocks.convert_raw().map_err(From::from))
+    }
+
     pub fn current_pstate(&self) -> sys::Result<PState> {
         trace!("gpu.current_pstate()");
         let mut pstate = 0;
@@ -229,22 +423,38 @@ impl PhysicalGpu {
 
     pub fn pstates(&self) -> sys::Result<<pstate::NV_GPU_PERF_PSTATES20_INFO as RawConversion>::Target> {
         trace!("gpu.pstates()");
-        let mut info = pstate::NV_GPU_PERF_PSTATES20_INFO::zeroed();
-        info.version = pstate::NV_GPU_PERF_PSTATES20_INFO_VER;
 
-        sys::status_result(unsafe { pstate::NvAPI_GPU_GetPstates20(self.0, &mut info) })
-            .and_then(|_| info.convert_raw().map_err(From::from))
+        call_versioned(pstate::NV_GPU_PERF_PSTATES20_INFO_V2::zeroed(), |info| unsafe { pstate::NvAPI_GPU_GetPstates20(self.0, info) })
+            .and_then(|info| info.convert_raw().map_err(From::from))
+            .or_else(fallback_version(|| call_versioned(pstate::NV_GPU_PERF_PSTATES20_INFO_V1::zeroed(), |info| unsafe {
+                pstate::NvAPI_GPU_GetPstates20(self.0, info as *mut pstate::NV_GPU_PERF_PSTATES20_INFO_V1 as *mut _)
+            }).and_then(|info| info.convert_raw().map_err(From::from))))
     }
 
+    /// Deprecated, for GPUs older than Kepler that don't support `pstates()`.
+    ///
+    /// `set_pstates()` applies its deltas to the default clocks reported here on these GPUs.
+    pub fn pstates_legacy(&self) -> sys::Result<PStates> {
+        trace!("gpu.pstates_legacy()");
+        let info = |flags: u32| call_versioned(pstate::NV_GPU_PERF_PSTATES_INFO::zeroed(), |info| unsafe {
+            pstate::NvAPI_GPU_GetPstatesInfoEx(self.0, info, flags)
+        });
+
+        let current = info(0)?;
+        let defaults = info(pstate::NV_GPU_PERF_PSTATES_INFO_INPUT_FLAG_DEFAULT)?;
+        PStates::from_raw_legacy(&current, &defaults).map_err(From::from)
+    }
+
+    /// Falls back to the legacy perf clocks on GPUs older than Kepler.
     pub fn set_pstates<I: Iterator<Item=(PState, ClockDomain, KilohertzDelta)>>(&self, deltas: I) -> sys::Result<()> {
         trace!("gpu.set_pstates()");
         use std::collections::BTreeMap;
 
-        let mut info = pstate::NV_GPU_PERF_PSTATES20_INFO::zeroed();
-        info.version = pstate::NV_GPU_PERF_PSTATES20_INFO_VER;
+        let deltas: Vec<_> = deltas.collect();
+        let mut info = pstate::NV_GPU_PERF_PSTATES20_INFO_V2::zeroed();
 
         let mut map: BTreeMap<PState, (usize, usize)> = Default::default();
-        for (pstate, clock, delta) in deltas {
+        for &(pstate, clock, delta) in &deltas {
             trace!("gpu.set_pstate({:?}, {:?}, {:?})", pstate, clock, delta);
             let pstates = map.len();
             let map = map.entry(pstate).or_insert((pstates, 0));
@@ -258,7 +468,132 @@ impl PhysicalGpu {
         info.numPstates = map.len() as _;
         info.numClocks = map.iter().map(|v| (v.1).1).max().unwrap_or(0) as _;
 
-        sys::status_result(unsafe { pstate::private::NvAPI_GPU_SetPstates20(self.0, &info) })
+        self.set_pstates20(info).or_else(|e| match e {
+            sys::Status::NotSupported | sys::Status::NoImplementation => self.set_perf_clocks(&deltas),
+            e => Err(e),
+        })
+    }
+
+    /// Sets pstate clocks to their default frequencies as reported by `pstates_legacy()`, offset by `deltas`.
+    fn set_perf_clocks(&self, deltas: &[(PState, ClockDomain, KilohertzDelta)]) -> sys::Result<()> {
+        trace!("gpu.set_perf_clocks({:?})", deltas);
+        let defaults = call_versioned(pstate::NV_GPU_PERF_PSTATES_INFO::zeroed(), |info| unsafe {
+            pstate::NvAPI_GPU_GetPstatesInfoEx(self.0, info, pstate::NV_GPU_PERF_PSTATES_INFO_INPUT_FLAG_DEFAULT)
+        })?;
+        let mut clocks = call_versioned(pstate::private::NV_GPU_PERF_CLOCKS::zeroed(), |clocks| unsafe {
+            pstate::private::NvAPI_GPU_GetPerfClocks(self.0, clocks)
+        })?;
+
+        let (levels, domains) = (clocks.numLevels as usize, clocks.numDomains as usize);
+        for &(pstate, clock, delta) in deltas {
+            let default = defaults.pstates.iter().take(defaults.numPstates as usize)
+                .find(|p| p.pstateId == pstate.raw())
+                .and_then(|p| p.clocks.iter().take(defaults.numClocks as usize).find(|c| c.domainId == clock.raw()))
+                .ok_or(sys::Status::InvalidArgument)?;
+            let entry = clocks.levels.iter_mut().take(levels)
+                .find(|l| l.pstateId == pstate.raw())
+                .and_then(|l| l.domains.iter_mut().take(domains).find(|d| d.domainId == clock.raw()))
+                .ok_or(sys::Status::InvalidArgument)?;
+            entry.freq_kHz = default.freq.checked_add_signed(delta.0).ok_or(sys::Status::InvalidArgument)?;
+        }
+
+        call_versioned(clocks, |clocks| unsafe { pstate::private::NvAPI_GPU_SetPerfClocks(self.0, clocks) }).map(drop)
+    }
+
+    /// Sets the base voltage deltas of individual pstates.
+    pub fn set_pstate_base_voltages<I: Iterator<Item=(PState, VoltageDomain, MicrovoltsDelta)>>(&self, deltas: I) -> sys::Result<()> {
+        trace!("gpu.set_pstate_base_voltages()");
+        use std::collections::BTreeMap;
+
+        let mut info = pstate::NV_GPU_PERF_PSTATES20_INFO_V2::zeroed();
+
+        let mut map: BTreeMap<PState, (usize, usize)> = Default::default();
+        for (pstate, domain, delta) in deltas {
+            trace!("gpu.set_pstate_base_voltage({:?}, {:?}, {:?})", pstate, domain, delta);
+            let pstates = map.len();
+            let map = map.entry(pstate).or_insert((pstates, 0));
+            let entry = &mut info.pstates[map.0];
+            entry.pstateId = pstate.raw();
+            let entry = entry.baseVoltages.get_mut(map.1).ok_or(sys::Status::ArgumentExceedMaxSize)?;
+            entry.domainId = domain.raw();
+            entry.voltDelta_uV.value = delta.0;
+            map.1 += 1;
+        }
+        info.numPstates = map.len() as _;
+        info.numBaseVoltages = map.iter().map(|v| (v.1).1).max().unwrap_or(0) as _;
+
+        self.set_pstates20(info)
+    }
+
+    /// Sets the overvolt deltas reported by `pstates().overvolt`.
+    pub fn set_overvolt<I: Iterator<Item=(VoltageDomain, MicrovoltsDelta)>>(&self, deltas: I) -> sys::Result<()> {
+        trace!("gpu.set_overvolt()");
+        let mut info = pstate::NV_GPU_PERF_PSTATES20_INFO_V2::zeroed();
+
+        for (domain, delta) in deltas {
+            trace!("gpu.set_overvolt({:?}, {:?})", domain, delta);
+            let entry = info.voltages.get_mut(info.numVoltages as usize).ok_or(sys::Status::ArgumentExceedMaxSize)?;
+            entry.domainId = domain.raw();
+            entry.voltDelta_uV.value = delta.0;
+            info.numVoltages += 1;
+        }
+
+        self.set_pstates20(info)
+    }
+
+    fn set_pstates20(&self, info: pstate::NV_GPU_PERF_PSTATES20_INFO_V2) -> sys::Result<()> {
+        call_versioned(info, |info| unsafe { pstate::private::NvAPI_GPU_SetPstates20(self.0, info) }).map(drop)
+            .or_else(|e| match e {
+                // The V1 layout is a prefix of V2, so the same settings apply to either unless overvolting
+                sys::Status::IncompatibleStructVersion if info.numVoltages == 0 => call_versioned(info.v1, |info| unsafe {
+                    pstate::private::NvAPI_GPU_SetPstates20(self.0, info as *const pstate::NV_GPU_PERF_PSTATES20_INFO_V1 as *const _)
+                }).map(drop),
+                e => Err(e),
+            })
+    }
+
+    /// Private and undocumented.
+    pub fn pstate_client_limits(&self) -> sys::Result<PStateClientLimits> {
+        trace!("gpu.pstate_client_limits()");
+        let limit = |id: pstate::private::PstateClientLimit| -> sys::Result<PState> {
+            let mut pstate = 0;
+            sys::status_result(unsafe { pstate::private::NvAPI_GPU_GetPstateClientLimits(self.0, id.raw(), &mut pstate) })?;
+            PState::from_raw(pstate).map_err(From::from)
+        };
+
+        Ok(PStateClientLimits {
+            hard: limit(pstate::private::PstateClientLimit::Hard)?,
+            soft: limit(pstate::private::PstateClientLimit::Soft)?,
+        })
+    }
+
+    /// Private and undocumented.
+    ///
+    /// `pstates.min` becomes the hard limit and `pstates.max` the soft limit.
+    /// Combined with `enable_dynamic_pstates(false)`, `set_pstate_client_limits(Range::from_scalar(PState::P0))`
+    /// pins the GPU to P0 until both are restored.
+    pub fn set_pstate_client_limits(&self, pstates: Range<PState>) -> sys::Result<()> {
+        trace!("gpu.set_pstate_client_limits({:?})", pstates);
+        let limit = |id: pstate::private::PstateClientLimit, pstate: PState| {
+            sys::status_result(unsafe { pstate::private::NvAPI_GPU_SetPstateClientLimits(self.0, id.raw(), pstate.raw()) })
+        };
+
+        limit(pstate::private::PstateClientLimit::Hard, pstates.min)?;
+        limit(pstate::private::PstateClientLimit::Soft, pstates.max)
+    }
+
+    /// Private and undocumented.
+    pub fn enable_dynamic_pstates(&self, enable: bool) -> sys::Result<()> {
+        trace!("gpu.enable_dynamic_pstates({:?})", enable);
+
+        sys::status_result(unsafe { pstate::private::NvAPI_GPU_EnableDynamicPstates(self.0, enable as u32) })
+    }
+
+    /// Private and undocumented.
+    pub fn enable_overclocked_pstates(&self, enable: bool) -> sys::Result<()> {
+        trace!("gpu.enable_overclocked_pstates({:?})", enable);
+
+        sys::status_result(unsafe { pstate::private::NvAPI_GPU_EnableOverclockedPstates(self.0, enable as u32) })
     }
 
     pub fn dynamic_pstates_info(&self) -> sys::Result<Utilizations> {
@@ -396,8 +731,15 @@ impl PhysicalGpu {
         sys::status_result(unsafe { power::private::NvAPI_GPU_ClientVoltRailsSetControl(self.0, &data) })
     }
 
-    pub fn power_usage(&self) -> sys::Result<<power::private::NV_GPU_POWER_TOPO as RawConversion>::Target> {
+    pub fn power_usage(&self) -> sys::Result<Vec<Percentage1000>> {
         trace!("gpu.power_usage()");
+
+        self.power_topology().map(|entries| entries.into_iter().map(|entry| entry.power).collect())
+    }
+
+    /// Power draw of each measured channel.
+    pub fn power_topology(&self) -> sys::Result<<power::private::NV_GPU_POWER_TOPO as RawConversion>::Target> {
+        trace!("gpu.power_topology()");
         let mut data = power::private::NV_GPU_POWER_TOPO::zeroed();
         data.version = power::private::NV_GPU_POWER_TOPO_VER;
 
@@ -405,6 +747,16 @@ impl PhysicalGpu {
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
+    /// Describes each channel reported by `power_topology()`, in the same order.
+    pub fn power_topology_info(&self) -> sys::Result<<power::private::NV_GPU_POWER_TOPO_INFO as RawConversion>::Target> {
+        trace!("gpu.power_topology_info()");
+        let mut data = power::private::NV_GPU_POWER_TOPO_INFO::zeroed();
+        data.version = power::private::NV_GPU_POWER_TOPO_INFO_VER;
+
+        sys::status_result(unsafe { power::private::NvAPI_GPU_ClientPowerTopologyGetInfo(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
     pub fn power_limit_info(&self) -> sys::Result<<power::private::NV_GPU_POWER_INFO as RawConversion>::Target> {
         trace!("gpu.power_limit_info()");
         let mut data = power::private::NV_GPU_POWER_INFO::zeroed();
@@ -464,6 +816,35 @@ impl PhysicalGpu {
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
+    /// Reads the sensors selected by `mask`, which must all be supported by the GPU.
+    pub fn thermal_sensors(&self, mask: u32) -> sys::Result<<thermal::private::NV_GPU_THERMAL_SENSORS as RawConversion>::Target> {
+        trace!("gpu.thermal_sensors({:#x})", mask);
+        let mut data = thermal::private::NV_GPU_THERMAL_SENSORS::zeroed();
+        data.version = thermal::private::NV_GPU_THERMAL_SENSORS_VER;
+        data.mask = mask;
+
+        sys::status_result(unsafe { thermal::private::NvAPI_GPU_ThermalGetSensors(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Probes the mask of sensors supported by `thermal_sensors()`, which are numbered contiguously.
+    ///
+    /// Each probe requests a contiguous `(1 << n) - 1` mask, so this takes up to 32 calls.
+    pub fn thermal_sensors_mask(&self) -> sys::Result<u32> {
+        trace!("gpu.thermal_sensors_mask()");
+        let mut supported = 0;
+        for count in 1..=thermal::private::NVAPI_MAX_THERMAL_SENSORS {
+            let mask = !0u32 >> (thermal::private::NVAPI_MAX_THERMAL_SENSORS - count);
+            match self.thermal_sensors(mask) {
+                Ok(..) => supported = mask,
+                Err(e) if count == 1 => return Err(e),
+                Err(..) => break,
+            }
+        }
+
+        Ok(supported)
+    }
+
     pub fn set_thermal_limit<I: Iterator<Item=crate::thermal::ThermalLimit>>(&self, value: I) -> sys::Result<()> {
         trace!("gpu.set_thermal_limit()");
         let mut data = thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS::zeroed();
@@ -488,23 +869,180 @@ impl PhysicalGpu {
             .and_then(|_| data.convert_raw().map_err(From::from))
     }
 
+    /// Falls back to the client fan cooler API on GPUs that no longer support the legacy one.
+    ///
+    /// `Manual` levels are applied as is, while any other policy returns the fan to automatic control.
     pub fn set_cooler_levels<I: Iterator<Item=CoolerLevel>>(&self, index: Option<u32>, values: I) -> sys::Result<()> {
         trace!("gpu.set_cooler_levels({:?})", index);
+        let values: Vec<_> = values.collect();
         let mut data = cooler::private::NV_GPU_SETCOOLER_LEVEL::zeroed();
         data.version = cooler::private::NV_GPU_SETCOOLER_LEVEL_VER;
-        for (entry, level) in data.cooler.iter_mut().zip(values) {
+        for (entry, level) in data.cooler.iter_mut().zip(&values) {
             trace!("gpu.set_cooler_level({:?})", level);
             entry.currentLevel = level.level.0;
             entry.currentPolicy = level.policy.raw();
         }
 
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_SetCoolerLevels(self.0, index.unwrap_or(cooler::private::NVAPI_COOLER_TARGET_ALL as _), &mut data) })
+        let status = sys::status_result(unsafe { cooler::private::NvAPI_GPU_SetCoolerLevels(self.0, index.unwrap_or(cooler::private::NVAPI_COOLER_TARGET_ALL as _), &data) });
+        match optional(status)? {
+            Some(()) => Ok(()),
+            None => self.update_fan_coolers(|i, entry| {
+                let level = match index {
+                    Some(index) if index as usize == i => values.first(),
+                    Some(..) => None,
+                    None => values.get(i),
+                };
+                if let Some(level) = level {
+                    entry.level = level.level.0;
+                    entry.controlMode = match level.policy {
+                        CoolerPolicy::Manual => cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE_MANUAL,
+                        _ => cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE_AUTO,
+                    };
+                }
+            }),
+        }
     }
 
+    /// Falls back to returning fans to automatic control through the client fan cooler API.
     pub fn restore_cooler_settings(&self, index: &[u32]) -> sys::Result<()> {
         trace!("gpu.restore_cooler_settings({:?})", index);
         let ptr = if index.is_empty() { ptr::null() } else { index.as_ptr() };
-        sys::status_result(unsafe { cooler::private::NvAPI_GPU_RestoreCoolerSettings(self.0, ptr, index.len() as u32) })
+        let status = sys::status_result(unsafe { cooler::private::NvAPI_GPU_RestoreCoolerSettings(self.0, ptr, index.len() as u32) });
+        match optional(status)? {
+            Some(()) => Ok(()),
+            None => self.update_fan_coolers(|i, entry| if index.is_empty() || index.contains(&(i as u32)) {
+                entry.controlMode = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE_AUTO;
+            }),
+        }
+    }
+
+    fn update_fan_coolers<F: FnMut(usize, &mut cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_ENTRY)>(&self, mut f: F) -> sys::Result<()> {
+        let mut data = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL::zeroed();
+        data.version = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER;
+        sys::status_result(unsafe { cooler::private::NvAPI_GPU_ClientFanCoolersGetControl(self.0, &mut data) })?;
+
+        let count = data.count as usize;
+        for (i, entry) in data.coolers.iter_mut().take(count).enumerate() {
+            f(i, entry);
+        }
+
+        sys::status_result(unsafe { cooler::private::NvAPI_GPU_ClientFanCoolersSetControl(self.0, &data) })
+    }
+
+    /// Turing and newer
+    pub fn fan_cooler_info(&self) -> sys::Result<<cooler::private::NV_GPU_CLIENT_FAN_COOLERS_INFO as RawConversion>::Target> {
+        trace!("gpu.fan_cooler_info()");
+        let mut data = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_INFO::zeroed();
+        data.version = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_INFO_VER;
+
+        sys::status_result(unsafe { cooler::private::NvAPI_GPU_ClientFanCoolersGetInfo(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Turing and newer
+    pub fn fan_cooler_status(&self) -> sys::Result<<cooler::private::NV_GPU_CLIENT_FAN_COOLERS_STATUS as RawConversion>::Target> {
+        trace!("gpu.fan_cooler_status()");
+        let mut data = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_STATUS::zeroed();
+        data.version = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_STATUS_VER;
+
+        sys::status_result(unsafe { cooler::private::NvAPI_GPU_ClientFanCoolersGetStatus(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Turing and newer
+    pub fn fan_cooler_control(&self) -> sys::Result<<cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL as RawConversion>::Target> {
+        trace!("gpu.fan_cooler_control()");
+        let mut data = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL::zeroed();
+        data.version = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER;
+
+        sys::status_result(unsafe { cooler::private::NvAPI_GPU_ClientFanCoolersGetControl(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Turing and newer
+    pub fn set_fan_cooler_control<I: Iterator<Item=FanCoolerControl>>(&self, values: I) -> sys::Result<()> {
+        trace!("gpu.set_fan_cooler_control()");
+        let mut data = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL::zeroed();
+        data.version = cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER;
+        for value in values {
+            trace!("gpu.set_fan_cooler_control({:?})", value);
+            let entry = data.coolers.get_mut(data.count as usize).ok_or(sys::Status::ArgumentExceedMaxSize)?;
+            entry.coolerId = value.id;
+            entry.level = value.level.0;
+            entry.controlMode = value.mode.raw();
+            data.count += 1;
+        }
+
+        sys::status_result(unsafe { cooler::private::NvAPI_GPU_ClientFanCoolersSetControl(self.0, &data) })
+    }
+
+    pub fn illumination_supported(&self, attribute: IlluminationAttribute) -> sys::Result<bool> {
+        trace!("gpu.illumination_supported({:?})", attribute);
+        let mut data = illum::NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM::zeroed();
+        data.version = illum::NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_VER;
+        data.hPhysicalGpu = self.0;
+        data.Attribute = attribute.raw();
+
+        sys::status_result(unsafe { illum::NvAPI_GPU_QueryIlluminationSupport(&mut data) })
+            .map(|_| data.bSupported.get())
+    }
+
+    /// Brightness of the logo or SLI bridge LEDs
+    pub fn illumination(&self, attribute: IlluminationAttribute) -> sys::Result<Percentage> {
+        trace!("gpu.illumination({:?})", attribute);
+        let mut data = illum::NV_GPU_GET_ILLUMINATION_PARM::zeroed();
+        data.version = illum::NV_GPU_GET_ILLUMINATION_PARM_VER;
+        data.hPhysicalGpu = self.0;
+        data.Attribute = attribute.raw();
+
+        sys::status_result(unsafe { illum::NvAPI_GPU_GetIllumination(&mut data) })
+            .map(|_| Percentage(data.Value))
+    }
+
+    pub fn set_illumination(&self, attribute: IlluminationAttribute, value: Percentage) -> sys::Result<()> {
+        trace!("gpu.set_illumination({:?}, {:?})", attribute, value);
+        let mut data = illum::NV_GPU_SET_ILLUMINATION_PARM::zeroed();
+        data.version = illum::NV_GPU_SET_ILLUMINATION_PARM_VER;
+        data.hPhysicalGpu = self.0;
+        data.Attribute = attribute.raw();
+        data.Value = value.0;
+
+        sys::status_result(unsafe { illum::NvAPI_GPU_SetIllumination(&data) })
+    }
+
+    pub fn illumination_zones(&self) -> sys::Result<<illum::NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS as RawConversion>::Target> {
+        trace!("gpu.illumination_zones()");
+        let mut data = illum::NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS::zeroed();
+        data.version = illum::NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_VER;
+
+        sys::status_result(unsafe { illum::NvAPI_GPU_ClientIllumZonesGetInfo(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Entries correspond to the zones returned by `illumination_zones()`
+    pub fn illumination_zone_control(&self, default: bool) -> sys::Result<<illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS as RawConversion>::Target> {
+        trace!("gpu.illumination_zone_control({:?})", default);
+        let mut data = illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS::zeroed();
+        data.version = illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER;
+        data.bDefault.set(default);
+
+        sys::status_result(unsafe { illum::NvAPI_GPU_ClientIllumZonesGetControl(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Every zone returned by `illumination_zones()` must be given, in the same order.
+    pub fn set_illumination_zone_control<I: Iterator<Item=IllumZoneControl>>(&self, values: I) -> sys::Result<()> {
+        trace!("gpu.set_illumination_zone_control()");
+        let mut data = illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS::zeroed();
+        data.version = illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER;
+        for value in values {
+            trace!("gpu.set_illumination_zone_control({:?})", value);
+            let entry = data.zones.get_mut(data.numIllumZonesControl as usize).ok_or(sys::Status::ArgumentExceedMaxSize)?;
+            *entry = value.raw()?;
+            data.numIllumZonesControl += 1;
+        }
+
+        sys::status_result(unsafe { illum::NvAPI_GPU_ClientIllumZonesSetControl(self.0, &data) })
     }
 
     pub fn cooler_policy_table(&self, index: u32, policy: crate::thermal::CoolerPolicy) -> sys::Result<<cooler::private::NV_GPU_COOLER_POLICY_TABLE as RawConversion>::Target> {
@@ -590,6 +1128,46 @@ impl PhysicalGpu {
             .map(|_| PerformanceDecreaseReason::from_bits_truncate(data))
     }
 
+    pub fn ecc_status(&self) -> sys::Result<EccStatus> {
+        trace!("gpu.ecc_status()");
+        let mut data = ecc::NV_GPU_ECC_STATUS_INFO::zeroed();
+        data.version = ecc::NV_GPU_ECC_STATUS_INFO_VER;
+
+        sys::status_result(unsafe { ecc::NvAPI_GPU_GetECCStatusInfo(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    pub fn ecc_errors(&self) -> sys::Result<EccErrors> {
+        trace!("gpu.ecc_errors()");
+        let mut data = ecc::NV_GPU_ECC_ERROR_INFO::zeroed();
+        data.version = ecc::NV_GPU_ECC_ERROR_INFO_VER;
+
+        sys::status_result(unsafe { ecc::NvAPI_GPU_GetECCErrorInfo(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// Clears the error counts since boot (`current`) and/or the persistent `aggregate` counts.
+    pub fn reset_ecc_errors(&self, current: bool, aggregate: bool) -> sys::Result<()> {
+        trace!("gpu.reset_ecc_errors({:?}, {:?})", current, aggregate);
+        sys::status_result(unsafe { ecc::NvAPI_GPU_ResetECCErrorInfo(self.0, current as _, aggregate as _) })
+    }
+
+    pub fn ecc_configuration(&self) -> sys::Result<EccConfigurationInfo> {
+        trace!("gpu.ecc_configuration()");
+        let mut data = ecc::NV_GPU_ECC_CONFIGURATION_INFO::zeroed();
+        data.version = ecc::NV_GPU_ECC_CONFIGURATION_INFO_VER;
+
+        sys::status_result(unsafe { ecc::NvAPI_GPU_GetECCConfigurationInfo(self.0, &mut data) })
+            .and_then(|_| data.convert_raw().map_err(From::from))
+    }
+
+    /// `immediately` is only honoured when `ecc_status()` reports `EccConfiguration::Immediate`,
+    /// otherwise the change takes effect after a reboot.
+    pub fn set_ecc_configuration(&self, enable: bool, immediately: bool) -> sys::Result<()> {
+        trace!("gpu.set_ecc_configuration({:?}, {:?})", enable, immediately);
+        sys::status_result(unsafe { ecc::NvAPI_GPU_SetECCConfiguration(self.0, enable as _, immediately as _) })
+    }
+
     pub fn display_ids_all(&self) -> sys::Result<Vec<<display::NV_GPU_DISPLAYIDS as RawConversion>::Target>> {
         trace!("gpu.display_ids_all()");
         let mut count = 0;
@@ -620,32 +1198,108 @@ impl PhysicalGpu {
             .and_then(|_| data.into_iter().map(|v| v.convert_raw().map_err(From::from)).collect())
     }
 
+    /// The output of a display driven by this GPU, as a single bit of the display
+    /// masks used by `i2c_read` and `i2c_write`.
+    pub fn display_output_id(&self, display_id: u32) -> sys::Result<u32> {
+        trace!("gpu.display_output_id({:#x})", display_id);
+        let mut gpu = Default::default();
+        let mut output = 0;
+        sys::status_result(unsafe { display::NvAPI_SYS_GetGpuAndOutputIdFromDisplayId(display_id, &mut gpu, &mut output) })?;
+        match gpu.as_raw() == self.0.as_raw() {
+            true => Ok(output),
+            false => Err(sys::Status::InvalidArgument),
+        }
+    }
+
+    /// Reads the complete EDID of a display, including any extension blocks.
+    ///
+    /// Older drivers only report the first 256 bytes.
+    /// Fails with `Status::DataNotFound` if the display has no EDID.
+    pub fn edid(&self, display_id: u32) -> sys::Result<Vec<u8>> {
+        trace!("gpu.edid({})", display_id);
+
+        self.edid_v3(display_id)
+            .or_else(fallback_version(|| call_versioned(display::NV_EDID_V2::zeroed(), |data| unsafe {
+                display::NvAPI_GPU_GetEDID(self.0, display_id, data as *mut display::NV_EDID_V2 as *mut _)
+            }).map(|data| {
+                let len = (data.sizeofEDID as usize).min(display::NV_EDID_DATA_SIZE);
+                data.EDID_Data[..len].to_vec()
+            })))
+            .or_else(fallback_version(|| call_versioned(display::NV_EDID_V1::zeroed(), |data| unsafe {
+                display::NvAPI_GPU_GetEDID(self.0, display_id, data as *mut display::NV_EDID_V1 as *mut _)
+            }).map(|data| {
+                // the size isn't reported, so derive it from the extension block count
+                let len = ((data.EDID_Data[0x7e] as usize + 1) * 0x80).min(display::NV_EDID_DATA_SIZE);
+                data.EDID_Data[..len].to_vec()
+            })))
+    }
+
+    /// Reads the EDID in 256 byte pages, starting over if it changes partway through.
+    fn edid_v3(&self, display_id: u32) -> sys::Result<Vec<u8>> {
+        const ATTEMPTS: usize = 3;
+
+        'attempt: for _ in 0..ATTEMPTS {
+            let mut edid = Vec::new();
+            let mut edid_id = None;
+            loop {
+                let mut data = display::NV_EDID_V3::zeroed();
+                data.offset = edid.len() as u32;
+                let data = call_versioned(data, |data| unsafe { display::NvAPI_GPU_GetEDID(self.0, display_id, data) })?;
+                if edid_id.is_some() && edid_id != Some(data.edidId) {
+                    continue 'attempt
+                }
+                edid_id = Some(data.edidId);
+
+                let remaining = (data.sizeofEDID as usize).saturating_sub(edid.len());
+                edid.extend_from_slice(&data.EDID_Data[..remaining.min(display::NV_EDID_DATA_SIZE)]);
+                if remaining <= display::NV_EDID_DATA_SIZE {
+                    return Ok(edid)
+                }
+            }
+        }
+
+        Err(sys::Status::Error)
+    }
+
+    /// Overrides the EDID of a display, or removes the override if `edid` is empty.
+    ///
+    /// Only a single 256 byte page can be written, larger EDIDs fail with `Status::ArgumentExceedMaxSize`.
+    pub fn set_edid(&self, display_id: u32, edid: &[u8]) -> sys::Result<()> {
+        trace!("gpu.set_edid({}, {:?})", display_id, edid);
+        if edid.len() > display::NV_EDID_DATA_SIZE {
+            return Err(sys::Status::ArgumentExceedMaxSize)
+        }
+
+        let mut data = display::NV_EDID_V3::zeroed();
+        data.EDID_Data[..edid.len()].copy_from_slice(edid);
+        data.sizeofEDID = edid.len() as u32;
+
+        call_versioned(data, |data| unsafe { display::NvAPI_GPU_SetEDID(self.0, display_id, data) })
+            .map(drop)
+            .or_else(fallback_version(|| call_versioned(data.v2, |data| unsafe {
+                display::NvAPI_GPU_SetEDID(self.0, display_id, data as *mut display::NV_EDID_V2 as *mut _)
+            }).map(drop)))
+    }
+
     pub fn i2c_read(&self, display_mask: u32, port: Option<u8>, port_is_ddc: bool, address: u8, register: &[u8], bytes: &mut [u8], speed: i2c::I2cSpeed) -> sys::Result<usize> {
         trace!("i2c_read({}, {:?}, {:?}, 0x{:02x}, {:?}, {:?})", display_mask, port, port_is_ddc, address, register, speed);
-        let mut data = i2c::NV_I2C_INFO::zeroed();
-        data.version = i2c::NV_I2C_INFO_VER;
-        data.displayMask = display_mask;
-        data.bIsDDCPort = if port_is_ddc { sys::NV_TRUE } else { sys::NV_FALSE } as _;
-        data.i2cDevAddress = address << 1;
-        data.pbI2cRegAddress = if register.is_empty() { ptr::null_mut() } else { register.as_ptr() as *mut _ };
-        data.regAddrSize = register.len() as _;
+        let mut data = Self::i2c_info(display_mask, port, port_is_ddc, address, register, bytes, speed);
         data.pbData = bytes.as_mut_ptr();
-        data.cbSize = bytes.len() as _;
-        data.i2cSpeed = i2c::NVAPI_I2C_SPEED_DEPRECATED;
-        data.i2cSpeedKhz = speed.raw();
-        if let Some(port) = port {
-            data.portId = port;
-            data.bIsPortIdSet = sys::NV_TRUE as _;
-        }
 
-        sys::status_result(unsafe { i2c::NvAPI_I2CRead(self.0, &mut data) })
-            .map(|_| data.cbSize as usize) // TODO: not actually sure if this ever changes?
+        self.i2c_call(data, |data| unsafe { i2c::NvAPI_I2CRead(self.0, data) })
+            .map(|cb| cb as usize) // TODO: not actually sure if this ever changes?
     }
 
     pub fn i2c_write(&self, display_mask: u32, port: Option<u8>, port_is_ddc: bool, address: u8, register: &[u8], bytes: &[u8], speed: i2c::I2cSpeed) -> sys::Result<()> {
         trace!("i2c_write({}, {:?}, {:?}, 0x{:02x}, {:?}, {:?})", display_mask, port, port_is_ddc, address, register, speed);
-        let mut data = i2c::NV_I2C_INFO::zeroed();
-        data.version = i2c::NV_I2C_INFO_VER;
+        let data = Self::i2c_info(display_mask, port, port_is_ddc, address, register, bytes, speed);
+
+        self.i2c_call(data, |data| unsafe { i2c::NvAPI_I2CWrite(self.0, data) })
+            .map(drop)
+    }
+
+    fn i2c_info(display_mask: u32, port: Option<u8>, port_is_ddc: bool, address: u8, register: &[u8], bytes: &[u8], speed: i2c::I2cSpeed) -> i2c::NV_I2C_INFO_V3 {
+        let mut data = i2c::NV_I2C_INFO_V3::zeroed();
         data.displayMask = display_mask;
         data.bIsDDCPort = if port_is_ddc { sys::NV_TRUE } else { sys::NV_FALSE } as _;
         data.i2cDevAddress = address << 1;
@@ -659,12 +1313,242 @@ impl PhysicalGpu {
             data.portId = port;
             data.bIsPortIdSet = sys::NV_TRUE as _;
         }
+        data
+    }
+
+    /// Older layouts are prefixes of `NV_I2C_INFO_V3`, and are only attempted
+    /// when the request doesn't depend on the fields they lack.
+    fn i2c_call<F: FnMut(*mut i2c::NV_I2C_INFO) -> sys::status::NvAPI_Status>(&self, data: i2c::NV_I2C_INFO_V3, mut f: F) -> sys::Result<u32> {
+        let legacy = data.bIsPortIdSet == 0;
+        call_versioned(data, |data| f(data))
+            .map(|data| data.cbSize)
+            .or_else(|e| match e {
+                sys::Status::IncompatibleStructVersion if legacy => {
+                    let data = unsafe { ptr::read(&data as *const _ as *const i2c::NV_I2C_INFO_V2) };
+                    call_versioned(data, |data| f(data as *mut i2c::NV_I2C_INFO_V2 as *mut _))
+                        .map(|data| data.cbSize)
+                },
+                e => Err(e),
+            })
+            .or_else(|e| match e {
+                // V1 has no speed selection, so it can only be used with the default speed
+                sys::Status::IncompatibleStructVersion if legacy && data.i2cSpeedKhz == i2c::I2cSpeed::Default.raw() => {
+                    let mut data = unsafe { ptr::read(&data as *const _ as *const i2c::NV_I2C_INFO_V1) };
+                    data.i2cSpeed = 0;
+                    call_versioned(data, |data| f(data as *mut i2c::NV_I2C_INFO_V1 as *mut _))
+                        .map(|data| data.cbSize)
+                },
+                e => Err(e),
+            })
+    }
+}
 
-        sys::status_result(unsafe { i2c::NvAPI_I2CWrite(self.0, &mut data) })
-            .map(drop)
+impl LogicalGpu {
+    pub fn handle(&self) -> &sys::handles::NvLogicalGpuHandle {
+        &self.0
+    }
+
+    pub fn enumerate() -> sys::Result<Vec<Self>> {
+        trace!("gpu.enumerate_logical()");
+        let mut handles = [Default::default(); sys::types::NVAPI_MAX_LOGICAL_GPUS];
+        let mut len = 0;
+        match unsafe { gpu::NvAPI_EnumLogicalGPUs(&mut handles, &mut len) } {
+            sys::status::NVAPI_NVIDIA_DEVICE_NOT_FOUND => Ok(Vec::new()),
+            status => sys::status_result(status).map(move |_| handles[..len as usize].iter().cloned().map(LogicalGpu).collect()),
+        }
+    }
+
+    pub fn physical_gpus(&self) -> sys::Result<Vec<PhysicalGpu>> {
+        trace!("gpu.physical_gpus()");
+        let mut handles = [Default::default(); sys::types::NVAPI_MAX_PHYSICAL_GPUS];
+        let mut len = 0;
+        sys::status_result(unsafe { gpu::NvAPI_GetPhysicalGPUsFromLogicalGPU(self.0, &mut handles, &mut len) })
+            .map(move |_| handles[..len as usize].iter().cloned().map(PhysicalGpu).collect())
+    }
+
+    pub fn info(&self) -> sys::Result<LogicalGpuInfo> {
+        trace!("gpu.info()");
+        let mut luid = sys::types::LUID::zeroed();
+        let mut data = gpu::NV_LOGICAL_GPU_DATA::zeroed();
+        data.version = gpu::NV_LOGICAL_GPU_DATA_VER;
+        data.pOSAdapterId = &mut luid;
+
+        sys::status_result(unsafe { gpu::NvAPI_GPU_GetLogicalGpuInfo(self.0, &mut data) })?;
+        Ok(LogicalGpuInfo {
+            adapter_luid: luid.into(),
+            physical_gpus: data.physicalGpuHandles.get(..data.physicalGpuCount as usize).ok_or(sys::ArgumentRangeError)?
+                .iter().cloned().map(PhysicalGpu).collect(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct LogicalGpuInfo {
+    /// The adapter the OS sees, shared by every physical GPU in an SLI group
+    pub adapter_luid: Luid,
+    pub physical_gpus: Vec<PhysicalGpu>,
+}
+
+/// GPU generations, in chronological order followed by `Tegra` and `Unknown`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum Architecture {
+    Curie,
+    Tesla,
+    Fermi,
+    Kepler,
+    Maxwell,
+    Pascal,
+    Volta,
+    Turing,
+    Ampere,
+    Hopper,
+    Ada,
+    Blackwell,
+    /// Integrated into a Tegra SoC
+    Tegra,
+    Unknown,
+}
+
+impl Architecture {
+    pub fn from_id(id: ArchitectureId) -> Self {
+        match id {
+            ArchitectureId::T2X | ArchitectureId::T3X | ArchitectureId::T4X => Architecture::Tegra,
+            ArchitectureId::NV40 | ArchitectureId::G78 => Architecture::Curie,
+            ArchitectureId::NV50 | ArchitectureId::G80 | ArchitectureId::G90 | ArchitectureId::GT200 => Architecture::Tesla,
+            ArchitectureId::GF100 | ArchitectureId::GF110 => Architecture::Fermi,
+            ArchitectureId::GK100 | ArchitectureId::GK110 | ArchitectureId::GK200 => Architecture::Kepler,
+            ArchitectureId::GM000 | ArchitectureId::GM200 => Architecture::Maxwell,
+            ArchitectureId::GP100 => Architecture::Pascal,
+            ArchitectureId::GV100 | ArchitectureId::GV110 => Architecture::Volta,
+            ArchitectureId::TU100 => Architecture::Turing,
+            ArchitectureId::GA100 => Architecture::Ampere,
+            ArchitectureId::GH100 => Architecture::Hopper,
+            ArchitectureId::AD100 => Architecture::Ada,
+            ArchitectureId::GB100 | ArchitectureId::GB200 => Architecture::Blackwell,
+        }
+    }
+
+    /// Decodes a chip codename such as `GP104`, as returned by `PhysicalGpu::short_name()`.
+    pub fn from_codename(codename: &str) -> Self {
+        let codename = codename.to_ascii_uppercase();
+        let prefixes = [
+            ("GB", Architecture::Blackwell),
+            ("AD", Architecture::Ada),
+            ("GH", Architecture::Hopper),
+            ("GA", Architecture::Ampere),
+            ("TU", Architecture::Turing),
+            ("GV", Architecture::Volta),
+            ("GP", Architecture::Pascal),
+            ("GM", Architecture::Maxwell),
+            ("GK", Architecture::Kepler),
+            ("GF", Architecture::Fermi),
+            ("GT2", Architecture::Tesla),
+            ("G8", Architecture::Tesla),
+            ("G9", Architecture::Tesla),
+            ("NV4", Architecture::Curie),
+            ("G7", Architecture::Curie),
+        ];
+        prefixes.iter().find(|&&(prefix, _)| codename.starts_with(prefix))
+            .map(|&(_, arch)| arch)
+            .unwrap_or(Architecture::Unknown)
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct ArchInfo {
+    pub architecture: Architecture,
+    /// The chip within the architecture, such as `4` for GP104
+    pub implementation: u32,
+    /// `Unknown` for revisions the driver headers don't name
+    pub revision: ChipRevision,
+}
+
+impl RawConversion for gpu::NV_GPU_ARCH_INFO {
+    type Target = ArchInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(ArchInfo {
+            architecture: ArchitectureId::from_raw(self.architecture).map(Architecture::from_id).unwrap_or(Architecture::Unknown),
+            implementation: self.implementation,
+            revision: ChipRevision::from_raw(self.revision).unwrap_or(ChipRevision::Unknown),
+        })
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct GpuHardwareInfo {
+    /// Set for external GPUs, such as those in a Thunderbolt enclosure
+    pub external: bool,
+    pub ray_tracing_cores: Option<u32>,
+    pub tensor_cores: Option<u32>,
+}
+
+impl RawConversion for gpu::NV_GPU_INFO_V1 {
+    type Target = GpuHardwareInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(GpuHardwareInfo {
+            external: self.flags & 1 != 0,
+            ray_tracing_cores: None,
+            tensor_cores: None,
+        })
+    }
+}
+
+impl RawConversion for gpu::NV_GPU_INFO_V2 {
+    type Target = GpuHardwareInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        self.v1.convert_raw().map(|info| GpuHardwareInfo {
+            ray_tracing_cores: Some(self.rayTracingCores),
+            tensor_cores: Some(self.tensorCores),
+            ..info
+        })
+    }
+}
+
+/// A PCI address, as reported by tools such as `lspci` and `nvidia-smi`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct PciLocation {
+    /// NVAPI doesn't report the PCI segment, so this is always 0.
+    pub domain: u32,
+    pub bus: u32,
+    /// The device number on the bus
+    pub slot: u32,
+}
+
+impl fmt::Display for PciLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04x}:{:02x}:{:02x}.0", self.domain, self.bus, self.slot)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct BusInfo {
+    pub bus_type: BusType,
+    pub location: Option<PciLocation>,
+    pub irq: Option<u32>,
+    /// The current PCIe lane count
+    pub link_width: Option<u32>,
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub struct PciIdentifiers {
@@ -720,12 +1604,15 @@ pub struct MemoryInfo {
     pub dedicated_available: Kibibytes,
     pub system: Kibibytes,
     pub shared: Kibibytes,
+    /// Zero if the driver only supports `NV_DISPLAY_DRIVER_MEMORY_INFO_V1`
     pub dedicated_available_current: Kibibytes,
+    /// Zero if the driver does not support `NV_DISPLAY_DRIVER_MEMORY_INFO_V3`
     pub dedicated_evictions_size: Kibibytes,
+    /// Zero if the driver does not support `NV_DISPLAY_DRIVER_MEMORY_INFO_V3`
     pub dedicated_evictions: u32,
 }
 
-impl RawConversion for driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO {
+impl RawConversion for driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V1 {
     type Target = MemoryInfo;
     type Error = Infallible;
 
@@ -735,13 +1622,100 @@ impl RawConversion for driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO {
             dedicated_available: Kibibytes(self.availableDedicatedVideoMemory),
             system: Kibibytes(self.systemVideoMemory),
             shared: Kibibytes(self.sharedSystemMemory),
+            dedicated_available_current: Kibibytes(0),
+            dedicated_evictions_size: Kibibytes(0),
+            dedicated_evictions: 0,
+        })
+    }
+}
+
+impl RawConversion for driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V2 {
+    type Target = MemoryInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(MemoryInfo {
             dedicated_available_current: Kibibytes(self.curAvailableDedicatedVideoMemory),
+            .. self.v1.convert_raw()?
+        })
+    }
+}
+
+impl RawConversion for driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_V3 {
+    type Target = MemoryInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(MemoryInfo {
             dedicated_evictions_size: Kibibytes(self.dedicatedVideoMemoryEvictionsSize),
             dedicated_evictions: self.dedicatedVideoMemoryEvictionCount,
+            .. self.v2.convert_raw()?
         })
     }
 }
 
+/// A video BIOS version such as `86.04.17.00.01`, which compares component-wise.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct VbiosVersion {
+    /// The leading `XX.XX.XX.XX` components
+    pub revision: u32,
+    /// The trailing OEM component
+    pub oem_revision: u8,
+}
+
+impl VbiosVersion {
+    pub fn new(revision: u32, oem_revision: u8) -> Self {
+        VbiosVersion {
+            revision,
+            oem_revision,
+        }
+    }
+
+    pub fn components(&self) -> [u8; 5] {
+        let [a, b, c, d] = self.revision.to_be_bytes();
+        [a, b, c, d, self.oem_revision]
+    }
+}
+
+impl fmt::Display for VbiosVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d, oem] = self.components();
+        write!(f, "{:02x}.{:02x}.{:02x}.{:02x}.{:02x}", a, b, c, d, oem)
+    }
+}
+
+/// A string that isn't formatted as `XX.XX.XX.XX.XX` hex components.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ParseVbiosVersionError;
+
+impl fmt::Display for ParseVbiosVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid VBIOS version")
+    }
+}
+
+impl error::Error for ParseVbiosVersionError { }
+
+impl FromStr for VbiosVersion {
+    type Err = ParseVbiosVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = [0u8; 5];
+        let mut parts = s.trim().split('.');
+        for c in components.iter_mut() {
+            let part = parts.next().ok_or(ParseVbiosVersionError)?;
+            *c = u8::from_str_radix(part, 16).map_err(|_| ParseVbiosVersionError)?;
+        }
+        if parts.next().is_some() {
+            return Err(ParseVbiosVersionError)
+        }
+
+        let [a, b, c, d, oem] = components;
+        Ok(VbiosVersion::new(u32::from_be_bytes([a, b, c, d]), oem))
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub struct DriverModel {
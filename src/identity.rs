@@ -0,0 +1,179 @@
+use std::{fmt, cmp, hash};
+use serde::{Serialize, Deserialize};
+use crate::sys;
+use crate::types::optional;
+use crate::gpu::{PhysicalGpu, PciIdentifiers, PciLocation};
+
+/// The board serial number stored in the InfoROM.
+///
+/// Boards without one report all zeroes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct BoardSerial(pub [u8; 0x10]);
+
+impl BoardSerial {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&b| b == 0)
+    }
+
+    /// The serial as text, if it's printable ASCII padded with NULs.
+    pub fn as_str(&self) -> Option<&str> {
+        let len = self.0.iter().position(|&b| b == 0).unwrap_or(self.0.len());
+        let (serial, padding) = self.0.split_at(len);
+        if len > 0 && serial.iter().all(|b| b.is_ascii_graphic()) && padding.iter().all(|&b| b == 0) {
+            std::str::from_utf8(serial).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::LowerHex for BoardSerial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+impl fmt::UpperHex for BoardSerial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02X}", b))
+    }
+}
+
+/// Formats as ASCII when possible, or hex otherwise.
+impl fmt::Display for BoardSerial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_str() {
+            Some(serial) => f.write_str(serial),
+            None => fmt::LowerHex::fmt(self, f),
+        }
+    }
+}
+
+impl fmt::Debug for BoardSerial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BoardSerial({})", self)
+    }
+}
+
+/// The locally unique identifier Windows assigns to a display adapter.
+///
+/// It identifies the same adapter to DXGI, CUDA and NVML for as long as the system
+/// stays up, but changes across reboots so it isn't part of `GpuIdentity`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct Luid {
+    pub high: i32,
+    pub low: u32,
+}
+
+impl Luid {
+    pub fn as_u64(&self) -> u64 {
+        (self.high as u32 as u64) << 32 | self.low as u64
+    }
+
+    /// The in-memory layout of a Windows `LUID`, as returned by `cuDeviceGetLuid`.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        self.as_u64().to_le_bytes()
+    }
+}
+
+impl From<sys::types::LUID> for Luid {
+    fn from(luid: sys::types::LUID) -> Self {
+        Luid {
+            high: luid.HighPart,
+            low: luid.LowPart,
+        }
+    }
+}
+
+impl fmt::Display for Luid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:08x}-{:08x}", self.high, self.low)
+    }
+}
+
+/// Properties of a physical GPU that remain stable across re-enumeration and reboots,
+/// suitable for keying persisted per-GPU state.
+///
+/// Each property is `None` if the GPU or driver doesn't report it.
+/// Equality, ordering and hashing ignore `gpu_id`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GpuIdentity {
+    pub board_serial: Option<BoardSerial>,
+    pub pci: Option<PciIdentifiers>,
+    pub location: Option<PciLocation>,
+    /// Assigned by the driver, which may change it when the system configuration changes.
+    pub gpu_id: Option<u32>,
+}
+
+impl GpuIdentity {
+    fn key(&self) -> (&Option<BoardSerial>, &Option<PciIdentifiers>, &Option<PciLocation>) {
+        (&self.board_serial, &self.pci, &self.location)
+    }
+
+    pub fn query(gpu: &PhysicalGpu) -> sys::Result<Self> {
+        Ok(GpuIdentity {
+            board_serial: optional(gpu.board_serial())?.filter(|serial| !serial.is_empty()),
+            pci: optional(gpu.pci_identifiers())?,
+            location: optional(gpu.pci_location())?,
+            gpu_id: optional(gpu.gpu_id())?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.board_serial.is_none() && self.location.is_none() && self.gpu_id.is_none()
+    }
+
+    /// Whether two identities may describe the same device, for when some properties are unknown.
+    ///
+    /// The board serial, PCI IDs and bus location must agree where both sides know them.
+    /// `gpu_id` is only a last resort, as the driver may reassign it.
+    pub fn matches(&self, other: &Self) -> bool {
+        fn compare<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> Option<bool> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a == b),
+                _ => None,
+            }
+        }
+
+        let stable = [
+            compare(&self.location, &other.location),
+            compare(&self.board_serial, &other.board_serial),
+        ];
+        if compare(&self.pci, &other.pci) == Some(false) || stable.contains(&Some(false)) {
+            false
+        } else if stable.contains(&Some(true)) {
+            true
+        } else {
+            compare(&self.gpu_id, &other.gpu_id).unwrap_or(false)
+        }
+    }
+}
+
+impl PartialEq for GpuIdentity {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for GpuIdentity { }
+
+impl PartialOrd for GpuIdentity {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GpuIdentity {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl hash::Hash for GpuIdentity {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
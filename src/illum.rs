@@ -0,0 +1,309 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+use log::trace;
+use crate::sys::gpu::illum;
+use crate::sys;
+use crate::types::{Percentage, RawConversion};
+
+pub use sys::gpu::illum::{IlluminationAttribute, IllumDeviceType, IllumZoneType, IllumZoneLocation, IllumControlMode, IllumCycleType};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IllumZone {
+    pub kind: IllumZoneType,
+    pub device_index: u8,
+    /// `IllumZoneLocation::Invalid` if the driver reports a location this crate doesn't know
+    pub location: IllumZoneLocation,
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_INFO_V1 {
+    type Target = IllumZone;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(IllumZone {
+            kind: IllumZoneType::from_raw(self.type_)?,
+            device_index: self.illumDeviceIdx,
+            location: IllumZoneLocation::from_raw(self.zoneLocation).unwrap_or(IllumZoneLocation::Invalid),
+        })
+    }
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_V1 {
+    type Target = Vec<IllumZone>;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        self.zones.get(..self.numIllumZones as usize).ok_or(sys::ArgumentRangeError)?
+            .iter().map(RawConversion::convert_raw).collect()
+    }
+}
+
+/// The colour of a zone, depending on its `IllumZoneType`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IllumColor {
+    /// Fixed colour and single colour zones can only control their brightness
+    Fixed,
+    Rgb {
+        red: u8,
+        green: u8,
+        blue: u8,
+    },
+    Rgbw {
+        red: u8,
+        green: u8,
+        blue: u8,
+        white: u8,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IllumLevel {
+    pub color: IllumColor,
+    pub brightness: Percentage,
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGB_PARAMS {
+    type Target = IllumLevel;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(IllumLevel {
+            color: IllumColor::Rgb {
+                red: self.colorR,
+                green: self.colorG,
+                blue: self.colorB,
+            },
+            brightness: Percentage(self.brightnessPct as _),
+        })
+    }
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGBW_PARAMS {
+    type Target = IllumLevel;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(IllumLevel {
+            color: IllumColor::Rgbw {
+                red: self.colorR,
+                green: self.colorG,
+                blue: self.colorB,
+                white: self.colorW,
+            },
+            brightness: Percentage(self.brightnessPct as _),
+        })
+    }
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS {
+    type Target = IllumLevel;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(IllumLevel {
+            color: IllumColor::Fixed,
+            brightness: Percentage(self.brightnessPct as _),
+        })
+    }
+}
+
+impl IllumLevel {
+    fn brightness_raw(&self) -> sys::Result<u8> {
+        match u8::try_from(self.brightness.0) {
+            Ok(brightness) if brightness <= 100 => Ok(brightness),
+            _ => Err(sys::Status::InvalidArgument),
+        }
+    }
+
+    fn rgb_raw(&self) -> sys::Result<illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGB_PARAMS> {
+        match self.color {
+            IllumColor::Rgb { red, green, blue } => Ok(illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGB_PARAMS {
+                colorR: red,
+                colorG: green,
+                colorB: blue,
+                brightnessPct: self.brightness_raw()?,
+            }),
+            _ => Err(sys::Status::InvalidArgument),
+        }
+    }
+
+    fn rgbw_raw(&self) -> sys::Result<illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGBW_PARAMS> {
+        match self.color {
+            IllumColor::Rgbw { red, green, blue, white } => Ok(illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGBW_PARAMS {
+                colorR: red,
+                colorG: green,
+                colorB: blue,
+                colorW: white,
+                brightnessPct: self.brightness_raw()?,
+            }),
+            _ => Err(sys::Status::InvalidArgument),
+        }
+    }
+
+    fn brightness_params_raw(&self) -> sys::Result<illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS> {
+        match self.color {
+            IllumColor::Fixed => Ok(illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS {
+                brightnessPct: self.brightness_raw()?,
+            }),
+            _ => Err(sys::Status::InvalidArgument),
+        }
+    }
+}
+
+/// The timing of a cycle between two `IllumLevel` endpoints
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IllumPattern {
+    pub cycle: IllumCycleType,
+    pub group_count: u8,
+    pub rise: Duration,
+    pub fall: Duration,
+    pub hold_a: Duration,
+    pub hold_b: Duration,
+    pub group_idle: Duration,
+    pub phase_offset: Duration,
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR {
+    type Target = IllumPattern;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(IllumPattern {
+            cycle: IllumCycleType::from_raw(self.cycleType)?,
+            group_count: self.grpCount,
+            rise: Duration::from_millis(self.riseTimems as _),
+            fall: Duration::from_millis(self.fallTimems as _),
+            hold_a: Duration::from_millis(self.ATimems as _),
+            hold_b: Duration::from_millis(self.BTimems as _),
+            group_idle: Duration::from_millis(self.grpIdleTimems as _),
+            phase_offset: Duration::from_millis(self.phaseOffsetms as _),
+        })
+    }
+}
+
+impl IllumPattern {
+    fn raw(&self) -> sys::Result<illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR> {
+        fn millis(duration: Duration) -> sys::Result<u16> {
+            u16::try_from(duration.as_millis()).map_err(|_| sys::Status::InvalidArgument)
+        }
+
+        Ok(illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR {
+            cycleType: self.cycle.raw(),
+            grpCount: self.group_count,
+            riseTimems: millis(self.rise)?,
+            fallTimems: millis(self.fall)?,
+            ATimems: millis(self.hold_a)?,
+            BTimems: millis(self.hold_b)?,
+            grpIdleTimems: millis(self.group_idle)?,
+            phaseOffsetms: millis(self.phase_offset)?,
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IllumZoneControlValue {
+    Manual(IllumLevel),
+    PiecewiseLinear {
+        endpoints: [IllumLevel; 2],
+        pattern: IllumPattern,
+    },
+}
+
+impl IllumZoneControlValue {
+    pub fn mode(&self) -> IllumControlMode {
+        match *self {
+            IllumZoneControlValue::Manual(..) => IllumControlMode::Manual,
+            IllumZoneControlValue::PiecewiseLinear { .. } => IllumControlMode::PiecewiseLinear,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IllumZoneControl {
+    pub kind: IllumZoneType,
+    pub value: IllumZoneControlValue,
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_V1 {
+    type Target = IllumZoneControl;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        use sys::gpu::illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE as Value;
+
+        trace!("convert_raw({:#?})", self);
+        let kind = IllumZoneType::from_raw(self.type_)?;
+        let mode = IllumControlMode::from_raw(self.ctrlMode)?;
+        let value = match self.data.get(kind, mode).ok_or(sys::ArgumentRangeError)? {
+            Value::ManualRgb(data) => IllumZoneControlValue::Manual(data.convert_raw()?),
+            Value::ManualRgbw(data) => IllumZoneControlValue::Manual(data.convert_raw()?),
+            Value::ManualBrightness(data) => IllumZoneControlValue::Manual(data.convert_raw()?),
+            Value::PiecewiseLinearRgb(data) => IllumZoneControlValue::PiecewiseLinear {
+                endpoints: [data.rgbParams[0].convert_raw()?, data.rgbParams[1].convert_raw()?],
+                pattern: data.piecewiseLinearData.convert_raw()?,
+            },
+            Value::PiecewiseLinearRgbw(data) => IllumZoneControlValue::PiecewiseLinear {
+                endpoints: [data.rgbwParams[0].convert_raw()?, data.rgbwParams[1].convert_raw()?],
+                pattern: data.piecewiseLinearData.convert_raw()?,
+            },
+            Value::PiecewiseLinearBrightness(data) => IllumZoneControlValue::PiecewiseLinear {
+                endpoints: [data.brightnessParams[0].convert_raw()?, data.brightnessParams[1].convert_raw()?],
+                pattern: data.piecewiseLinearData.convert_raw()?,
+            },
+        };
+
+        Ok(IllumZoneControl {
+            kind,
+            value,
+        })
+    }
+}
+
+impl RawConversion for illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_V1 {
+    type Target = Vec<IllumZoneControl>;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        self.zones.get(..self.numIllumZonesControl as usize).ok_or(sys::ArgumentRangeError)?
+            .iter().map(RawConversion::convert_raw).collect()
+    }
+}
+
+impl IllumZoneControl {
+    /// Fails with `InvalidArgument` if the colours don't match the zone type.
+    pub(crate) fn raw(&self) -> sys::Result<illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_V1> {
+        use sys::gpu::illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE as Value;
+
+        let value = match (self.kind, &self.value) {
+            (IllumZoneType::Rgb, IllumZoneControlValue::Manual(level)) => Value::ManualRgb(level.rgb_raw()?),
+            (IllumZoneType::Rgbw, IllumZoneControlValue::Manual(level)) => Value::ManualRgbw(level.rgbw_raw()?),
+            (IllumZoneType::ColorFixed, IllumZoneControlValue::Manual(level)) | (IllumZoneType::SingleColor, IllumZoneControlValue::Manual(level)) =>
+                Value::ManualBrightness(level.brightness_params_raw()?),
+            (IllumZoneType::Rgb, IllumZoneControlValue::PiecewiseLinear { endpoints, pattern }) =>
+                Value::PiecewiseLinearRgb(illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_RGB {
+                    rgbParams: [endpoints[0].rgb_raw()?, endpoints[1].rgb_raw()?],
+                    piecewiseLinearData: pattern.raw()?,
+                }),
+            (IllumZoneType::Rgbw, IllumZoneControlValue::PiecewiseLinear { endpoints, pattern }) =>
+                Value::PiecewiseLinearRgbw(illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_RGBW {
+                    rgbwParams: [endpoints[0].rgbw_raw()?, endpoints[1].rgbw_raw()?],
+                    piecewiseLinearData: pattern.raw()?,
+                }),
+            (IllumZoneType::ColorFixed, IllumZoneControlValue::PiecewiseLinear { endpoints, pattern }) | (IllumZoneType::SingleColor, IllumZoneControlValue::PiecewiseLinear { endpoints, pattern }) =>
+                Value::PiecewiseLinearBrightness(illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_BRIGHTNESS {
+                    brightnessParams: [endpoints[0].brightness_params_raw()?, endpoints[1].brightness_params_raw()?],
+                    piecewiseLinearData: pattern.raw()?,
+                }),
+            (IllumZoneType::Invalid, _) => return Err(sys::Status::InvalidArgument),
+        };
+
+        let mut data = illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_V1::zeroed();
+        data.type_ = self.kind.raw();
+        data.ctrlMode = self.value.mode().raw();
+        data.data.set(value);
+        Ok(data)
+    }
+}
@@ -1,6 +1,11 @@
+use std::convert::Infallible;
+use serde::{Serialize, Deserialize};
 use crate::sys;
+use crate::sys::sysgeneral;
 use log::trace;
-use crate::types::RawConversion;
+use crate::types::{RawConversion, call_versioned, fallback_version, optional};
+
+pub use sys::sysgeneral::ChipsetInfoFlags;
 
 pub fn driver_version() -> sys::Result<(u32, String)> {
     trace!("driver_version()");
@@ -43,3 +48,153 @@ pub fn unload() -> sys::Result<()> {
         sys::status_result(sys::nvapi::NvAPI_Unload())
     }
 }
+
+/// The host chipset.
+///
+/// Fields missing from older drivers are zero or empty.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct ChipsetInfo {
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub vendor_name: String,
+    pub chipset_name: String,
+    pub flags: ChipsetInfoFlags,
+    pub subsystem_vendor_id: u32,
+    pub subsystem_device_id: u32,
+    pub subsystem_vendor_name: String,
+    pub host_bridge_vendor_id: u32,
+    pub host_bridge_device_id: u32,
+    pub host_bridge_subsystem_vendor_id: u32,
+    pub host_bridge_subsystem_device_id: u32,
+}
+
+impl RawConversion for sysgeneral::NV_CHIPSET_INFO_V1 {
+    type Target = ChipsetInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(ChipsetInfo {
+            vendor_id: self.vendorId,
+            device_id: self.deviceId,
+            vendor_name: self.szVendorName.convert_raw()?,
+            chipset_name: self.szChipsetName.convert_raw()?,
+            flags: ChipsetInfoFlags::empty(),
+            subsystem_vendor_id: 0,
+            subsystem_device_id: 0,
+            subsystem_vendor_name: String::new(),
+            host_bridge_vendor_id: 0,
+            host_bridge_device_id: 0,
+            host_bridge_subsystem_vendor_id: 0,
+            host_bridge_subsystem_device_id: 0,
+        })
+    }
+}
+
+impl RawConversion for sysgeneral::NV_CHIPSET_INFO_V2 {
+    type Target = ChipsetInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(ChipsetInfo {
+            flags: ChipsetInfoFlags::from_bits_truncate(self.flags),
+            .. self.v1.convert_raw()?
+        })
+    }
+}
+
+impl RawConversion for sysgeneral::NV_CHIPSET_INFO_V3 {
+    type Target = ChipsetInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(ChipsetInfo {
+            subsystem_vendor_id: self.subSysVendorId,
+            subsystem_device_id: self.subSysDeviceId,
+            subsystem_vendor_name: self.szSubSysVendorName.convert_raw()?,
+            .. self.v2.convert_raw()?
+        })
+    }
+}
+
+impl RawConversion for sysgeneral::NV_CHIPSET_INFO_V4 {
+    type Target = ChipsetInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(ChipsetInfo {
+            host_bridge_vendor_id: self.HBvendorId,
+            host_bridge_device_id: self.HBdeviceId,
+            host_bridge_subsystem_vendor_id: self.HBsubSysVendorId,
+            host_bridge_subsystem_device_id: self.HBsubSysDeviceId,
+            .. self.v3.convert_raw()?
+        })
+    }
+}
+
+/// Notebook lid and docking station state, using the driver's undocumented encoding.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct LidDockInfo {
+    pub lid_state: u32,
+    pub dock_state: u32,
+    pub lid_policy: u32,
+    pub dock_policy: u32,
+    pub forced_lid_mechanism: bool,
+    pub forced_dock_mechanism: bool,
+}
+
+impl RawConversion for sysgeneral::NV_LID_DOCK_PARAMS {
+    type Target = LidDockInfo;
+    type Error = Infallible;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        Ok(LidDockInfo {
+            lid_state: self.currentLidState,
+            dock_state: self.currentDockState,
+            lid_policy: self.currentLidPolicy,
+            dock_policy: self.currentDockPolicy,
+            forced_lid_mechanism: self.forcedLidMechanismPresent != 0,
+            forced_dock_mechanism: self.forcedDockMechanismPresent != 0,
+        })
+    }
+}
+
+/// Describes the host system, omitting anything it doesn't report.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct SystemInfo {
+    pub chipset: Option<ChipsetInfo>,
+    /// Only available on notebooks
+    pub lid_dock: Option<LidDockInfo>,
+}
+
+pub fn chipset_info() -> sys::Result<ChipsetInfo> {
+    trace!("chipset_info()");
+
+    call_versioned(sysgeneral::NV_CHIPSET_INFO_V4::zeroed(), |data| unsafe { sysgeneral::NvAPI_SYS_GetChipSetInfo(data) })
+        .and_then(|data| data.convert_raw().map_err(Into::into))
+        .or_else(fallback_version(|| call_versioned(sysgeneral::NV_CHIPSET_INFO_V3::zeroed(), |data| unsafe {
+            sysgeneral::NvAPI_SYS_GetChipSetInfo(data as *mut sysgeneral::NV_CHIPSET_INFO_V3 as *mut _)
+        }).and_then(|data| data.convert_raw().map_err(Into::into))))
+        .or_else(fallback_version(|| call_versioned(sysgeneral::NV_CHIPSET_INFO_V2::zeroed(), |data| unsafe {
+            sysgeneral::NvAPI_SYS_GetChipSetInfo(data as *mut sysgeneral::NV_CHIPSET_INFO_V2 as *mut _)
+        }).and_then(|data| data.convert_raw().map_err(Into::into))))
+        .or_else(fallback_version(|| call_versioned(sysgeneral::NV_CHIPSET_INFO_V1::zeroed(), |data| unsafe {
+            sysgeneral::NvAPI_SYS_GetChipSetInfo(data as *mut sysgeneral::NV_CHIPSET_INFO_V1 as *mut _)
+        }).and_then(|data| data.convert_raw().map_err(Into::into))))
+}
+
+pub fn lid_dock_info() -> sys::Result<LidDockInfo> {
+    trace!("lid_dock_info()");
+
+    call_versioned(sysgeneral::NV_LID_DOCK_PARAMS::zeroed(), |data| unsafe { sysgeneral::NvAPI_SYS_GetLidAndDockInfo(data) })
+        .and_then(|data| data.convert_raw().map_err(Into::into))
+}
+
+pub fn system_info() -> sys::Result<SystemInfo> {
+    Ok(SystemInfo {
+        chipset: optional(chipset_info())?,
+        lid_dock: optional(lid_dock_info())?,
+    })
+}
@@ -7,8 +7,14 @@ mod types;
 mod pstate;
 mod clock;
 mod thermal;
+mod illum;
+mod ecc;
 mod gpu;
+mod display;
+mod edid;
 mod info;
+mod identity;
+mod resilient;
 #[cfg(feature = "i2c")]
 mod i2c_impl;
 
@@ -16,8 +22,14 @@ pub use types::*;
 pub use pstate::*;
 pub use clock::*;
 pub use thermal::*;
+pub use illum::*;
+pub use ecc::*;
 pub use gpu::*;
+pub use display::*;
+pub use edid::*;
 pub use info::*;
+pub use identity::*;
+pub use resilient::*;
 #[cfg(feature = "i2c")]
 pub use i2c_impl::*;
 
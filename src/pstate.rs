@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::convert::Infallible;
 use log::trace;
+use serde::{Serialize, Deserialize};
 use crate::sys::gpu::pstate;
 use crate::sys;
 use crate::types::{Microvolts, MicrovoltsDelta, Kilohertz, KilohertzDelta, Percentage, Range, Delta, RawConversion};
@@ -8,6 +9,14 @@ use crate::clock::ClockDomain;
 
 pub use sys::gpu::pstate::{PstateId as PState, VoltageInfoDomain as VoltageDomain, UtilizationDomain};
 
+/// The pstates a client has limited the GPU to, see `PhysicalGpu::set_pstate_client_limits()`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct PStateClientLimits {
+    pub hard: PState,
+    pub soft: PState,
+}
+
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub struct PStateSettings {
     pub id: PState,
@@ -84,7 +93,53 @@ impl PStateSettings {
     }
 }
 
-impl RawConversion for pstate::NV_GPU_PERF_PSTATES20_INFO_V2 {
+impl PStates {
+    /// Combines the current and default settings reported by the pre-Kepler
+    /// `NvAPI_GPU_GetPstatesInfoEx()`, which exposes frequencies rather than offsets.
+    ///
+    /// Clock offsets are the difference between the two. Their adjustable range
+    /// isn't reported, so it only covers the current offset.
+    pub fn from_raw_legacy(current: &pstate::NV_GPU_PERF_PSTATES_INFO, defaults: &pstate::NV_GPU_PERF_PSTATES_INFO) -> Result<Self, sys::ArgumentRangeError> {
+        trace!("convert_raw({:#?}, {:#?})", current, defaults);
+        let pstates = current.pstates.iter().take(current.numPstates as usize).map(|ps| -> Result<_, sys::ArgumentRangeError> {
+            let default = defaults.pstates.iter().take(defaults.numPstates as usize)
+                .find(|d| d.pstateId == ps.pstateId);
+            let editable = ps.flag_overclockable();
+            Ok(PStateSettings {
+                id: PState::from_raw(ps.pstateId)?,
+                editable,
+                clocks: ps.clocks.iter().take(current.numClocks as usize).map(|clock| -> Result<_, sys::ArgumentRangeError> {
+                    let default = default.and_then(|d| d.clocks.iter().find(|c| c.domainId == clock.domainId))
+                        .map(|c| c.freq).unwrap_or(clock.freq);
+                    let delta = KilohertzDelta(clock.freq as i32 - default as i32);
+                    Ok(ClockEntry::Single {
+                        domain: ClockDomain::from_raw(clock.domainId)?,
+                        editable: editable && clock.flag_overclockable(),
+                        frequency_delta: Delta {
+                            value: delta,
+                            range: Range::from_scalar(delta),
+                        },
+                        frequency: Kilohertz(clock.freq),
+                    })
+                }).collect::<Result<_, _>>()?,
+                base_voltages: ps.voltages.iter().take(current.numVoltages as usize).map(|voltage| Ok::<_, sys::ArgumentRangeError>(BaseVoltage {
+                    voltage_domain: VoltageDomain::from_raw(voltage.domainId)?,
+                    editable: false,
+                    voltage: Microvolts(voltage.mvolt * 1000),
+                    voltage_delta: Default::default(),
+                })).collect::<Result<_, _>>()?,
+            })
+        }).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(PStates {
+            editable: pstates.iter().any(|ps| ps.editable),
+            pstates,
+            overvolt: Vec::new(),
+        })
+    }
+}
+
+impl RawConversion for pstate::NV_GPU_PERF_PSTATES20_INFO_V1 {
     type Target = PStates;
     type Error = sys::ArgumentRangeError;
 
@@ -93,7 +148,20 @@ impl RawConversion for pstate::NV_GPU_PERF_PSTATES20_INFO_V2 {
         Ok(PStates {
             editable: self.bIsEditable.get(),
             pstates: self.pstates[..self.numPstates as usize].iter().map(|ps| PStateSettings::from_raw(ps, self.numClocks as _, self.numBaseVoltages as _)).collect::<Result<_, _>>()?,
+            overvolt: Vec::new(),
+        })
+    }
+}
+
+impl RawConversion for pstate::NV_GPU_PERF_PSTATES20_INFO_V2 {
+    type Target = PStates;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(PStates {
             overvolt: self.voltages[..self.numVoltages as usize].iter().map(RawConversion::convert_raw).collect::<Result<_, _>>()?,
+            .. self.v1.convert_raw()?
         })
     }
 }
@@ -0,0 +1,72 @@
+use std::sync::{Mutex, MutexGuard};
+use log::trace;
+use crate::sys;
+use crate::gpu::PhysicalGpu;
+use crate::identity::GpuIdentity;
+
+/// A `PhysicalGpu` that recovers from `Status::HandleInvalidated`.
+///
+/// NVAPI invalidates every handle when the display or SLI configuration changes.
+/// When a call fails this way, GPUs are re-enumerated and the handle is re-bound
+/// to the same physical device before the call is retried.
+#[derive(Debug)]
+pub struct ResilientGpu {
+    gpu: Mutex<PhysicalGpu>,
+    identity: GpuIdentity,
+}
+
+impl ResilientGpu {
+    /// Fails with `Status::NotSupported` if the GPU has no identifying properties to re-bind with.
+    pub fn new(gpu: PhysicalGpu) -> sys::Result<Self> {
+        let identity = GpuIdentity::query(&gpu)?;
+        if identity.is_empty() {
+            return Err(sys::Status::NotSupported)
+        }
+
+        Ok(ResilientGpu {
+            gpu: Mutex::new(gpu),
+            identity,
+        })
+    }
+
+    pub fn enumerate() -> sys::Result<Vec<Self>> {
+        PhysicalGpu::enumerate()?.into_iter().map(Self::new).collect()
+    }
+
+    /// The current handle, which may be replaced by a later call.
+    pub fn handle(&self) -> sys::handles::NvPhysicalGpuHandle {
+        *self.lock().handle()
+    }
+
+    /// Calls `f` with the current handle, re-binding and retrying once if the handle was invalidated.
+    ///
+    /// Fails with `Status::NvidiaDeviceNotFound` if the GPU is no longer present.
+    pub fn call<T, F: FnMut(&PhysicalGpu) -> sys::Result<T>>(&self, mut f: F) -> sys::Result<T> {
+        let mut gpu = self.lock();
+        match f(&gpu) {
+            Err(sys::Status::HandleInvalidated) => {
+                *gpu = self.find()?;
+                f(&gpu)
+            },
+            res => res,
+        }
+    }
+
+    /// Re-enumerates GPUs and binds to the one matching this device.
+    pub fn rebind(&self) -> sys::Result<()> {
+        let gpu = self.find()?;
+        *self.lock() = gpu;
+        Ok(())
+    }
+
+    fn find(&self) -> sys::Result<PhysicalGpu> {
+        trace!("gpu.rebind({:?})", self.identity);
+        PhysicalGpu::enumerate()?.into_iter()
+            .find(|gpu| GpuIdentity::query(gpu).map(|id| self.identity.matches(&id)).unwrap_or(false))
+            .ok_or(sys::Status::NvidiaDeviceNotFound)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PhysicalGpu> {
+        self.gpu.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
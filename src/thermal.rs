@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use log::trace;
 use crate::sys::gpu::{thermal, cooler};
 use crate::sys;
@@ -115,6 +116,21 @@ impl RawConversion for thermal::private::NV_GPU_CLIENT_THERMAL_POLICIES_STATUS {
     }
 }
 
+/// Temperatures indexed by sensor mask bit
+impl RawConversion for thermal::private::NV_GPU_THERMAL_SENSORS {
+    type Target = BTreeMap<usize, CelsiusShifted>;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(self.temperatures.iter().enumerate()
+            .filter(|&(i, _)| self.mask & (1 << i) != 0)
+            .map(|(i, &t)| (i, CelsiusShifted(t)))
+            .collect()
+        )
+    }
+}
+
 pub use sys::gpu::cooler::private::{CoolerType, CoolerController, CoolerPolicy, CoolerTarget, CoolerControl};
 
 #[derive(Debug, Copy, Clone)]
@@ -236,3 +252,106 @@ impl RawConversion for cooler::private::NV_GPU_COOLER_POLICY_TABLE {
         })
     }
 }
+
+pub use sys::gpu::cooler::private::FanCoolerControlMode;
+
+/// A fan as reported by the client fan cooler API of Turing and newer GPUs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FanCoolerInfo {
+    pub id: u32,
+    pub max_rpm: u32,
+}
+
+impl RawConversion for cooler::private::NV_GPU_CLIENT_FAN_COOLERS_INFO_ENTRY {
+    type Target = FanCoolerInfo;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(FanCoolerInfo {
+            id: self.coolerId,
+            max_rpm: self.maxRpm,
+        })
+    }
+}
+
+impl RawConversion for cooler::private::NV_GPU_CLIENT_FAN_COOLERS_INFO {
+    type Target = Vec<FanCoolerInfo>;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        self.coolers.get(..self.count as usize).ok_or(sys::ArgumentRangeError)?
+            .iter().map(RawConversion::convert_raw).collect()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FanCoolerStatus {
+    pub id: u32,
+    pub rpm: u32,
+    pub level_range: Range<Percentage>,
+    pub level: Percentage,
+}
+
+impl RawConversion for cooler::private::NV_GPU_CLIENT_FAN_COOLERS_STATUS_ENTRY {
+    type Target = FanCoolerStatus;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(FanCoolerStatus {
+            id: self.coolerId,
+            rpm: self.currentRpm,
+            level_range: Range {
+                min: Percentage::from_raw(self.currentMinLevel)?,
+                max: Percentage::from_raw(self.currentMaxLevel)?,
+            },
+            level: Percentage::from_raw(self.currentLevel)?,
+        })
+    }
+}
+
+impl RawConversion for cooler::private::NV_GPU_CLIENT_FAN_COOLERS_STATUS {
+    type Target = Vec<FanCoolerStatus>;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        self.coolers.get(..self.count as usize).ok_or(sys::ArgumentRangeError)?
+            .iter().map(RawConversion::convert_raw).collect()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FanCoolerControl {
+    pub id: u32,
+    /// Only applies in `Manual` mode
+    pub level: Percentage,
+    pub mode: FanCoolerControlMode,
+}
+
+impl RawConversion for cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL_ENTRY {
+    type Target = FanCoolerControl;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        Ok(FanCoolerControl {
+            id: self.coolerId,
+            level: Percentage::from_raw(self.level)?,
+            mode: FanCoolerControlMode::from_raw(self.controlMode)?,
+        })
+    }
+}
+
+impl RawConversion for cooler::private::NV_GPU_CLIENT_FAN_COOLERS_CONTROL {
+    type Target = Vec<FanCoolerControl>;
+    type Error = sys::ArgumentRangeError;
+
+    fn convert_raw(&self) -> Result<Self::Target, Self::Error> {
+        trace!("convert_raw({:#?})", self);
+        self.coolers.get(..self.count as usize).ok_or(sys::ArgumentRangeError)?
+            .iter().map(RawConversion::convert_raw).collect()
+    }
+}
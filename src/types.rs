@@ -3,6 +3,7 @@ use std::{fmt, ops};
 use std::convert::Infallible;
 use serde::{Serialize, Deserialize};
 use crate::sys;
+use crate::sys::types::Versioned;
 
 pub trait RawConversion {
     type Target;
@@ -11,6 +12,39 @@ pub trait RawConversion {
     fn convert_raw(&self) -> Result<Self::Target, Self::Error>;
 }
 
+/// Calls `f` with each version of `data`, newest first, until the driver accepts one.
+pub fn call_versioned<T: Versioned, F: FnMut(&mut T) -> sys::status::NvAPI_Status>(mut data: T, mut f: F) -> sys::Result<T> {
+    let mut res = Err(sys::Status::IncompatibleStructVersion);
+    for &version in T::VERSIONS.iter().rev() {
+        data.set_version(version);
+        res = sys::status_result(f(&mut data));
+        match res {
+            Err(sys::Status::IncompatibleStructVersion) => (),
+            _ => break,
+        }
+    }
+
+    res.map(|_| data)
+}
+
+/// Retries with an older struct layout if the driver rejected the newer one,
+/// for use with `Result::or_else`.
+pub fn fallback_version<T, F: FnOnce() -> sys::Result<T>>(f: F) -> impl FnOnce(sys::Status) -> sys::Result<T> {
+    move |e| match e {
+        sys::Status::IncompatibleStructVersion => f(),
+        e => Err(e),
+    }
+}
+
+/// Maps the errors of APIs a GPU or driver doesn't provide to `None`.
+pub(crate) fn optional<T>(v: sys::Result<T>) -> sys::Result<Option<T>> {
+    match v {
+        Ok(v) => Ok(Some(v)),
+        Err(sys::Status::NotSupported) | Err(sys::Status::NoImplementation) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 impl RawConversion for sys::types::NvAPI_ShortString {
     type Target = String;
     type Error = Infallible;
@@ -437,6 +471,27 @@ impl From<Percentage> for Percentage1000 {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq, Default)]
+pub struct Milliwatts(pub u32);
+
+impl fmt::Display for Milliwatts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.0 as f32 / 1000.0;
+        if let Some(precision) = f.precision() {
+            write!(f, "{:.*} W", precision, value)
+        } else {
+            write!(f, "{} W", value)
+        }
+    }
+}
+
+impl fmt::Debug for Milliwatts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq, Default)]
 pub struct Range<T> {
@@ -470,6 +525,10 @@ impl<T> Range<T> {
             max: v,
         }
     }
+
+    pub fn contains(&self, v: &T) -> bool where T: PartialOrd {
+        *v >= self.min && *v <= self.max
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
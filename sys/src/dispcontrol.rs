@@ -1,6 +1,7 @@
 use std::os::raw::c_char;
 use crate::status::NvAPI_Status;
 use crate::handles;
+use crate::types;
 
 nvapi! {
     pub type EnumNvidiaDisplayHandleFn = extern "C" fn(thisEnum: u32, pNvDispHandle: *mut handles::NvDisplayHandle) -> NvAPI_Status;
@@ -35,10 +36,50 @@ nvapi! {
 }
 
 nvapi! {
-    pub type DISP_GetAssociatedUnAttachedNvidiaDisplayHandleFn = extern "C" fn(szDisplayName: *const c_char, pNvUnAttachedDispHandle: *mut handles::NvDisplayHandle) -> NvAPI_Status;
+    pub type DISP_GetAssociatedUnAttachedNvidiaDisplayHandleFn = extern "C" fn(szDisplayName: *const c_char, pNvUnAttachedDispHandle: *mut handles::NvUnAttachedDisplayHandle) -> NvAPI_Status;
 
     /// This function returns the handle of an unattached NVIDIA display that is
     /// associated with the given display name (such as "\\DISPLAY1").
     pub unsafe fn NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle;
 }
 
+nvapi! {
+    pub type GetAssociatedNvidiaDisplayNameFn = extern "C" fn(NvDispHandle: handles::NvDisplayHandle, szDisplayName: *mut types::NvAPI_ShortString) -> NvAPI_Status;
+
+    /// For a given NVIDIA display handle, this function returns a string (such as "\\.\DISPLAY1")
+    /// to identify the display.
+    pub unsafe fn NvAPI_GetAssociatedNvidiaDisplayName;
+}
+
+nvapi! {
+    pub type GetUnAttachedAssociatedDisplayNameFn = extern "C" fn(hNvUnAttachedDisp: handles::NvUnAttachedDisplayHandle, szDisplayName: *mut types::NvAPI_ShortString) -> NvAPI_Status;
+
+    /// This function returns the display name given, for example, "\\DISPLAY1",
+    /// using the unattached NVIDIA display handle
+    pub unsafe fn NvAPI_GetUnAttachedAssociatedDisplayName;
+}
+
+nvapi! {
+    pub type GetPhysicalGPUsFromDisplayFn = extern "C" fn(hNvDisp: handles::NvDisplayHandle, nvGPUHandle: *mut [handles::NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status;
+
+    /// This function returns an array of physical GPU handles associated with the specified display.
+    ///
+    /// At least one GPU must be present in the system and running an NVIDIA display driver.
+    ///
+    /// The array nvGPUHandle will be filled with physical GPU handle values.  The returned
+    /// gpuCount determines how many entries in the array are valid.
+    ///
+    /// If the display corresponds to more than one physical GPU, the first GPU returned
+    /// is the one with the attached active output.
+    pub unsafe fn NvAPI_GetPhysicalGPUsFromDisplay;
+}
+
+nvapi! {
+    pub type GetPhysicalGPUFromUnAttachedDisplayFn = extern "C" fn(hNvUnAttachedDisp: handles::NvUnAttachedDisplayHandle, pPhysicalGpu: *mut handles::NvPhysicalGpuHandle) -> NvAPI_Status;
+
+    /// This function returns a physical GPU handle associated with the specified unattached display.
+    /// The source GPU is a physical render GPU which renders the frame buffer but may or may not drive the scan out.
+    ///
+    /// At least one GPU must be present in the system and running an NVIDIA display driver.
+    pub unsafe fn NvAPI_GetPhysicalGPUFromUnAttachedDisplay;
+}
@@ -51,9 +51,7 @@ pub type NV_GPU_CLOCK_FREQUENCIES_V2 = NV_GPU_CLOCK_FREQUENCIES_V1;
 /// Used in NvAPI_GPU_GetAllClockFrequencies()
 pub type NV_GPU_CLOCK_FREQUENCIES = NV_GPU_CLOCK_FREQUENCIES_V2;
 
-nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER_1(NV_GPU_CLOCK_FREQUENCIES_V1 = 4 * 2 + (4 * 2) * NVAPI_MAX_GPU_PUBLIC_CLOCKS, 1) }
-nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER_2(NV_GPU_CLOCK_FREQUENCIES_V2 = 4 * 2 + (4 * 2) * NVAPI_MAX_GPU_PUBLIC_CLOCKS, 2) }
-nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER_3(NV_GPU_CLOCK_FREQUENCIES_V2 = 4 * 2 + (4 * 2) * NVAPI_MAX_GPU_PUBLIC_CLOCKS, 3) }
+nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER_1, NV_GPU_CLOCK_FREQUENCIES_VER_2, NV_GPU_CLOCK_FREQUENCIES_VER_3(NV_GPU_CLOCK_FREQUENCIES_V2 = 4 * 2 + (4 * 2) * NVAPI_MAX_GPU_PUBLIC_CLOCKS, 1, 2, 3) }
 nvversion! { NV_GPU_CLOCK_FREQUENCIES_VER = NV_GPU_CLOCK_FREQUENCIES_VER_3 }
 
 nvenum! {
@@ -145,6 +143,20 @@ pub mod private {
 
     pub type NV_CLOCKS_INFO = NV_CLOCKS_INFO_V1;
 
+    /// `NV_CLOCKS_INFO_V1::clocks` holds consecutive tables of this many entries:
+    /// current, default and boost clocks (assumption).
+    pub const NV_CLOCKS_INFO_TABLE_SIZE: usize = 32;
+    /// Indices within each table of `NV_CLOCKS_INFO_V1::clocks`
+    pub const NV_CLOCKS_INFO_GRAPHICS: usize = 0;
+    pub const NV_CLOCKS_INFO_MEMORY: usize = 8;
+    pub const NV_CLOCKS_INFO_VIDEO: usize = 12; // assumption
+    pub const NV_CLOCKS_INFO_PROCESSOR: usize = 14;
+    /// Only reported by GPUs with a separate shader clock, which run the graphics clock at half its rate
+    pub const NV_CLOCKS_INFO_SHADER: usize = 30;
+    pub const NV_CLOCKS_INFO_CURRENT: usize = 0;
+    pub const NV_CLOCKS_INFO_DEFAULT: usize = NV_CLOCKS_INFO_TABLE_SIZE; // assumption
+    pub const NV_CLOCKS_INFO_BOOST: usize = NV_CLOCKS_INFO_TABLE_SIZE * 2; // assumption
+
     nvapi! {
         pub type GPU_GetAllClocksFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pClocksInfo: *mut NV_CLOCKS_INFO) -> NvAPI_Status;
 
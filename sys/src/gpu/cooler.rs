@@ -289,4 +289,120 @@ pub mod private {
         /// coolerCount: Number of coolers to restore.
         pub unsafe fn NvAPI_GPU_RestoreCoolerPolicyTable;
     }
+
+    pub const NVAPI_MAX_FAN_COOLERS: usize = 32;
+
+    nvstruct! {
+        pub struct NV_GPU_CLIENT_FAN_COOLERS_INFO_ENTRY {
+            pub coolerId: u32,
+            pub unknown: [u32; 2],
+            pub maxRpm: u32,
+            pub reserved: [u32; 8],
+        }
+    }
+
+    nvstruct! {
+        pub struct NV_GPU_CLIENT_FAN_COOLERS_INFO_V1 {
+            pub version: u32,
+            pub unknown: u32,
+            pub count: u32,
+            pub reserved: [u32; 8],
+            pub coolers: [NV_GPU_CLIENT_FAN_COOLERS_INFO_ENTRY; NVAPI_MAX_FAN_COOLERS],
+        }
+    }
+
+    nvversion! { NV_GPU_CLIENT_FAN_COOLERS_INFO_VER_1(NV_GPU_CLIENT_FAN_COOLERS_INFO_V1 = 4 * 11 + 4 * 12 * NVAPI_MAX_FAN_COOLERS, 1) }
+    nvversion! { NV_GPU_CLIENT_FAN_COOLERS_INFO_VER = NV_GPU_CLIENT_FAN_COOLERS_INFO_VER_1 }
+
+    pub type NV_GPU_CLIENT_FAN_COOLERS_INFO = NV_GPU_CLIENT_FAN_COOLERS_INFO_V1;
+
+    nvapi! {
+        pub type GPU_ClientFanCoolersGetInfoFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pFanCoolersInfo: *mut NV_GPU_CLIENT_FAN_COOLERS_INFO) -> NvAPI_Status;
+
+        /// Undocumented function. Replaces NvAPI_GPU_GetCoolerSettings() since Turing.
+        pub unsafe fn NvAPI_GPU_ClientFanCoolersGetInfo;
+    }
+
+    nvstruct! {
+        pub struct NV_GPU_CLIENT_FAN_COOLERS_STATUS_ENTRY {
+            pub coolerId: u32,
+            pub currentRpm: u32,
+            /// Percentage
+            pub currentMinLevel: u32,
+            pub currentMaxLevel: u32,
+            pub currentLevel: u32,
+            pub reserved: [u32; 8],
+        }
+    }
+
+    nvstruct! {
+        pub struct NV_GPU_CLIENT_FAN_COOLERS_STATUS_V1 {
+            pub version: u32,
+            pub count: u32,
+            pub reserved: [u32; 8],
+            pub coolers: [NV_GPU_CLIENT_FAN_COOLERS_STATUS_ENTRY; NVAPI_MAX_FAN_COOLERS],
+        }
+    }
+
+    nvversion! { NV_GPU_CLIENT_FAN_COOLERS_STATUS_VER_1(NV_GPU_CLIENT_FAN_COOLERS_STATUS_V1 = 4 * 10 + 4 * 13 * NVAPI_MAX_FAN_COOLERS, 1) }
+    nvversion! { NV_GPU_CLIENT_FAN_COOLERS_STATUS_VER = NV_GPU_CLIENT_FAN_COOLERS_STATUS_VER_1 }
+
+    pub type NV_GPU_CLIENT_FAN_COOLERS_STATUS = NV_GPU_CLIENT_FAN_COOLERS_STATUS_V1;
+
+    nvapi! {
+        pub type GPU_ClientFanCoolersGetStatusFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pFanCoolersStatus: *mut NV_GPU_CLIENT_FAN_COOLERS_STATUS) -> NvAPI_Status;
+
+        /// Undocumented function.
+        pub unsafe fn NvAPI_GPU_ClientFanCoolersGetStatus;
+    }
+
+    nvenum! {
+        pub enum NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE / FanCoolerControlMode {
+            NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE_AUTO / Auto = 0,
+            NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE_MANUAL / Manual = 1,
+        }
+    }
+
+    nvenum_display! {
+        FanCoolerControlMode => _
+    }
+
+    nvstruct! {
+        pub struct NV_GPU_CLIENT_FAN_COOLERS_CONTROL_ENTRY {
+            pub coolerId: u32,
+            /// Percentage, applied in manual mode
+            pub level: u32,
+            pub controlMode: NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE,
+            pub reserved: [u32; 8],
+        }
+    }
+
+    nvstruct! {
+        pub struct NV_GPU_CLIENT_FAN_COOLERS_CONTROL_V1 {
+            pub version: u32,
+            pub unknown: u32,
+            pub count: u32,
+            pub reserved: [u32; 8],
+            pub coolers: [NV_GPU_CLIENT_FAN_COOLERS_CONTROL_ENTRY; NVAPI_MAX_FAN_COOLERS],
+        }
+    }
+
+    nvversion! { NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER_1(NV_GPU_CLIENT_FAN_COOLERS_CONTROL_V1 = 4 * 11 + 4 * 11 * NVAPI_MAX_FAN_COOLERS, 1) }
+    nvversion! { NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER = NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER_1 }
+
+    pub type NV_GPU_CLIENT_FAN_COOLERS_CONTROL = NV_GPU_CLIENT_FAN_COOLERS_CONTROL_V1;
+
+    nvapi! {
+        pub type GPU_ClientFanCoolersGetControlFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pFanCoolersControl: *mut NV_GPU_CLIENT_FAN_COOLERS_CONTROL) -> NvAPI_Status;
+
+        /// Undocumented function.
+        pub unsafe fn NvAPI_GPU_ClientFanCoolersGetControl;
+    }
+
+    nvapi! {
+        pub type GPU_ClientFanCoolersSetControlFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pFanCoolersControl: *const NV_GPU_CLIENT_FAN_COOLERS_CONTROL) -> NvAPI_Status;
+
+        /// Undocumented function. Replaces NvAPI_GPU_SetCoolerLevels() and NvAPI_GPU_RestoreCoolerSettings() since Turing.
+        pub unsafe fn NvAPI_GPU_ClientFanCoolersSetControl;
+    }
 }
@@ -80,8 +80,7 @@ nvstruct! {
     }
 }
 
-nvversion! { NV_GPU_DISPLAYIDS_VER1(NV_GPU_DISPLAYIDS = 4 * 4, 1) }
-nvversion! { NV_GPU_DISPLAYIDS_VER2(NV_GPU_DISPLAYIDS = 4 * 4, 3) }
+nvversion! { NV_GPU_DISPLAYIDS_VER1, NV_GPU_DISPLAYIDS_VER2(NV_GPU_DISPLAYIDS = 4 * 4, 1, 3) }
 nvversion! { NV_GPU_DISPLAYIDS_VER = NV_GPU_DISPLAYIDS_VER2 }
 
 nvapi! {
@@ -120,3 +119,103 @@ nvapi! {
     /// - `NVAPI_INSUFFICIENT_BUFFER`: When the input buffer(pDisplayIds) is less than the actual number of display IDs
     pub unsafe fn NvAPI_GPU_GetAllDisplayIds;
 }
+
+nvapi! {
+    pub type SYS_GetGpuAndOutputIdFromDisplayIdFn = extern "C" fn(displayId: u32, hPhysicalGpu: *mut NvPhysicalGpuHandle, outputId: *mut u32) -> NvAPI_Status;
+
+    /// This API converts a display ID to a Physical GPU handle and output ID.
+    /// The output ID has exactly 1 bit set, as used by display masks.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: hPhysicalGpu or outputId is NULL
+    /// - `NVAPI_ID_OUT_OF_RANGE`: The DisplayId corresponds to a display which is not within the normal outputId range.
+    pub unsafe fn NvAPI_SYS_GetGpuAndOutputIdFromDisplayId;
+}
+
+pub const NV_EDID_V1_DATA_SIZE: usize = 256;
+pub const NV_EDID_DATA_SIZE: usize = NV_EDID_V1_DATA_SIZE;
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetEDID() and NvAPI_GPU_SetEDID()
+    pub struct NV_EDID_V1 {
+        /// Structure version
+        pub version: u32,
+        /// EDID_Data[NV_EDID_DATA_SIZE]
+        pub EDID_Data: [u8; NV_EDID_V1_DATA_SIZE],
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetEDID() and NvAPI_GPU_SetEDID()
+    pub struct NV_EDID_V2 {
+        pub v1: NV_EDID_V1,
+        /// Size of the whole EDID
+        pub sizeofEDID: u32,
+    }
+}
+nvinherit! { NV_EDID_V2(v1: NV_EDID_V1) }
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetEDID() and NvAPI_GPU_SetEDID()
+    pub struct NV_EDID_V3 {
+        pub v2: NV_EDID_V2,
+        /// ID which always returned in a monotonically increasing counter.
+        /// Across a split-EDID read we need to verify that all calls returned the same edidId.
+        /// This counter is incremented if we get the updated EDID.
+        pub edidId: u32,
+        /// Which 256 byte page of the EDID we want to read. Start at 0.
+        /// If the read succeeds with edidSize > NV_EDID_DATA_SIZE,
+        /// call back again with offset+256 until we have read the entire buffer
+        pub offset: u32,
+    }
+}
+nvinherit! { NV_EDID_V3(v2: NV_EDID_V2) }
+
+pub type NV_EDID = NV_EDID_V3;
+
+nvversion! { NV_EDID_VER1(NV_EDID_V1 = 4 + NV_EDID_V1_DATA_SIZE, 1) }
+nvversion! { NV_EDID_VER2(NV_EDID_V2 = 4 + NV_EDID_V1_DATA_SIZE + 4, 2) }
+nvversion! { NV_EDID_VER3(NV_EDID_V3 = 4 + NV_EDID_V1_DATA_SIZE + 4 * 3, 3) }
+nvversion! { NV_EDID_VER = NV_EDID_VER3 }
+
+nvapi! {
+    pub type GPU_GetEDIDFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status;
+
+    /// This function returns the EDID data for the specified GPU handle and connection bit mask.
+    /// displayOutputId should have exactly 1 bit set to indicate a single display.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pEDID is NULL; displayOutputId has 0 or > 1 bits set
+    /// - `NVAPI_OK`: *pEDID contains valid data.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: No NVIDIA GPU driving a display was found.
+    /// - `NVAPI_EXPECTED_PHYSICAL_GPU_HANDLE`: hPhysicalGpu was not a physical GPU handle.
+    /// - `NVAPI_DATA_NOT_FOUND`: The requested display does not contain an EDID.
+    pub unsafe fn NvAPI_GPU_GetEDID;
+}
+
+nvapi! {
+    pub type GPU_SetEDIDFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status;
+
+    /// Thus function sets the EDID data for the specified GPU handle and connection bit mask.
+    /// User can either send (Gpu handle & output id) or only display Id in variable displayOutputId parameter & hPhysicalGpu parameter can be default handle (0).
+    ///
+    /// Note: The EDID will be cached across the boot session and will be enumerated to the OS in this call.
+    /// To remove the EDID set sizeofEDID to zero.
+    /// OS and NVAPI connection status APIs will reflect the newly set or removed EDID dynamically.
+    ///
+    /// This feature will NOT be supported on the following boards:
+    /// - GeForce
+    /// - Quadro VX
+    /// - Tesla
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pEDID is NULL; displayOutputId has 0 or > 1 bits set
+    /// - `NVAPI_OK`: *pEDID data was applied to the requested displayOutputId.
+    /// - `NVAPI_NVIDIA_DEVICE_NOT_FOUND`: No NVIDIA GPU driving a display was found.
+    /// - `NVAPI_EXPECTED_PHYSICAL_GPU_HANDLE`: hPhysicalGpu was not a physical GPU handle.
+    /// - `NVAPI_NOT_SUPPORTED`: For the above mentioned GPUs
+    pub unsafe fn NvAPI_GPU_SetEDID;
+}
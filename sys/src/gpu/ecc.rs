@@ -0,0 +1,130 @@
+use crate::status::NvAPI_Status;
+use crate::handles::NvPhysicalGpuHandle;
+use crate::types::{NvBool, BoolU32};
+
+nvenum! {
+    /// Used in NV_GPU_ECC_STATUS_INFO.
+    pub enum NV_ECC_CONFIGURATION / EccConfiguration {
+        NV_ECC_CONFIGURATION_NOT_SUPPORTED / NotSupported = 0,
+        /// Changes require a POST to take effect
+        NV_ECC_CONFIGURATION_DEFERRED / Deferred = 1,
+        /// Changes can optionally be made to take effect immediately
+        NV_ECC_CONFIGURATION_IMMEDIATE / Immediate = 2,
+    }
+}
+
+nvenum_display! {
+    EccConfiguration => {
+        NotSupported = "Not Supported",
+        _ = _,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetECCStatusInfo()
+    pub struct NV_GPU_ECC_STATUS_INFO_V1 {
+        /// Structure version
+        pub version: u32,
+        /// ECC memory feature support
+        pub isSupported: BoolU32,
+        /// Supported ECC memory feature configuration options
+        pub configurationOptions: NV_ECC_CONFIGURATION,
+        /// Active ECC memory setting
+        pub isEnabled: BoolU32,
+    }
+}
+
+nvversion! { NV_GPU_ECC_STATUS_INFO_VER_1(NV_GPU_ECC_STATUS_INFO_V1 = 4 * 4, 1) }
+nvversion! { NV_GPU_ECC_STATUS_INFO_VER = NV_GPU_ECC_STATUS_INFO_VER_1 }
+
+pub type NV_GPU_ECC_STATUS_INFO = NV_GPU_ECC_STATUS_INFO_V1;
+
+nvapi! {
+    pub type GPU_GetECCStatusInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pECCStatusInfo: *mut NV_GPU_ECC_STATUS_INFO) -> NvAPI_Status;
+
+    /// This function returns ECC memory status information.
+    pub unsafe fn NvAPI_GPU_GetECCStatusInfo;
+}
+
+nvstruct! {
+    pub struct NV_GPU_ECC_ERROR_INFO_COUNTERS {
+        /// Number of single-bit ECC errors detected
+        pub singleBitErrors: u64,
+        /// Number of double-bit ECC errors detected
+        pub doubleBitErrors: u64,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetECCErrorInfo()
+    pub struct NV_GPU_ECC_ERROR_INFO_V1 {
+        /// Structure version
+        pub version: u32,
+        /// Errors detected since last boot
+        pub current: NV_GPU_ECC_ERROR_INFO_COUNTERS,
+        /// Errors detected since last counter reset
+        pub aggregate: NV_GPU_ECC_ERROR_INFO_COUNTERS,
+    }
+}
+
+nvversion! { NV_GPU_ECC_ERROR_INFO_VER_1(NV_GPU_ECC_ERROR_INFO_V1 = 8 + 8 * 2 * 2, 1) }
+nvversion! { NV_GPU_ECC_ERROR_INFO_VER = NV_GPU_ECC_ERROR_INFO_VER_1 }
+
+pub type NV_GPU_ECC_ERROR_INFO = NV_GPU_ECC_ERROR_INFO_V1;
+
+nvapi! {
+    pub type GPU_GetECCErrorInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pECCErrorInfo: *mut NV_GPU_ECC_ERROR_INFO) -> NvAPI_Status;
+
+    /// This function returns ECC memory error information.
+    pub unsafe fn NvAPI_GPU_GetECCErrorInfo;
+}
+
+nvapi! {
+    pub type GPU_ResetECCErrorInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, bResetCurrent: NvBool, bResetAggregate: NvBool) -> NvAPI_Status;
+
+    /// This function resets ECC memory error counters.
+    pub unsafe fn NvAPI_GPU_ResetECCErrorInfo;
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetECCConfigurationInfo()
+    pub struct NV_GPU_ECC_CONFIGURATION_INFO_V1 {
+        /// Structure version
+        pub version: u32,
+        /// - bit 0 is the current ECC configuration stored in non-volatile memory
+        /// - bit 1 is the factory default ECC configuration
+        pub flags: u32,
+    }
+}
+
+impl NV_GPU_ECC_CONFIGURATION_INFO_V1 {
+    pub fn flag_enabled(&self) -> bool {
+        self.flags & 1 != 0
+    }
+
+    pub fn flag_enabled_by_default(&self) -> bool {
+        self.flags & 2 != 0
+    }
+}
+
+nvversion! { NV_GPU_ECC_CONFIGURATION_INFO_VER_1(NV_GPU_ECC_CONFIGURATION_INFO_V1 = 4 * 2, 1) }
+nvversion! { NV_GPU_ECC_CONFIGURATION_INFO_VER = NV_GPU_ECC_CONFIGURATION_INFO_VER_1 }
+
+pub type NV_GPU_ECC_CONFIGURATION_INFO = NV_GPU_ECC_CONFIGURATION_INFO_V1;
+
+nvapi! {
+    pub type GPU_GetECCConfigurationInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pECCConfigurationInfo: *mut NV_GPU_ECC_CONFIGURATION_INFO) -> NvAPI_Status;
+
+    /// This function returns ECC memory configuration information.
+    pub unsafe fn NvAPI_GPU_GetECCConfigurationInfo;
+}
+
+nvapi! {
+    pub type GPU_SetECCConfigurationFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, bEnable: NvBool, bEnableImmediately: NvBool) -> NvAPI_Status;
+
+    /// This function updates the ECC memory configuration setting.
+    ///
+    /// `bEnableImmediately` requests that the new setting take effect right away,
+    /// which is only honoured when `NV_ECC_CONFIGURATION_IMMEDIATE` is supported.
+    pub unsafe fn NvAPI_GPU_SetECCConfiguration;
+}
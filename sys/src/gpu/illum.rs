@@ -0,0 +1,492 @@
+use std::ptr;
+use crate::status::NvAPI_Status;
+use crate::handles::NvPhysicalGpuHandle;
+use crate::types::{NvBool, BoolU32};
+
+nvenum! {
+    /// Used in NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM, NV_GPU_GET_ILLUMINATION_PARM and NV_GPU_SET_ILLUMINATION_PARM
+    pub enum NV_GPU_ILLUMINATION_ATTRIB / IlluminationAttribute {
+        NV_GPU_IA_LOGO_BRIGHTNESS / LogoBrightness = 0,
+        NV_GPU_IA_SLI_BRIGHTNESS / SliBrightness = 1,
+    }
+}
+
+nvenum_display! {
+    IlluminationAttribute => {
+        LogoBrightness = "Logo",
+        SliBrightness = "SLI Bridge",
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_QueryIlluminationSupport()
+    pub struct NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_V1 {
+        /// IN - Structure version
+        pub version: u32,
+        /// IN - The handle of the GPU
+        pub hPhysicalGpu: NvPhysicalGpuHandle,
+        /// IN - An enumeration value specifying the Illumination attribute to be queried
+        pub Attribute: NV_GPU_ILLUMINATION_ATTRIB,
+        /// OUT - A boolean value to indicate if this attribute is supported
+        pub bSupported: BoolU32,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetIllumination() and NvAPI_GPU_SetIllumination()
+    pub struct NV_GPU_ILLUMINATION_PARM_V1 {
+        /// IN - Structure version
+        pub version: u32,
+        /// IN - The handle of the GPU
+        pub hPhysicalGpu: NvPhysicalGpuHandle,
+        /// IN - An enumeration value specifying the Illumination attribute
+        pub Attribute: NV_GPU_ILLUMINATION_ATTRIB,
+        /// Brightness as a percentage, OUT for NvAPI_GPU_GetIllumination() and IN for NvAPI_GPU_SetIllumination()
+        pub Value: u32,
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+const NV_GPU_ILLUMINATION_PARM_V1_SIZE: usize = 4 * 2 + 8 + 4 * 2;
+#[cfg(target_pointer_width = "32")]
+const NV_GPU_ILLUMINATION_PARM_V1_SIZE: usize = 4 + 4 + 4 * 2;
+
+pub type NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM = NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_V1;
+pub type NV_GPU_GET_ILLUMINATION_PARM_V1 = NV_GPU_ILLUMINATION_PARM_V1;
+pub type NV_GPU_GET_ILLUMINATION_PARM = NV_GPU_GET_ILLUMINATION_PARM_V1;
+pub type NV_GPU_SET_ILLUMINATION_PARM_V1 = NV_GPU_ILLUMINATION_PARM_V1;
+pub type NV_GPU_SET_ILLUMINATION_PARM = NV_GPU_SET_ILLUMINATION_PARM_V1;
+
+nvversion! { NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_VER_1(NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_V1 = NV_GPU_ILLUMINATION_PARM_V1_SIZE, 1) }
+nvversion! { NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_VER = NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_VER_1 }
+nvversion! { NV_GPU_ILLUMINATION_PARM_VER_1(NV_GPU_ILLUMINATION_PARM_V1 = NV_GPU_ILLUMINATION_PARM_V1_SIZE, 1) }
+nvversion! { NV_GPU_GET_ILLUMINATION_PARM_VER_1 = NV_GPU_ILLUMINATION_PARM_VER_1 }
+nvversion! { NV_GPU_GET_ILLUMINATION_PARM_VER = NV_GPU_GET_ILLUMINATION_PARM_VER_1 }
+nvversion! { NV_GPU_SET_ILLUMINATION_PARM_VER_1 = NV_GPU_ILLUMINATION_PARM_VER_1 }
+nvversion! { NV_GPU_SET_ILLUMINATION_PARM_VER = NV_GPU_SET_ILLUMINATION_PARM_VER_1 }
+
+nvapi! {
+    pub type GPU_QueryIlluminationSupportFn = extern "C" fn(pIlluminationSupportInfo: *mut NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM) -> NvAPI_Status;
+
+    /// This function reports if the specified illumination attribute is supported.
+    ///
+    /// Only a single GPU can be managed by this call at a time, as specified by the handle in the structure.
+    pub unsafe fn NvAPI_GPU_QueryIlluminationSupport;
+}
+
+nvapi! {
+    pub type GPU_GetIlluminationFn = extern "C" fn(pIlluminationInfo: *mut NV_GPU_GET_ILLUMINATION_PARM) -> NvAPI_Status;
+
+    /// This function reports the current illumination value of the specified attribute.
+    pub unsafe fn NvAPI_GPU_GetIllumination;
+}
+
+nvapi! {
+    pub type GPU_SetIlluminationFn = extern "C" fn(pIlluminationInfo: *const NV_GPU_SET_ILLUMINATION_PARM) -> NvAPI_Status;
+
+    /// This function sets the value of the specified illumination attribute.
+    pub unsafe fn NvAPI_GPU_SetIllumination;
+}
+
+pub const NV_GPU_CLIENT_ILLUM_DEVICE_NUM_DEVICES_MAX: usize = 32;
+pub const NV_GPU_CLIENT_ILLUM_ZONE_NUM_ZONES_MAX: usize = 32;
+pub const NV_GPU_CLIENT_ILLUM_RSVD_SIZE: usize = 64;
+pub const NV_GPU_CLIENT_ILLUM_CTRL_MODE_PIECEWISE_LINEAR_COLOR_ENDPOINTS: usize = 2;
+
+nvenum! {
+    pub enum NV_GPU_CLIENT_ILLUM_DEVICE_TYPE / IllumDeviceType {
+        NV_GPU_CLIENT_ILLUM_DEVICE_TYPE_INVALID / Invalid = 0,
+        NV_GPU_CLIENT_ILLUM_DEVICE_TYPE_MCUV10 / McuV10 = 1,
+        NV_GPU_CLIENT_ILLUM_DEVICE_TYPE_GPIO_PWM_RGBW_V10 / GpioPwmRgbwV10 = 2,
+        NV_GPU_CLIENT_ILLUM_DEVICE_TYPE_GPIO_PWM_SINGLE_COLOR_V10 / GpioPwmSingleColorV10 = 3,
+    }
+}
+
+nvenum_display! {
+    IllumDeviceType => {
+        McuV10 = "MCU",
+        GpioPwmRgbwV10 = "GPIO PWM RGBW",
+        GpioPwmSingleColorV10 = "GPIO PWM Single Color",
+        _ = _,
+    }
+}
+
+nvenum! {
+    pub enum NV_GPU_CLIENT_ILLUM_ZONE_TYPE / IllumZoneType {
+        NV_GPU_CLIENT_ILLUM_ZONE_TYPE_INVALID / Invalid = 0,
+        NV_GPU_CLIENT_ILLUM_ZONE_TYPE_RGB / Rgb = 1,
+        NV_GPU_CLIENT_ILLUM_ZONE_TYPE_COLOR_FIXED / ColorFixed = 2,
+        NV_GPU_CLIENT_ILLUM_ZONE_TYPE_RGBW / Rgbw = 3,
+        NV_GPU_CLIENT_ILLUM_ZONE_TYPE_SINGLE_COLOR / SingleColor = 4,
+    }
+}
+
+nvenum_display! {
+    IllumZoneType => {
+        Rgb = "RGB",
+        ColorFixed = "Fixed Color",
+        Rgbw = "RGBW",
+        SingleColor = "Single Color",
+        _ = _,
+    }
+}
+
+nvenum! {
+    pub enum NV_GPU_CLIENT_ILLUM_ZONE_LOCATION / IllumZoneLocation {
+        NV_GPU_CLIENT_ILLUM_ZONE_LOCATION_GPU_TOP_0 / GpuTop0 = 0x00,
+        NV_GPU_CLIENT_ILLUM_ZONE_LOCATION_GPU_FRONT_0 / GpuFront0 = 0x08,
+        NV_GPU_CLIENT_ILLUM_ZONE_LOCATION_GPU_BACK_0 / GpuBack0 = 0x0c,
+        NV_GPU_CLIENT_ILLUM_ZONE_LOCATION_SLI_TOP_0 / SliTop0 = 0x20,
+        NV_GPU_CLIENT_ILLUM_ZONE_LOCATION_INVALID / Invalid = -1,
+    }
+}
+
+nvenum_display! {
+    IllumZoneLocation => {
+        GpuTop0 = "GPU Top",
+        GpuFront0 = "GPU Front",
+        GpuBack0 = "GPU Back",
+        SliTop0 = "SLI Bridge",
+        _ = _,
+    }
+}
+
+nvenum! {
+    pub enum NV_GPU_CLIENT_ILLUM_CTRL_MODE / IllumControlMode {
+        /// Manual control of the zone colour and brightness
+        NV_GPU_CLIENT_ILLUM_CTRL_MODE_MANUAL / Manual = 0,
+        /// Cycles between two colour endpoints
+        NV_GPU_CLIENT_ILLUM_CTRL_MODE_PIECEWISE_LINEAR / PiecewiseLinear = 1,
+        NV_GPU_CLIENT_ILLUM_CTRL_MODE_INVALID / Invalid = 0xff,
+    }
+}
+
+nvenum_display! {
+    IllumControlMode => {
+        PiecewiseLinear = "Piecewise Linear",
+        _ = _,
+    }
+}
+
+nvenum! {
+    pub enum NV_GPU_CLIENT_ILLUM_PIECEWISE_LINEAR_CYCLE_TYPE / IllumCycleType {
+        /// Halts half way through the cycle
+        NV_GPU_CLIENT_ILLUM_PIECEWISE_LINEAR_CYCLE_HALF_HALT / HalfHalt = 0,
+        /// Halts at the end of a full cycle
+        NV_GPU_CLIENT_ILLUM_PIECEWISE_LINEAR_CYCLE_FULL_HALT / FullHalt = 1,
+        /// Repeats the cycle indefinitely
+        NV_GPU_CLIENT_ILLUM_PIECEWISE_LINEAR_CYCLE_FULL_REPEAT / FullRepeat = 2,
+        NV_GPU_CLIENT_ILLUM_PIECEWISE_LINEAR_CYCLE_INVALID / Invalid = 0xff,
+    }
+}
+
+nvenum_display! {
+    IllumCycleType => {
+        HalfHalt = "Half Halt",
+        FullHalt = "Full Halt",
+        FullRepeat = "Repeat",
+        _ = _,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_DEVICE_INFO_V1 {
+        pub type_: NV_GPU_CLIENT_ILLUM_DEVICE_TYPE,
+        /// Bitmask of supported NV_GPU_CLIENT_ILLUM_CTRL_MODE values
+        pub ctrlModeMask: u32,
+        /// Device specific data, such as the I2C device index or GPIO pins
+        pub data: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_ClientIllumDevicesGetInfo()
+    pub struct NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS_V1 {
+        pub version: u32,
+        pub numIllumDevices: u32,
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+        pub devices: [NV_GPU_CLIENT_ILLUM_DEVICE_INFO_V1; NV_GPU_CLIENT_ILLUM_DEVICE_NUM_DEVICES_MAX],
+    }
+}
+
+const NV_GPU_CLIENT_ILLUM_DEVICE_INFO_V1_SIZE: usize = 4 * 2 + NV_GPU_CLIENT_ILLUM_RSVD_SIZE * 2;
+
+nvversion! { NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS_VER_1(NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS_V1 = 4 * 2 + NV_GPU_CLIENT_ILLUM_RSVD_SIZE + NV_GPU_CLIENT_ILLUM_DEVICE_INFO_V1_SIZE * NV_GPU_CLIENT_ILLUM_DEVICE_NUM_DEVICES_MAX, 1) }
+nvversion! { NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS_VER = NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS_VER_1 }
+
+pub type NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS = NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS_V1;
+
+nvapi! {
+    pub type GPU_ClientIllumDevicesGetInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pIllumDevicesInfo: *mut NV_GPU_CLIENT_ILLUM_DEVICE_INFO_PARAMS) -> NvAPI_Status;
+
+    /// This API returns static information about illumination devices on the GPU.
+    pub unsafe fn NvAPI_GPU_ClientIllumDevicesGetInfo;
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_DEVICE_SYNC_V1 {
+        /// Synchronize the device with others
+        pub bSync: NvBool,
+        /// Time stamp in milliseconds that the sync is relative to
+        pub timeStampms: u64,
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_V1 {
+        pub type_: NV_GPU_CLIENT_ILLUM_DEVICE_TYPE,
+        pub syncData: NV_GPU_CLIENT_ILLUM_DEVICE_SYNC_V1,
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_ClientIllumDevicesGetControl() and NvAPI_GPU_ClientIllumDevicesSetControl()
+    pub struct NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS_V1 {
+        pub version: u32,
+        pub numIllumDevices: u32,
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+        pub devices: [NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_V1; NV_GPU_CLIENT_ILLUM_DEVICE_NUM_DEVICES_MAX],
+    }
+}
+
+const NV_GPU_CLIENT_ILLUM_DEVICE_SYNC_V1_SIZE: usize = 8 * 2 + NV_GPU_CLIENT_ILLUM_RSVD_SIZE;
+const NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_V1_SIZE: usize = 8 + NV_GPU_CLIENT_ILLUM_DEVICE_SYNC_V1_SIZE + NV_GPU_CLIENT_ILLUM_RSVD_SIZE;
+
+nvversion! { NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS_VER_1(NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS_V1 = 4 * 2 + NV_GPU_CLIENT_ILLUM_RSVD_SIZE + NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_V1_SIZE * NV_GPU_CLIENT_ILLUM_DEVICE_NUM_DEVICES_MAX, 1) }
+nvversion! { NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS_VER = NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS_VER_1 }
+
+pub type NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS = NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS_V1;
+
+nvapi! {
+    pub type GPU_ClientIllumDevicesGetControlFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pClientIllumDevicesControl: *mut NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS) -> NvAPI_Status;
+
+    /// This API gets the control parameters of the illumination devices on the GPU.
+    pub unsafe fn NvAPI_GPU_ClientIllumDevicesGetControl;
+}
+
+nvapi! {
+    pub type GPU_ClientIllumDevicesSetControlFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pClientIllumDevicesControl: *const NV_GPU_CLIENT_ILLUM_DEVICE_CONTROL_PARAMS) -> NvAPI_Status;
+
+    /// This API sets the control parameters of the illumination devices on the GPU.
+    pub unsafe fn NvAPI_GPU_ClientIllumDevicesSetControl;
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_INFO_V1 {
+        pub type_: NV_GPU_CLIENT_ILLUM_ZONE_TYPE,
+        /// Index of the illumination device that controls this zone
+        pub illumDeviceIdx: u8,
+        /// Provider index
+        pub provIdx: u8,
+        pub zoneLocation: NV_GPU_CLIENT_ILLUM_ZONE_LOCATION,
+        /// Zone type specific data, currently reserved for all types
+        pub data: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_ClientIllumZonesGetInfo()
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_V1 {
+        pub version: u32,
+        pub numIllumZones: u32,
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+        pub zones: [NV_GPU_CLIENT_ILLUM_ZONE_INFO_V1; NV_GPU_CLIENT_ILLUM_ZONE_NUM_ZONES_MAX],
+    }
+}
+
+const NV_GPU_CLIENT_ILLUM_ZONE_INFO_V1_SIZE: usize = 4 * 3 + NV_GPU_CLIENT_ILLUM_RSVD_SIZE * 2;
+
+nvversion! { NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_VER_1(NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_V1 = 4 * 2 + NV_GPU_CLIENT_ILLUM_RSVD_SIZE + NV_GPU_CLIENT_ILLUM_ZONE_INFO_V1_SIZE * NV_GPU_CLIENT_ILLUM_ZONE_NUM_ZONES_MAX, 1) }
+nvversion! { NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_VER = NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_VER_1 }
+
+pub type NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS = NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_V1;
+
+nvapi! {
+    pub type GPU_ClientIllumZonesGetInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pIllumZonesInfo: *mut NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS) -> NvAPI_Status;
+
+    /// This API returns static information about illumination zones on the GPU.
+    pub unsafe fn NvAPI_GPU_ClientIllumZonesGetInfo;
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGB_PARAMS {
+        pub colorR: u8,
+        pub colorG: u8,
+        pub colorB: u8,
+        /// Brightness percentage
+        pub brightnessPct: u8,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGBW_PARAMS {
+        pub colorR: u8,
+        pub colorG: u8,
+        pub colorB: u8,
+        pub colorW: u8,
+        /// Brightness percentage
+        pub brightnessPct: u8,
+    }
+}
+
+nvstruct! {
+    /// Shared by the fixed colour and single colour zone types
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS {
+        /// Brightness percentage
+        pub brightnessPct: u8,
+    }
+}
+
+pub type NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_COLOR_FIXED_PARAMS = NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS;
+pub type NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_SINGLE_COLOR_PARAMS = NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS;
+
+nvstruct! {
+    /// Describes the timing of a cycle between two colour endpoints
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR {
+        pub cycleType: NV_GPU_CLIENT_ILLUM_PIECEWISE_LINEAR_CYCLE_TYPE,
+        /// Number of times the group repeats before the idle time
+        pub grpCount: u8,
+        /// Time in ms to transition from endpoint A to B
+        pub riseTimems: u16,
+        /// Time in ms to transition from endpoint B to A
+        pub fallTimems: u16,
+        /// Time in ms to hold endpoint A
+        pub ATimems: u16,
+        /// Time in ms to hold endpoint B
+        pub BTimems: u16,
+        /// Time in ms to idle between groups
+        pub grpIdleTimems: u16,
+        /// Time in ms to offset the cycle relative to the zone's sync point
+        pub phaseOffsetms: u16,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_RGB {
+        pub rgbParams: [NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGB_PARAMS; NV_GPU_CLIENT_ILLUM_CTRL_MODE_PIECEWISE_LINEAR_COLOR_ENDPOINTS],
+        pub piecewiseLinearData: NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_RGBW {
+        pub rgbwParams: [NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGBW_PARAMS; NV_GPU_CLIENT_ILLUM_CTRL_MODE_PIECEWISE_LINEAR_COLOR_ENDPOINTS],
+        pub piecewiseLinearData: NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR,
+    }
+}
+
+nvstruct! {
+    /// Shared by the fixed colour and single colour zone types
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_BRIGHTNESS {
+        pub brightnessParams: [NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS; NV_GPU_CLIENT_ILLUM_CTRL_MODE_PIECEWISE_LINEAR_COLOR_ENDPOINTS],
+        pub piecewiseLinearData: NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR,
+    }
+}
+
+const NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_SIZE: usize = NV_GPU_CLIENT_ILLUM_RSVD_SIZE * 2;
+
+/// A union of the zone type and control mode specific data
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA([u32; NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_SIZE / 4]);
+
+#[derive(Copy, Clone, Debug)]
+pub enum NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE {
+    ManualRgb(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGB_PARAMS),
+    PiecewiseLinearRgb(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_RGB),
+    ManualRgbw(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGBW_PARAMS),
+    PiecewiseLinearRgbw(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_RGBW),
+    /// Fixed colour and single colour zones
+    ManualBrightness(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS),
+    /// Fixed colour and single colour zones
+    PiecewiseLinearBrightness(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_PIECEWISE_LINEAR_BRIGHTNESS),
+}
+
+impl NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA {
+    /// Returns `None` for invalid zone types or control modes.
+    pub fn get(&self, kind: IllumZoneType, mode: IllumControlMode) -> Option<NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE> {
+        use self::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE as Value;
+
+        Some(match (kind, mode) {
+            (IllumZoneType::Rgb, IllumControlMode::Manual) => Value::ManualRgb(unsafe { self.read() }),
+            (IllumZoneType::Rgb, IllumControlMode::PiecewiseLinear) => Value::PiecewiseLinearRgb(unsafe { self.read() }),
+            (IllumZoneType::Rgbw, IllumControlMode::Manual) => Value::ManualRgbw(unsafe { self.read() }),
+            (IllumZoneType::Rgbw, IllumControlMode::PiecewiseLinear) => Value::PiecewiseLinearRgbw(unsafe { self.read() }),
+            (IllumZoneType::ColorFixed, IllumControlMode::Manual) | (IllumZoneType::SingleColor, IllumControlMode::Manual) =>
+                Value::ManualBrightness(unsafe { self.read() }),
+            (IllumZoneType::ColorFixed, IllumControlMode::PiecewiseLinear) | (IllumZoneType::SingleColor, IllumControlMode::PiecewiseLinear) =>
+                Value::PiecewiseLinearBrightness(unsafe { self.read() }),
+            _ => return None,
+        })
+    }
+
+    pub fn set(&mut self, value: NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE) {
+        use self::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE as Value;
+
+        unsafe {
+            match value {
+                Value::ManualRgb(value) => self.write(value),
+                Value::PiecewiseLinearRgb(value) => self.write(value),
+                Value::ManualRgbw(value) => self.write(value),
+                Value::PiecewiseLinearRgbw(value) => self.write(value),
+                Value::ManualBrightness(value) => self.write(value),
+                Value::PiecewiseLinearBrightness(value) => self.write(value),
+            }
+        }
+    }
+
+    /// All variants are located at the start of the union, and are smaller and no more aligned than it.
+    unsafe fn read<T: Copy>(&self) -> T {
+        ptr::read(self.0.as_ptr() as *const T)
+    }
+
+    unsafe fn write<T: Copy>(&mut self, value: T) {
+        ptr::write(self.0.as_mut_ptr() as *mut T, value)
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_V1 {
+        pub type_: NV_GPU_CLIENT_ILLUM_ZONE_TYPE,
+        pub ctrlMode: NV_GPU_CLIENT_ILLUM_CTRL_MODE,
+        pub data: NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA,
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_ClientIllumZonesGetControl() and NvAPI_GPU_ClientIllumZonesSetControl()
+    pub struct NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_V1 {
+        pub version: u32,
+        /// Query or apply the default control values instead of the current ones
+        pub bDefault: BoolU32,
+        pub numIllumZonesControl: u32,
+        pub rsvd: [u8; NV_GPU_CLIENT_ILLUM_RSVD_SIZE],
+        pub zones: [NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_V1; NV_GPU_CLIENT_ILLUM_ZONE_NUM_ZONES_MAX],
+    }
+}
+
+const NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_V1_SIZE: usize = 4 * 2 + NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_SIZE + NV_GPU_CLIENT_ILLUM_RSVD_SIZE;
+
+nvversion! { NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER_1(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_V1 = 4 * 3 + NV_GPU_CLIENT_ILLUM_RSVD_SIZE + NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_V1_SIZE * NV_GPU_CLIENT_ILLUM_ZONE_NUM_ZONES_MAX, 1) }
+nvversion! { NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER = NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER_1 }
+
+pub type NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS = NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_V1;
+
+nvapi! {
+    pub type GPU_ClientIllumZonesGetControlFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pIllumZonesControl: *mut NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS) -> NvAPI_Status;
+
+    /// This API gets the current or default control parameters of the illumination zones on the GPU.
+    pub unsafe fn NvAPI_GPU_ClientIllumZonesGetControl;
+}
+
+nvapi! {
+    pub type GPU_ClientIllumZonesSetControlFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pIllumZonesControl: *const NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS) -> NvAPI_Status;
+
+    /// This API sets the control parameters of the illumination zones on the GPU.
+    pub unsafe fn NvAPI_GPU_ClientIllumZonesSetControl;
+}
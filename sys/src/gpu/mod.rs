@@ -1,5 +1,5 @@
 use crate::status::NvAPI_Status;
-use crate::handles::NvPhysicalGpuHandle;
+use crate::handles::{NvPhysicalGpuHandle, NvLogicalGpuHandle};
 use crate::types;
 
 /// The GPU cooler APIs are used to get and set the fan level or equivalent
@@ -32,6 +32,14 @@ pub mod thermal;
 
 pub mod power;
 
+/// The GPU illumination APIs are used to control the LEDs of the logo, SLI
+/// bridge and other illumination zones associated with the GPU.
+pub mod illum;
+
+/// The GPU ECC APIs are used to query and configure error correction of the
+/// GPU memory on boards that support it.
+pub mod ecc;
+
 pub mod display;
 
 nvapi! {
@@ -56,6 +64,45 @@ nvapi! {
     pub unsafe fn NvAPI_EnumPhysicalGPUs;
 }
 
+nvapi! {
+    pub type EnumLogicalGPUsFn = extern "C" fn(nvGPUHandle: *mut [NvLogicalGpuHandle; types::NVAPI_MAX_LOGICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status;
+
+    /// This function returns an array of logical GPU handles.
+    ///
+    /// Each handle represents one or more GPUs acting in concert as a single graphics device.
+    ///
+    /// At least one GPU must be present in the system and running an NVIDIA display driver.
+    ///
+    /// The array nvGPUHandle will be filled with logical GPU handle values.  The returned
+    /// gpuCount determines how many entries in the array are valid.
+    ///
+    /// Note: All logical GPUs handles get invalidated on a GPU topology change, so the calling
+    /// application is required to renum the logical GPU handles to get latest physical handle
+    /// mapping after every GPU topology change activated by a call to NvAPI_SetGpuTopologies().
+    pub unsafe fn NvAPI_EnumLogicalGPUs;
+}
+
+nvapi! {
+    pub type GetPhysicalGPUsFromLogicalGPUFn = extern "C" fn(hLogicalGPU: NvLogicalGpuHandle, hPhysicalGPU: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the physical GPU handles associated with the specified logical GPU handle.
+    ///
+    /// At least one GPU must be present in the system and running an NVIDIA display driver.
+    ///
+    /// The array hPhysicalGPU will be filled with physical GPU handle values.  The returned
+    /// gpuCount determines how many entries in the array are valid.
+    pub unsafe fn NvAPI_GetPhysicalGPUsFromLogicalGPU;
+}
+
+nvapi! {
+    pub type GetLogicalGPUFromPhysicalGPUFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pLogicalGPU: *mut NvLogicalGpuHandle) -> NvAPI_Status;
+
+    /// This function returns the logical GPU handle associated with specified physical GPU handle.
+    ///
+    /// At least one GPU must be present in the system and running an NVIDIA display driver.
+    pub unsafe fn NvAPI_GetLogicalGPUFromPhysicalGPU;
+}
+
 nvapi! {
     pub type GPU_GetFullNameFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, szName: *mut types::NvAPI_ShortString) -> NvAPI_Status;
 
@@ -80,6 +127,20 @@ nvapi! {
     pub unsafe fn NvAPI_GPU_GetVbiosVersionString;
 }
 
+nvapi! {
+    pub type GPU_GetVbiosRevisionFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pBiosRevision: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the revision of the video BIOS associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetVbiosRevision;
+}
+
+nvapi! {
+    pub type GPU_GetVbiosOEMRevisionFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pBiosRevision: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the OEM revision of the video BIOS associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetVbiosOEMRevision;
+}
+
 nvapi! {
     pub type GPU_GetPCIIdentifiersFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pDeviceId: *mut u32, pSubSystemId: *mut u32, pRevisionId: *mut u32, pExtDeviceId: *mut u32) -> NvAPI_Status;
 
@@ -107,6 +168,35 @@ nvapi! {
     pub unsafe fn NvAPI_GPU_GetSystemType;
 }
 
+nvenum! {
+    /// Used in NvAPI_GPU_GetGPUType()
+    pub enum NV_GPU_TYPE / GpuType {
+        NV_SYSTEM_TYPE_GPU_UNKNOWN / Unknown = 0,
+        /// Integrated GPU
+        NV_SYSTEM_TYPE_IGPU / Integrated = 1,
+        /// Discrete GPU
+        NV_SYSTEM_TYPE_DGPU / Discrete = 2,
+    }
+}
+
+nvenum_display! {
+    GpuType => _
+}
+
+nvapi! {
+    pub type GPU_GetGPUTypeFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pGpuType: *mut NV_GPU_TYPE) -> NvAPI_Status;
+
+    /// This function returns the GPU type (integrated or discrete).
+    pub unsafe fn NvAPI_GPU_GetGPUType;
+}
+
+nvapi! {
+    pub type GPU_GetQuadroStatusFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pStatus: *mut u32) -> NvAPI_Status;
+
+    /// This function retrieves the Quadro status for the GPU (1 if Quadro, 0 if GeForce)
+    pub unsafe fn NvAPI_GPU_GetQuadroStatus;
+}
+
 nvapi! {
     pub type GPU_GetShaderSubPipeCountFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pCount: *mut u32) -> NvAPI_Status;
 
@@ -123,6 +213,65 @@ nvapi! {
     pub unsafe fn NvAPI_GPU_GetGpuCoreCount;
 }
 
+nvapi! {
+    pub type GPU_GetBusIdFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pBusId: *mut u32) -> NvAPI_Status;
+
+    /// Returns the ID of the bus associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetBusId;
+}
+
+nvapi! {
+    pub type GPU_GetBusSlotIdFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pBusSlotId: *mut u32) -> NvAPI_Status;
+
+    /// Returns the ID of the bus slot associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetBusSlotId;
+}
+
+nvenum! {
+    /// Used in NvAPI_GPU_GetBusType()
+    pub enum NV_GPU_BUS_TYPE / BusType {
+        NVAPI_GPU_BUS_TYPE_UNDEFINED / Undefined = 0,
+        NVAPI_GPU_BUS_TYPE_PCI / Pci = 1,
+        NVAPI_GPU_BUS_TYPE_AGP / Agp = 2,
+        NVAPI_GPU_BUS_TYPE_PCI_EXPRESS / PciExpress = 3,
+        NVAPI_GPU_BUS_TYPE_FPCI / Fpci = 4,
+        NVAPI_GPU_BUS_TYPE_AXI / Axi = 5,
+    }
+}
+
+nvenum_display! {
+    BusType => {
+        Pci = "PCI",
+        Agp = "AGP",
+        PciExpress = "PCIe",
+        Fpci = "FPCI",
+        Axi = "AXI",
+        _ = _,
+    }
+}
+
+nvapi! {
+    pub type GPU_GetBusTypeFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pBusType: *mut NV_GPU_BUS_TYPE) -> NvAPI_Status;
+
+    /// This function returns the type of bus associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetBusType;
+}
+
+nvapi! {
+    pub type GPU_GetIRQFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pIRQ: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the interrupt number associated with this GPU.
+    pub unsafe fn NvAPI_GPU_GetIRQ;
+}
+
+nvapi! {
+    pub type GPU_GetCurrentPCIEDownstreamWidthFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pWidth: *mut u32) -> NvAPI_Status;
+
+    /// This function returns the number of PCIE lanes being used for the PCIE interface
+    /// downstream from the GPU.
+    pub unsafe fn NvAPI_GPU_GetCurrentPCIEDownstreamWidth;
+}
+
 nvstruct! {
     pub struct NV_BOARD_INFO_V1 {
         /// structure version
@@ -162,6 +311,169 @@ nvapi! {
     pub unsafe fn NvAPI_GPU_GetPerfDecreaseInfo(hPhysicalGpu: NvPhysicalGpuHandle, pPerfDecrInfo: *mut NVAPI_GPU_PERF_DECREASE) -> NvAPI_Status;
 }
 
+nvenum! {
+    /// Used in NV_GPU_ARCH_INFO
+    pub enum NV_GPU_ARCHITECTURE_ID / ArchitectureId {
+        NV_GPU_ARCHITECTURE_T2X / T2X = 0xe0000020u32,
+        NV_GPU_ARCHITECTURE_T3X / T3X = 0xe0000030u32,
+        /// Also `NV_GPU_ARCHITECTURE_T12X`
+        NV_GPU_ARCHITECTURE_T4X / T4X = 0xe0000040u32,
+        NV_GPU_ARCHITECTURE_NV40 / NV40 = 0x00000040,
+        NV_GPU_ARCHITECTURE_NV50 / NV50 = 0x00000050,
+        NV_GPU_ARCHITECTURE_G78 / G78 = 0x00000060,
+        NV_GPU_ARCHITECTURE_G80 / G80 = 0x00000080,
+        NV_GPU_ARCHITECTURE_G90 / G90 = 0x00000090,
+        NV_GPU_ARCHITECTURE_GT200 / GT200 = 0x000000a0,
+        NV_GPU_ARCHITECTURE_GF100 / GF100 = 0x000000c0,
+        NV_GPU_ARCHITECTURE_GF110 / GF110 = 0x000000d0,
+        NV_GPU_ARCHITECTURE_GK100 / GK100 = 0x000000e0,
+        NV_GPU_ARCHITECTURE_GK110 / GK110 = 0x000000f0,
+        NV_GPU_ARCHITECTURE_GK200 / GK200 = 0x00000100,
+        NV_GPU_ARCHITECTURE_GM000 / GM000 = 0x00000110,
+        NV_GPU_ARCHITECTURE_GM200 / GM200 = 0x00000120,
+        NV_GPU_ARCHITECTURE_GP100 / GP100 = 0x00000130,
+        NV_GPU_ARCHITECTURE_GV100 / GV100 = 0x00000140,
+        NV_GPU_ARCHITECTURE_GV110 / GV110 = 0x00000150,
+        NV_GPU_ARCHITECTURE_TU100 / TU100 = 0x00000160,
+        NV_GPU_ARCHITECTURE_GA100 / GA100 = 0x00000170,
+        NV_GPU_ARCHITECTURE_GH100 / GH100 = 0x00000180,
+        NV_GPU_ARCHITECTURE_AD100 / AD100 = 0x00000190,
+        NV_GPU_ARCHITECTURE_GB100 / GB100 = 0x000001a0,
+        NV_GPU_ARCHITECTURE_GB200 / GB200 = 0x000001b0,
+    }
+}
+
+nvenum_display! {
+    ArchitectureId => _
+}
+
+nvenum! {
+    /// Used in NV_GPU_ARCH_INFO
+    pub enum NV_GPU_CHIP_REVISION / ChipRevision {
+        NV_GPU_CHIP_REV_EMULATION_QT / EmulationQt = 0x00000000,
+        NV_GPU_CHIP_REV_EMULATION_FPGA / EmulationFpga = 0x00000001,
+        NV_GPU_CHIP_REV_A01 / A01 = 0x00000011,
+        NV_GPU_CHIP_REV_A02 / A02 = 0x00000012,
+        NV_GPU_CHIP_REV_A03 / A03 = 0x00000013,
+        NV_GPU_CHIP_REV_UNKNOWN / Unknown = 0xffffffffu32,
+    }
+}
+
+nvenum_display! {
+    ChipRevision => {
+        EmulationQt = "QT Emulation",
+        EmulationFpga = "FPGA Emulation",
+        _ = _,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetArchInfo()
+    ///
+    /// `NV_GPU_ARCH_INFO_V1` shares the same layout.
+    pub struct NV_GPU_ARCH_INFO_V2 {
+        /// structure version
+        pub version: u32,
+        /// NV_GPU_ARCHITECTURE_ID
+        pub architecture: NV_GPU_ARCHITECTURE_ID,
+        /// NV_GPU_ARCH_IMPLEMENTATION_ID, whose values depend on the architecture
+        pub implementation: u32,
+        /// NV_GPU_CHIP_REVISION
+        pub revision: NV_GPU_CHIP_REVISION,
+    }
+}
+
+pub type NV_GPU_ARCH_INFO_V1 = NV_GPU_ARCH_INFO_V2;
+
+nvversion! { NV_GPU_ARCH_INFO_VER_1, NV_GPU_ARCH_INFO_VER_2(NV_GPU_ARCH_INFO_V2 = 4 * 4, 1, 2) }
+nvversion! { NV_GPU_ARCH_INFO_VER = NV_GPU_ARCH_INFO_VER_2 }
+
+pub type NV_GPU_ARCH_INFO = NV_GPU_ARCH_INFO_V2;
+
+nvapi! {
+    pub type GPU_GetArchInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pGpuArchInfo: *mut NV_GPU_ARCH_INFO) -> NvAPI_Status;
+
+    /// This function retrieves the architecture, implementation and chip revision of the GPU.
+    pub unsafe fn NvAPI_GPU_GetArchInfo;
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetGPUInfo()
+    pub struct NV_GPU_INFO_V1 {
+        /// Structure version
+        pub version: u32,
+        /// bit 0 (bIsExternalGpu) is set for external GPUs
+        pub flags: u32,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetGPUInfo()
+    pub struct NV_GPU_INFO_V2 {
+        pub v1: NV_GPU_INFO_V1,
+        pub reserved1: u64,
+        /// Number of ray tracing cores
+        pub rayTracingCores: u32,
+        /// Number of tensor cores
+        pub tensorCores: u32,
+        pub reserved2: [u32; 14],
+    }
+}
+nvinherit! { NV_GPU_INFO_V2(v1: NV_GPU_INFO_V1) }
+
+pub type NV_GPU_INFO = NV_GPU_INFO_V2;
+
+nvversion! { NV_GPU_INFO_VER_1(NV_GPU_INFO_V1 = 4 * 2, 1) }
+nvversion! { NV_GPU_INFO_VER_2(NV_GPU_INFO_V2 = 4 * 2 + 8 + 4 * 2 + 4 * 14, 2) }
+nvversion! { NV_GPU_INFO_VER = NV_GPU_INFO_VER_2 }
+
+nvapi! {
+    pub type GPU_GetGPUInfoFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pGpuInfo: *mut NV_GPU_INFO) -> NvAPI_Status;
+
+    /// This API will return NVIDIA GPU related information.
+    pub unsafe fn NvAPI_GPU_GetGPUInfo;
+}
+
+nvapi! {
+    pub type GPU_GetAdapterIdFromPhysicalGpuFn = extern "C" fn(hPhysicalGpu: NvPhysicalGpuHandle, pOSAdapterId: *mut types::LUID) -> NvAPI_Status;
+
+    /// This function returns the OS adapter ID (LUID) of a physical GPU, as used by the Windows CCD and DXGI APIs.
+    pub unsafe fn NvAPI_GPU_GetAdapterIdFromPhysicalGpu;
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetLogicalGpuInfo()
+    pub struct NV_LOGICAL_GPU_DATA_V1 {
+        /// Structure version
+        pub version: u32,
+        /// Returns the OS adapter ID. Must point to a buffer of at least the size of a LUID.
+        pub pOSAdapterId: *mut types::LUID,
+        /// Number of physical GPU handles associated with the logical GPU
+        pub physicalGpuCount: u32,
+        /// Physical GPU handles associated with the logical GPU
+        pub physicalGpuHandles: [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS],
+        /// Reserved for future use. Should be set to ZERO.
+        pub reserved: [u8; 64],
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+const NV_LOGICAL_GPU_DATA_V1_SIZE: usize = 4 + 4 + 8 + 4 + 4 + 8 * types::NVAPI_MAX_PHYSICAL_GPUS + 64;
+#[cfg(target_pointer_width = "32")]
+const NV_LOGICAL_GPU_DATA_V1_SIZE: usize = 4 + 4 + 4 + 4 * types::NVAPI_MAX_PHYSICAL_GPUS + 64;
+
+nvversion! { NV_LOGICAL_GPU_DATA_VER1(NV_LOGICAL_GPU_DATA_V1 = NV_LOGICAL_GPU_DATA_V1_SIZE, 1) }
+nvversion! { NV_LOGICAL_GPU_DATA_VER = NV_LOGICAL_GPU_DATA_VER1 }
+
+pub type NV_LOGICAL_GPU_DATA = NV_LOGICAL_GPU_DATA_V1;
+
+nvapi! {
+    pub type GPU_GetLogicalGpuInfoFn = extern "C" fn(hLogicalGpu: NvLogicalGpuHandle, pLogicalGpuData: *mut NV_LOGICAL_GPU_DATA) -> NvAPI_Status;
+
+    /// This function returns the OS adapter ID and the physical GPUs of a logical GPU.
+    pub unsafe fn NvAPI_GPU_GetLogicalGpuInfo;
+}
+
 /// Undocumented API
 pub mod private {
     use crate::status::NvAPI_Status;
@@ -158,9 +158,24 @@ pub mod private {
         pub unsafe fn NvAPI_GPU_ClientPowerPoliciesSetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pPowerStatus: *const NV_GPU_POWER_STATUS) -> NvAPI_Status;
     }
 
+    nvenum! {
+        /// The channel measured by a power topology entry.
+        pub enum NV_GPU_POWER_TOPO_DOMAIN / PowerTopologyDomain {
+            NV_GPU_POWER_TOPO_DOMAIN_GPU / Gpu = 0,
+            NV_GPU_POWER_TOPO_DOMAIN_BOARD / Board = 1,
+        }
+    }
+
+    nvenum_display! {
+        PowerTopologyDomain => {
+            Gpu = "GPU",
+            _ = _,
+        }
+    }
+
     nvstruct! {
         pub struct NV_GPU_POWER_TOPO_ENTRY {
-            pub a: u32,
+            pub domain: NV_GPU_POWER_TOPO_DOMAIN,
             pub b: u32,
             pub power: u32,
             pub d: u32,
@@ -180,6 +195,55 @@ pub mod private {
     nvversion! { NV_GPU_POWER_TOPO_VER_1(NV_GPU_POWER_TOPO_V1 = 4 * 2 + 4 * (4 * 4), 1) }
     nvversion! { NV_GPU_POWER_TOPO_VER = NV_GPU_POWER_TOPO_VER_1 }
 
+    nvenum! {
+        /// The rail a power topology channel measures.
+        pub enum NV_GPU_POWER_TOPO_RAIL / PowerTopologyRail {
+            NV_GPU_POWER_TOPO_RAIL_TOTAL_BOARD / TotalBoard = 0,
+            NV_GPU_POWER_TOPO_RAIL_GPU_CORE / GpuCore = 1,
+            NV_GPU_POWER_TOPO_RAIL_MEMORY / Memory = 2,
+            NV_GPU_POWER_TOPO_RAIL_PCIE / Pcie = 3,
+            NV_GPU_POWER_TOPO_RAIL_EIGHT_PIN / EightPin = 4,
+        }
+    }
+
+    nvenum_display! {
+        PowerTopologyRail => {
+            TotalBoard = "Total Board",
+            GpuCore = "GPU Core",
+            Pcie = "PCIe",
+            EightPin = "8-pin",
+            _ = _,
+        }
+    }
+
+    nvstruct! {
+        /// Describes the channel of the `NV_GPU_POWER_TOPO` entry at the same index.
+        pub struct NV_GPU_POWER_TOPO_INFO_ENTRY {
+            pub domain: NV_GPU_POWER_TOPO_DOMAIN,
+            pub rail: NV_GPU_POWER_TOPO_RAIL, // assumption
+            /// Power draw in mW that corresponds to 100%, or 0 if not reported
+            pub reference_mW: u32, // assumption
+            pub d: u32,
+        }
+    }
+
+    nvstruct! {
+        pub struct NV_GPU_POWER_TOPO_INFO_V1 {
+            pub version: u32,
+            pub count: u32,
+            pub entries: [NV_GPU_POWER_TOPO_INFO_ENTRY; 4],
+        }
+    }
+
+    pub type NV_GPU_POWER_TOPO_INFO = NV_GPU_POWER_TOPO_INFO_V1;
+
+    nvversion! { NV_GPU_POWER_TOPO_INFO_VER_1(NV_GPU_POWER_TOPO_INFO_V1 = 4 * 2 + 4 * (4 * 4), 1) }
+    nvversion! { NV_GPU_POWER_TOPO_INFO_VER = NV_GPU_POWER_TOPO_INFO_VER_1 }
+
+    nvapi! {
+        pub unsafe fn NvAPI_GPU_ClientPowerTopologyGetInfo(hPhysicalGPU: NvPhysicalGpuHandle, pPowerTopoInfo: *mut NV_GPU_POWER_TOPO_INFO) -> NvAPI_Status;
+    }
+
     nvapi! {
         pub unsafe fn NvAPI_GPU_ClientPowerTopologyGetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pPowerTopo: *mut NV_GPU_POWER_TOPO) -> NvAPI_Status;
     }
@@ -119,6 +119,94 @@ nvapi! {
     pub unsafe fn NvAPI_GPU_GetCurrentPstate;
 }
 
+nvstruct! {
+    pub struct NV_GPU_PERF_PSTATES_INFO_PSTATE_CLOCK {
+        /// ID of the clock domain
+        pub domainId: clock::NV_GPU_PUBLIC_CLOCK_ID,
+        /// bit 0 indicates if this clock is overclockable
+        pub flags: u32,
+        /// Clock frequency in kHz
+        pub freq: u32,
+    }
+}
+
+impl NV_GPU_PERF_PSTATES_INFO_PSTATE_CLOCK {
+    pub fn flag_overclockable(&self) -> bool {
+        self.flags & 1 != 0
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_PERF_PSTATES_INFO_PSTATE_VOLTAGE {
+        /// ID of the voltage domain, containing flags
+        pub domainId: NV_GPU_PERF_VOLTAGE_INFO_DOMAIN_ID,
+        /// Reserved for future use. Must be set to 0
+        pub flags: u32,
+        /// Voltage in mV
+        pub mvolt: u32,
+    }
+}
+
+nvstruct! {
+    pub struct NV_GPU_PERF_PSTATES_INFO_PSTATE {
+        /// ID of the P-State
+        pub pstateId: NV_GPU_PERF_PSTATE_ID,
+        /// - bit 0 indicates if the PCIE limit is GEN1 or GEN2
+        /// - bit 1 indicates if the Pstate is overclocked or not
+        /// - bit 2 indicates if the Pstate is overclockable or not
+        pub flags: u32,
+        pub clocks: [NV_GPU_PERF_PSTATES_INFO_PSTATE_CLOCK; clock::NVAPI_MAX_GPU_PERF_CLOCKS],
+        pub voltages: [NV_GPU_PERF_PSTATES_INFO_PSTATE_VOLTAGE; clock::NVAPI_MAX_GPU_PERF_VOLTAGES],
+    }
+}
+
+impl NV_GPU_PERF_PSTATES_INFO_PSTATE {
+    pub fn flag_overclocked(&self) -> bool {
+        self.flags & 2 != 0
+    }
+
+    pub fn flag_overclockable(&self) -> bool {
+        self.flags & 4 != 0
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_GPU_GetPstatesInfoEx().
+    pub struct NV_GPU_PERF_PSTATES_INFO_V2 {
+        pub version: u32,
+        /// - bit 0 indicates if perfmon is enabled or not
+        /// - bit 1 indicates if dynamic Pstate is capable or not
+        /// - bit 2 indicates if dynamic Pstate is enable or not
+        pub flags: u32,
+        /// The number of available p-states
+        pub numPstates: u32,
+        /// The number of clock domains supported by each P-State
+        pub numClocks: u32,
+        pub numVoltages: u32,
+        pub pstates: [NV_GPU_PERF_PSTATES_INFO_PSTATE; clock::NVAPI_MAX_GPU_PERF_PSTATES],
+    }
+}
+
+pub type NV_GPU_PERF_PSTATES_INFO = NV_GPU_PERF_PSTATES_INFO_V2;
+
+const NV_GPU_PERF_PSTATES_INFO_PSTATE_SIZE: usize = 4 * 2 + 4 * 3 * clock::NVAPI_MAX_GPU_PERF_CLOCKS + 4 * 3 * clock::NVAPI_MAX_GPU_PERF_VOLTAGES;
+
+nvversion! { NV_GPU_PERF_PSTATES_INFO_VER2, NV_GPU_PERF_PSTATES_INFO_VER3(NV_GPU_PERF_PSTATES_INFO_V2 = 4 * 5 + NV_GPU_PERF_PSTATES_INFO_PSTATE_SIZE * clock::NVAPI_MAX_GPU_PERF_PSTATES, 2, 3) }
+nvversion! { NV_GPU_PERF_PSTATES_INFO_VER = NV_GPU_PERF_PSTATES_INFO_VER3 }
+
+/// Used in NvAPI_GPU_GetPstatesInfoEx() to request the default rather than current settings.
+pub const NV_GPU_PERF_PSTATES_INFO_INPUT_FLAG_DEFAULT: u32 = 1;
+
+nvapi! {
+    pub type GPU_GetPstatesInfoExFn = extern "C" fn(hPhysicalGPU: NvPhysicalGpuHandle, pPerfPstatesInfo: *mut NV_GPU_PERF_PSTATES_INFO, inputFlags: u32) -> NvAPI_Status;
+
+    /// DEPRECATED - use NvAPI_GPU_GetPstates20() instead.
+    ///
+    /// This API retrieves all performance states (P-States) information.
+    /// `inputFlags` bit 0 selects the default settings rather than the current ones.
+    pub unsafe fn NvAPI_GPU_GetPstatesInfoEx;
+}
+
 nvenum! {
     pub enum NV_GPU_PERF_VOLTAGE_INFO_DOMAIN_ID / VoltageInfoDomain {
         NVAPI_GPU_PERF_VOLTAGE_INFO_DOMAIN_CORE / Core = 0,
@@ -292,8 +380,7 @@ const NV_GPU_PERF_PSTATES20_INFO_V1_SIZE: usize = 4 * 5 + NV_GPU_PERF_PSTATES20_
 const NV_GPU_PERF_PSTATES20_INFO_V2_SIZE: usize = NV_GPU_PERF_PSTATES20_INFO_V1_SIZE + 4 + (NV_GPU_PERF_PSTATE20_BASE_VOLTAGE_ENTRY_V1_SIZE) * NVAPI_MAX_GPU_PSTATE20_BASE_VOLTAGES;
 
 nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER1(NV_GPU_PERF_PSTATES20_INFO_V1 = NV_GPU_PERF_PSTATES20_INFO_V1_SIZE, 1) }
-nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER2(NV_GPU_PERF_PSTATES20_INFO_V2 = NV_GPU_PERF_PSTATES20_INFO_V2_SIZE, 2) }
-nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER3(NV_GPU_PERF_PSTATES20_INFO_V2 = NV_GPU_PERF_PSTATES20_INFO_V2_SIZE, 3) }
+nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER2, NV_GPU_PERF_PSTATES20_INFO_VER3(NV_GPU_PERF_PSTATES20_INFO_V2 = NV_GPU_PERF_PSTATES20_INFO_V2_SIZE, 2, 3) }
 nvversion! { NV_GPU_PERF_PSTATES20_INFO_VER = NV_GPU_PERF_PSTATES20_INFO_VER3 }
 
 nvapi! {
@@ -318,10 +405,127 @@ nvapi! {
 pub mod private {
     use crate::status::NvAPI_Status;
 
+    nvenum! {
+        /// Used in NvAPI_GPU_GetPstateClientLimits() and NvAPI_GPU_SetPstateClientLimits().
+        ///
+        /// Not present in any public nvapi header; these values are unverified.
+        pub enum NV_GPU_PERF_PSTATE_CLIENT_LIMIT_ID / PstateClientLimit {
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_HARD / Hard = 0x00000001,
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_SOFT / Soft = 0x00000002,
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_BOTH / Both = 0x00000003,
+        }
+    }
+
+    nvenum_display! {
+        PstateClientLimit => _
+    }
+
     nvapi! {
         pub type GPU_SetPstates20Fn = extern "C" fn(hPhysicalGPU: super::NvPhysicalGpuHandle, pPstatesInfo: *const super::NV_GPU_PERF_PSTATES20_INFO) -> NvAPI_Status;
 
         /// Undocumented private API
         pub unsafe fn NvAPI_GPU_SetPstates20;
     }
+
+    nvapi! {
+        pub type GPU_GetPstateClientLimitsFn = extern "C" fn(hPhysicalGPU: super::NvPhysicalGpuHandle, limitId: NV_GPU_PERF_PSTATE_CLIENT_LIMIT_ID, pPstate: *mut super::NV_GPU_PERF_PSTATE_ID) -> NvAPI_Status;
+
+        /// Undocumented private API
+        pub unsafe fn NvAPI_GPU_GetPstateClientLimits;
+    }
+
+    nvapi! {
+        pub type GPU_SetPstateClientLimitsFn = extern "C" fn(hPhysicalGPU: super::NvPhysicalGpuHandle, limitId: NV_GPU_PERF_PSTATE_CLIENT_LIMIT_ID, pstate: super::NV_GPU_PERF_PSTATE_ID) -> NvAPI_Status;
+
+        /// Undocumented private API
+        pub unsafe fn NvAPI_GPU_SetPstateClientLimits;
+    }
+
+    nvstruct! {
+        /// A clock domain of a performance level.
+        pub struct NV_GPU_PERF_CLOCKS_DOMAIN {
+            /// ID of the clock domain
+            pub domainId: super::clock::NV_GPU_PUBLIC_CLOCK_ID,
+            /// bit 0 indicates if this clock is overclockable (assumption)
+            pub flags: u32,
+            /// Clock frequency in kHz
+            pub freq_kHz: u32,
+        }
+    }
+
+    impl NV_GPU_PERF_CLOCKS_DOMAIN {
+        pub fn flag_overclockable(&self) -> bool {
+            self.flags & 1 != 0
+        }
+    }
+
+    nvstruct! {
+        /// The clocks of a single pstate.
+        pub struct NV_GPU_PERF_CLOCKS_LEVEL {
+            /// ID of the P-State
+            pub pstateId: super::NV_GPU_PERF_PSTATE_ID,
+            /// bit 2 indicates if the Pstate is overclockable (assumption, as in `NV_GPU_PERF_PSTATES_INFO_PSTATE`)
+            pub flags: u32,
+            pub domains: [NV_GPU_PERF_CLOCKS_DOMAIN; super::clock::NVAPI_MAX_GPU_PERF_CLOCKS],
+        }
+    }
+
+    impl NV_GPU_PERF_CLOCKS_LEVEL {
+        pub fn flag_overclockable(&self) -> bool {
+            self.flags & 4 != 0
+        }
+    }
+
+    nvstruct! {
+        /// Used in NvAPI_GPU_GetPerfClocks() and NvAPI_GPU_SetPerfClocks().
+        ///
+        /// Not present in any public nvapi header; the layout is an assumption
+        /// modelled on `NV_GPU_PERF_PSTATES_INFO`.
+        pub struct NV_GPU_PERF_CLOCKS_V1 {
+            pub version: u32,
+            pub flags: u32,
+            /// The number of populated levels
+            pub numLevels: u32,
+            /// The number of populated clock domains of each level
+            pub numDomains: u32,
+            pub levels: [NV_GPU_PERF_CLOCKS_LEVEL; super::clock::NVAPI_MAX_GPU_PERF_PSTATES],
+        }
+    }
+
+    pub type NV_GPU_PERF_CLOCKS = NV_GPU_PERF_CLOCKS_V1;
+
+    const NV_GPU_PERF_CLOCKS_LEVEL_SIZE: usize = 4 * 2 + 4 * 3 * super::clock::NVAPI_MAX_GPU_PERF_CLOCKS;
+
+    nvversion! { NV_GPU_PERF_CLOCKS_VER1(NV_GPU_PERF_CLOCKS_V1 = 4 * 4 + NV_GPU_PERF_CLOCKS_LEVEL_SIZE * super::clock::NVAPI_MAX_GPU_PERF_PSTATES, 1) }
+    nvversion! { NV_GPU_PERF_CLOCKS_VER = NV_GPU_PERF_CLOCKS_VER1 }
+
+    nvapi! {
+        pub type GPU_GetPerfClocksFn = extern "C" fn(hPhysicalGPU: super::NvPhysicalGpuHandle, pPerfClocks: *mut NV_GPU_PERF_CLOCKS) -> NvAPI_Status;
+
+        /// Undocumented private API, superseded by NvAPI_GPU_GetPstates20() on Kepler and newer.
+        pub unsafe fn NvAPI_GPU_GetPerfClocks;
+    }
+
+    nvapi! {
+        pub type GPU_SetPerfClocksFn = extern "C" fn(hPhysicalGPU: super::NvPhysicalGpuHandle, pPerfClocks: *mut NV_GPU_PERF_CLOCKS) -> NvAPI_Status;
+
+        /// Undocumented private API, superseded by NvAPI_GPU_SetPstates20() on Kepler and newer.
+        ///
+        /// Sets the absolute frequencies of every overclockable clock.
+        pub unsafe fn NvAPI_GPU_SetPerfClocks;
+    }
+
+    nvapi! {
+        pub type GPU_EnableDynamicPstatesFn = extern "C" fn(hPhysicalGPU: super::NvPhysicalGpuHandle, bEnable: u32) -> NvAPI_Status;
+
+        /// Undocumented private API
+        pub unsafe fn NvAPI_GPU_EnableDynamicPstates;
+    }
+
+    nvapi! {
+        pub type GPU_EnableOverclockedPstatesFn = extern "C" fn(hPhysicalGPU: super::NvPhysicalGpuHandle, bEnable: u32) -> NvAPI_Status;
+
+        /// Undocumented private API
+        pub unsafe fn NvAPI_GPU_EnableOverclockedPstates;
+    }
 }
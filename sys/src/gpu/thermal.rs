@@ -95,8 +95,7 @@ pub type NV_GPU_THERMAL_SETTINGS_V2 = NV_GPU_THERMAL_SETTINGS_V1; // the only di
 pub type NV_GPU_THERMAL_SETTINGS = NV_GPU_THERMAL_SETTINGS_V2;
 
 const NV_GPU_THERMAL_SETTINGS_V1_SIZE: usize = 4 * 2 + (4 * 5) * NVAPI_MAX_THERMAL_SENSORS_PER_GPU;
-nvversion! { NV_GPU_THERMAL_SETTINGS_VER_1(NV_GPU_THERMAL_SETTINGS_V1 = NV_GPU_THERMAL_SETTINGS_V1_SIZE, 1) }
-nvversion! { NV_GPU_THERMAL_SETTINGS_VER_2(NV_GPU_THERMAL_SETTINGS_V2 = NV_GPU_THERMAL_SETTINGS_V1_SIZE, 2) }
+nvversion! { NV_GPU_THERMAL_SETTINGS_VER_1, NV_GPU_THERMAL_SETTINGS_VER_2(NV_GPU_THERMAL_SETTINGS_V2 = NV_GPU_THERMAL_SETTINGS_V1_SIZE, 1, 2) }
 nvversion! { NV_GPU_THERMAL_SETTINGS_VER = NV_GPU_THERMAL_SETTINGS_VER_2 }
 
 nvapi! {
@@ -181,4 +180,28 @@ pub mod private {
     nvapi! {
         pub unsafe fn NvAPI_GPU_ClientThermalPoliciesSetStatus(hPhysicalGPU: NvPhysicalGpuHandle, pThermalLimit: *const NV_GPU_CLIENT_THERMAL_POLICIES_STATUS) -> NvAPI_Status;
     }
+
+    pub const NVAPI_MAX_THERMAL_SENSORS: usize = 32;
+
+    nvstruct! {
+        pub struct NV_GPU_THERMAL_SENSORS_V2 {
+            pub version: u32,
+            /// Bitmask of the requested sensors, each must be supported by the GPU
+            pub mask: u32,
+            pub reserved: [u32; 8],
+            /// Indexed by mask bit, in 1/256 degrees Celsius
+            pub temperatures: [i32; NVAPI_MAX_THERMAL_SENSORS],
+        }
+    }
+    const NV_GPU_THERMAL_SENSORS_V2_SIZE: usize = 4 * 2 + 4 * 8 + 4 * NVAPI_MAX_THERMAL_SENSORS;
+
+    pub type NV_GPU_THERMAL_SENSORS = NV_GPU_THERMAL_SENSORS_V2;
+
+    nvversion! { NV_GPU_THERMAL_SENSORS_VER_2(NV_GPU_THERMAL_SENSORS_V2 = NV_GPU_THERMAL_SENSORS_V2_SIZE, 2) }
+    nvversion! { NV_GPU_THERMAL_SENSORS_VER = NV_GPU_THERMAL_SENSORS_VER_2 }
+
+    nvapi! {
+        /// Reports hotspot and memory junction temperatures, among others not covered by NvAPI_GPU_GetThermalSettings()
+        pub unsafe fn NvAPI_GPU_ThermalGetSensors(hPhysicalGPU: NvPhysicalGpuHandle, pThermalSensors: *mut NV_GPU_THERMAL_SENSORS) -> NvAPI_Status;
+    }
 }
@@ -34,6 +34,17 @@ pub mod dx;
 
 pub mod dispcontrol;
 
+/// System APIs describe the host the GPUs are installed in, such as its chipset.
+pub mod sysgeneral;
+
+/// An in-process NVAPI implementation backed by simulated GPUs, for testing.
+#[cfg(feature = "mock")]
+pub mod mock;
+
+/// Capture and replay of NVAPI calls, for reproducing issues without the original hardware.
+#[cfg(feature = "record")]
+pub mod record;
+
 pub use nvid::Api;
 pub use nvapi::nvapi_QueryInterface;
 pub use types::*;
@@ -68,5 +79,3 @@ impl From<Infallible> for ArgumentRangeError {
         match e { }
     }
 }
-
-// TODO: NvAPI_SYS_GetChipSetInfo
@@ -5,6 +5,7 @@ macro_rules! nv_declare_handle {
     ) => {
         $(#[$meta])*
         #[derive(Copy, Clone, Debug)]
+        #[repr(transparent)]
         pub struct $name(*const ::std::os::raw::c_void);
 
         impl Default for $name {
@@ -12,6 +13,25 @@ macro_rules! nv_declare_handle {
                 $name(::std::ptr::null())
             }
         }
+
+        #[cfg(feature = "record")]
+        impl crate::record::CallArg for $name {
+            fn arg(&self) -> crate::record::Arg {
+                crate::record::Arg::value(&(self.0 as usize))
+            }
+        }
+
+        impl $name {
+            /// Wraps a raw handle value, as returned by the driver.
+            pub unsafe fn from_raw(ptr: *const ::std::os::raw::c_void) -> Self {
+                $name(ptr)
+            }
+
+            /// The opaque value of the handle.
+            pub fn as_raw(&self) -> *const ::std::os::raw::c_void {
+                self.0
+            }
+        }
     };
 }
 
@@ -208,9 +228,18 @@ macro_rules! nvapi {
         pub unsafe fn $fn($($arg: $arg_ty),*) -> $ret {
             static CACHE: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
 
-            match crate::nvapi::query_interface(crate::nvid::Api::$fn.id(), &CACHE) {
+            let call = || match crate::nvapi::query_interface(crate::nvid::Api::$fn.id(), &CACHE) {
                 Ok(ptr) => ::std::mem::transmute::<_, extern "C" fn($($arg: $arg_ty),*) -> $ret>(ptr)($($arg),*),
                 Err(e) => e.raw(),
+            };
+
+            #[cfg(feature = "record")]
+            {
+                crate::record::dispatch(crate::nvid::Api::$fn, &[$(crate::record::CallArg::arg(&$arg)),*], call)
+            }
+            #[cfg(not(feature = "record"))]
+            {
+                call()
             }
         }
     };
@@ -231,17 +260,23 @@ macro_rules! nvapi {
 
 // No `const fn` yet :(
 macro_rules! nvversion {
-    ($name:ident($struct:ident = $sz:expr, $ver:expr)) => {
-        pub const $name: u32 = ($sz) as u32 | ($ver as u32) << 16;
-        /*pub fn $name() -> u32 {
-            MAKE_NVAPI_VERSION::<$struct>($ver)
-        }*/
+    ($($name:ident),+($struct:ident = $sz:expr, $($ver:expr),+)) => {
+        $(
+            pub const $name: u32 = ($sz) as u32 | ($ver as u32) << 16;
+            /*pub fn $name() -> u32 {
+                MAKE_NVAPI_VERSION::<$struct>($ver)
+            }*/
 
-        mod $name {
-            #[test]
-            fn $name() {
-                assert_eq!(crate::types::GET_NVAPI_SIZE(super::$name), ::std::mem::size_of::<super::$struct>());
+            mod $name {
+                #[test]
+                fn $name() {
+                    assert_eq!(crate::types::GET_NVAPI_SIZE(super::$name), ::std::mem::size_of::<super::$struct>());
+                }
             }
+        )+
+
+        impl crate::types::Versioned for $struct {
+            const VERSIONS: &'static [u32] = &[$($name),+];
         }
     };
     ($name:ident = $target:ident) => {
@@ -0,0 +1,2304 @@
+//! A simulated NVAPI driver for testing without NVIDIA hardware.
+//!
+//! [`Mock::install`] points `nvapi_QueryInterface` at an in-process implementation
+//! that answers calls from a set of configurable virtual GPUs. The raw NVAPI
+//! structs that back each call are exposed directly on [`MockGpu`], so any value
+//! a real driver could report can be simulated. A `None` field makes the
+//! corresponding call fail with `NVAPI_NOT_SUPPORTED`, and any API not handled
+//! here reports `NVAPI_NO_IMPLEMENTATION`.
+//!
+//! State is kept for the lifetime of the [`MockSession`], so `Set*` calls are
+//! visible to later `Get*` calls. Only one session may be installed at a time;
+//! `install` blocks until any other session has been dropped.
+
+use std::sync::{Mutex, MutexGuard};
+use std::borrow::Borrow;
+use std::os::raw::{c_char, c_void};
+use std::ffi::CStr;
+use std::{mem, ptr};
+use crate::status::*;
+use crate::nvid::Api;
+use crate::handles::{NvPhysicalGpuHandle, NvLogicalGpuHandle, NvDisplayHandle, NvUnAttachedDisplayHandle};
+use crate::types::{self, BoolU32, NvBool, NvAPI_ShortString};
+use crate::driverapi::{self, NV_DISPLAY_DRIVER_MEMORY_INFO};
+use crate::gpu::{self, NV_BOARD_INFO};
+use crate::gpu::private::*;
+use crate::gpu::pstate::{self, *, private::*};
+use crate::gpu::clock::{self, *, private::*};
+use crate::gpu::thermal::{self, *, private::*};
+use crate::gpu::cooler::private::{self as cooler, *};
+use crate::gpu::power::private::*;
+use crate::gpu::illum::{self, *};
+use crate::gpu::ecc::*;
+use crate::gpu::display::*;
+use crate::sysgeneral::*;
+use crate::i2c;
+
+/// A simulated physical GPU.
+#[derive(Debug, Clone)]
+pub struct MockGpu {
+    pub full_name: Option<String>,
+    pub short_name: Option<String>,
+    pub vbios_version: Option<String>,
+    pub vbios_revision: Option<u32>,
+    pub vbios_oem_revision: Option<u32>,
+    pub driver_model: Option<u32>,
+    pub gpu_id: Option<u32>,
+    /// `(device_id, subsystem_id, revision_id, ext_device_id)`
+    pub pci_identifiers: Option<(u32, u32, u32, u32)>,
+    pub board_info: Option<NV_BOARD_INFO>,
+    pub arch_info: Option<gpu::NV_GPU_ARCH_INFO>,
+    pub gpu_info: Option<gpu::NV_GPU_INFO>,
+    /// The newest `NV_GPU_INFO` version understood, to simulate older drivers.
+    pub gpu_info_version: u32,
+    /// The OS adapter ID, also reported for the logical GPU by its first physical GPU
+    pub adapter_luid: Option<types::LUID>,
+    pub bus_id: Option<u32>,
+    pub bus_slot_id: Option<u32>,
+    pub bus_type: Option<gpu::NV_GPU_BUS_TYPE>,
+    pub irq: Option<u32>,
+    pub pcie_downstream_width: Option<u32>,
+    pub system_type: Option<gpu::NV_SYSTEM_TYPE>,
+    pub gpu_type: Option<gpu::NV_GPU_TYPE>,
+    /// 1 for Quadro GPUs
+    pub quadro_status: Option<u32>,
+    pub core_count: Option<u32>,
+    pub shader_pipe_count: Option<u32>,
+    pub shader_sub_pipe_count: Option<u32>,
+    pub ram_type: Option<NV_GPU_RAM_TYPE>,
+    pub ram_maker: Option<NV_GPU_RAM_MAKER>,
+    pub ram_bus_width: Option<u32>,
+    pub ram_bank_count: Option<u32>,
+    pub ram_partition_count: Option<u32>,
+    pub foundry: Option<NV_GPU_FOUNDRY>,
+    pub memory_info: Option<NV_DISPLAY_DRIVER_MEMORY_INFO>,
+    /// The newest `NV_DISPLAY_DRIVER_MEMORY_INFO` version understood, to simulate older drivers.
+    pub memory_info_version: u32,
+    /// Indexed by `NV_GPU_CLOCK_FREQUENCIES_CLOCK_TYPE`
+    pub clock_frequencies: [Option<NV_GPU_CLOCK_FREQUENCIES>; NV_GPU_CLOCK_FREQUENCIES_CLOCK_TYPE_NUM as usize],
+    /// The undocumented clock table of `NvAPI_GPU_GetAllClocks`
+    pub all_clocks: Option<NV_CLOCKS_INFO>,
+    pub current_pstate: Option<NV_GPU_PERF_PSTATE_ID>,
+    pub pstates: Option<NV_GPU_PERF_PSTATES20_INFO>,
+    /// Pre-Kepler pstates, as reported by `NvAPI_GPU_GetPstatesInfoEx`.
+    pub pstates_legacy: Option<NV_GPU_PERF_PSTATES_INFO>,
+    pub dynamic_pstates: Option<NV_GPU_DYNAMIC_PSTATES_INFO_EX>,
+    /// `(hard, soft)`
+    pub pstate_client_limits: Option<(NV_GPU_PERF_PSTATE_ID, NV_GPU_PERF_PSTATE_ID)>,
+    pub overclocked_pstates: Option<bool>,
+    pub usages: Option<NV_USAGES_INFO>,
+    pub tachometer: Option<u32>,
+    pub vfp_info: Option<NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO>,
+    /// Frequencies of the undervolted curve, offset by `vfp_control` deltas when read back.
+    pub vfp_status: Option<NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS>,
+    pub vfp_control: Option<NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL>,
+    pub vfp_ranges: Option<NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO>,
+    pub vfp_locks: Option<NV_GPU_PERF_CLIENT_LIMITS>,
+    pub volt_rails_status: Option<NV_GPU_CLIENT_VOLT_RAILS_STATUS>,
+    pub volt_rails_control: Option<NV_GPU_CLIENT_VOLT_RAILS_CONTROL>,
+    pub voltage_domains_status: Option<NV_VOLT_STATUS>,
+    pub voltage_step: Option<NV_VOLT_STATUS>,
+    pub voltage_table: Option<Box<NV_VOLT_TABLE>>,
+    pub power_topology: Option<NV_GPU_POWER_TOPO>,
+    pub power_topology_info: Option<NV_GPU_POWER_TOPO_INFO>,
+    pub power_info: Option<NV_GPU_POWER_INFO>,
+    pub power_status: Option<NV_GPU_POWER_STATUS>,
+    pub thermal_settings: Option<NV_GPU_THERMAL_SETTINGS>,
+    pub thermal_info: Option<NV_GPU_THERMAL_INFO>,
+    pub thermal_status: Option<NV_GPU_CLIENT_THERMAL_POLICIES_STATUS>,
+    /// `mask` holds the supported sensors
+    pub thermal_sensors: Option<NV_GPU_THERMAL_SENSORS>,
+    pub cooler_settings: Option<NV_GPU_COOLER_SETTINGS>,
+    pub fan_coolers_info: Option<NV_GPU_CLIENT_FAN_COOLERS_INFO>,
+    pub fan_coolers_status: Option<NV_GPU_CLIENT_FAN_COOLERS_STATUS>,
+    pub fan_coolers_control: Option<NV_GPU_CLIENT_FAN_COOLERS_CONTROL>,
+    /// Logo and SLI bridge brightness, indexed by `NV_GPU_ILLUMINATION_ATTRIB`
+    pub illumination: [Option<u32>; 2],
+    pub illum_zones_info: Option<NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS>,
+    pub illum_zones_control: Option<NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS>,
+    pub ecc_status: Option<NV_GPU_ECC_STATUS_INFO>,
+    pub ecc_errors: Option<NV_GPU_ECC_ERROR_INFO>,
+    pub ecc_configuration: Option<NV_GPU_ECC_CONFIGURATION_INFO>,
+    pub perf_info: Option<NV_GPU_PERF_INFO>,
+    pub perf_status: Option<NV_GPU_PERF_STATUS>,
+    pub perf_decrease: Option<gpu::NVAPI_GPU_PERF_DECREASE>,
+    pub display_ids: Vec<NV_GPU_DISPLAYIDS>,
+    /// Names of the attached displays driven by this GPU, such as `\\.\DISPLAY1`
+    pub displays: Vec<String>,
+    /// Names of the unattached displays rendered by this GPU
+    pub unattached_displays: Vec<String>,
+    /// EDIDs reported by `NvAPI_GPU_GetEDID`, keyed by `displayId`.
+    /// A display without an entry reports `NVAPI_DATA_NOT_FOUND`.
+    pub edids: Vec<(u32, Vec<u8>)>,
+    /// Registers of the devices on the I2C bus, keyed by their 7-bit address.
+    /// Register addresses are offsets into the device's bytes.
+    pub i2c_devices: Vec<(u8, Vec<u8>)>,
+    /// GPUs sharing a group are linked (SLI) into a single logical GPU.
+    /// Ungrouped GPUs each get their own.
+    pub sli_group: Option<u32>,
+}
+
+impl Default for MockGpu {
+    /// A GeForce GTX 1080 Founders Edition.
+    fn default() -> Self {
+        let mut board_info = NV_BOARD_INFO::zeroed();
+        board_info.BoardNum = *b"0323916047133\0\0\0";
+        let mut arch_info = gpu::NV_GPU_ARCH_INFO::zeroed();
+        arch_info.architecture = gpu::NV_GPU_ARCHITECTURE_GP100;
+        arch_info.implementation = 0x04;
+        arch_info.revision = gpu::NV_GPU_CHIP_REV_A01;
+
+        let mut memory_info = NV_DISPLAY_DRIVER_MEMORY_INFO::zeroed();
+        memory_info.dedicatedVideoMemory = 8 * 1024 * 1024;
+        memory_info.availableDedicatedVideoMemory = 8 * 1024 * 1024 - 0x3000;
+        memory_info.sharedSystemMemory = 8 * 1024 * 1024;
+        memory_info.curAvailableDedicatedVideoMemory = 6 * 1024 * 1024;
+
+        let clocks = |graphics: u32, memory: u32, video: u32| {
+            let mut clocks = NV_GPU_CLOCK_FREQUENCIES::zeroed();
+            for &(domain, frequency) in &[(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, graphics), (NVAPI_GPU_PUBLIC_CLOCK_MEMORY, memory), (NVAPI_GPU_PUBLIC_CLOCK_VIDEO, video)] {
+                let domain = &mut clocks.domain[domain as usize];
+                domain.bIsPresent.set(true);
+                domain.frequency = frequency;
+            }
+            Some(clocks)
+        };
+
+        let mut dynamic_pstates = NV_GPU_DYNAMIC_PSTATES_INFO_EX::zeroed();
+        let mut usages = NV_USAGES_INFO::zeroed();
+        for &(domain, percentage) in &[(NVAPI_GPU_UTILIZATION_DOMAIN_GPU, 12), (NVAPI_GPU_UTILIZATION_DOMAIN_FB, 6), (NVAPI_GPU_UTILIZATION_DOMAIN_VID, 0), (NVAPI_GPU_UTILIZATION_DOMAIN_BUS, 1)] {
+            let util = &mut dynamic_pstates.utilization[domain as usize];
+            util.bIsPresent.set(true);
+            util.percentage = percentage;
+            let usage = &mut usages.usages[domain as usize];
+            usage.bIsPresent.set(true);
+            usage.percentage = percentage;
+        }
+
+        let mut volt_rails_status = NV_GPU_CLIENT_VOLT_RAILS_STATUS::zeroed();
+        volt_rails_status.value_uV = 800000;
+
+        let mut perf_info = NV_GPU_PERF_INFO::zeroed();
+        perf_info.maxUnknown = 1;
+        perf_info.limitSupport = NV_GPU_PERF_FLAGS_POWER_LIMIT | NV_GPU_PERF_FLAGS_THERMAL_LIMIT | NV_GPU_PERF_FLAGS_VOLTAGE_REL_LIMIT | NV_GPU_PERF_FLAGS_VOLTAGE_OP_LIMIT | NV_GPU_PERF_FLAGS_NO_LOAD_LIMIT;
+
+        let mut perf_status = NV_GPU_PERF_STATUS::zeroed();
+        perf_status.limits = NV_GPU_PERF_FLAGS_NO_LOAD_LIMIT;
+        perf_status.unknown = 7;
+
+        let mut display = NV_GPU_DISPLAYIDS::zeroed();
+        display.connectorType = NV_MONITOR_CONN_TYPE_DP;
+        display.displayId = 0x80061082;
+        display.flags = NV_GPU_DISPLAYIDS_FLAGS_ACTIVE | NV_GPU_DISPLAYIDS_FLAGS_OS_VISIBLE | NV_GPU_DISPLAYIDS_FLAGS_CONNECTED | NV_GPU_DISPLAYIDS_FLAGS_PHYSICALLY_CONNECTED;
+
+        let mut gpu = MockGpu {
+            full_name: Some("GeForce GTX 1080".into()),
+            short_name: Some("GP104".into()),
+            vbios_version: Some("86.04.17.00.01".into()),
+            vbios_revision: Some(0x86041700),
+            vbios_oem_revision: Some(0x01),
+            driver_model: Some(0x2000),
+            gpu_id: Some(0x100),
+            pci_identifiers: Some((0x1b8010de, 0x119e10de, 0xa1, 0x1b80)),
+            board_info: Some(board_info),
+            arch_info: Some(arch_info),
+            gpu_info: Some(gpu::NV_GPU_INFO::zeroed()),
+            gpu_info_version: 2,
+            adapter_luid: Some(types::LUID { LowPart: 0x0000d1c4, HighPart: 0 }),
+            bus_id: Some(1),
+            bus_slot_id: Some(0),
+            bus_type: Some(gpu::NVAPI_GPU_BUS_TYPE_PCI_EXPRESS),
+            irq: Some(16),
+            pcie_downstream_width: Some(16),
+            system_type: Some(gpu::NV_SYSTEM_TYPE_DESKTOP),
+            gpu_type: Some(gpu::NV_SYSTEM_TYPE_DGPU),
+            quadro_status: Some(0),
+            core_count: Some(2560),
+            shader_pipe_count: Some(2560),
+            shader_sub_pipe_count: Some(20),
+            ram_type: Some(NV_GPU_RAM_GDDR5X),
+            ram_maker: Some(NV_GPU_RAM_MAKER_MICRON),
+            ram_bus_width: Some(256),
+            ram_bank_count: Some(8),
+            ram_partition_count: Some(8),
+            foundry: Some(NV_GPU_FOUNDRY_TSMC),
+            memory_info: Some(memory_info),
+            memory_info_version: 3,
+            clock_frequencies: [
+                clocks(1733000, 5005000, 1620000),
+                clocks(1607000, 5005000, 1477000),
+                clocks(1733000, 5005000, 1620000),
+            ],
+            all_clocks: None,
+            current_pstate: Some(NVAPI_GPU_PERF_PSTATE_P0),
+            pstates: None,
+            pstates_legacy: None,
+            dynamic_pstates: Some(dynamic_pstates),
+            pstate_client_limits: Some((NVAPI_GPU_PERF_PSTATE_P0, NVAPI_GPU_PERF_PSTATE_P0)),
+            overclocked_pstates: Some(false),
+            usages: Some(usages),
+            tachometer: Some(1100),
+            vfp_info: None,
+            vfp_status: None,
+            vfp_control: None,
+            vfp_ranges: None,
+            vfp_locks: None,
+            volt_rails_status: Some(volt_rails_status),
+            volt_rails_control: Some(NV_GPU_CLIENT_VOLT_RAILS_CONTROL::zeroed()),
+            voltage_domains_status: None,
+            voltage_step: None,
+            voltage_table: None,
+            power_topology: None,
+            power_topology_info: None,
+            power_info: None,
+            power_status: None,
+            thermal_settings: None,
+            thermal_info: None,
+            thermal_status: None,
+            thermal_sensors: None,
+            cooler_settings: None,
+            fan_coolers_info: None,
+            fan_coolers_status: None,
+            fan_coolers_control: None,
+            illumination: [None; 2],
+            illum_zones_info: None,
+            illum_zones_control: None,
+            ecc_status: None,
+            ecc_errors: None,
+            ecc_configuration: None,
+            perf_info: Some(perf_info),
+            perf_status: Some(perf_status),
+            perf_decrease: Some(gpu::NV_GPU_PERF_DECREASE_NONE),
+            display_ids: vec![display],
+            displays: vec!["\\\\.\\DISPLAY1".into()],
+            unattached_displays: Vec::new(),
+            edids: Vec::new(),
+            i2c_devices: Vec::new(),
+            sli_group: None,
+        };
+
+        gpu.add_pstate(NVAPI_GPU_PERF_PSTATE_P0, true, &[
+            MockClock::range(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, 139000, 2088500, 600000, 1093000).editable(-250000, 250000),
+            MockClock::single(NVAPI_GPU_PUBLIC_CLOCK_MEMORY, 5005000).editable(-1000000, 1000000),
+        ]);
+        gpu.add_pstate(NVAPI_GPU_PERF_PSTATE_P2, false, &[
+            MockClock::range(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, 139000, 2088500, 600000, 1093000),
+            MockClock::single(NVAPI_GPU_PUBLIC_CLOCK_MEMORY, 4513000),
+        ]);
+        gpu.add_pstate(NVAPI_GPU_PERF_PSTATE_P8, false, &[
+            MockClock::range(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, 139000, 607500, 600000, 1093000),
+            MockClock::single(NVAPI_GPU_PUBLIC_CLOCK_MEMORY, 405000),
+        ]);
+        gpu.set_vf_curve(
+            &(0..16).map(|i| (1215000 + i * 50000, 700000 + i * 25000)).collect::<Vec<_>>(),
+            &[(5005000, 0)],
+        );
+        gpu.add_sensor(NVAPI_THERMAL_CONTROLLER_GPU_INTERNAL, NVAPI_THERMAL_TARGET_GPU, (0, 127), 41, (65, 83, 92));
+        gpu.add_cooler(NVAPI_COOLER_TYPE_FAN, NVAPI_COOLER_TARGET_ALL, NVAPI_COOLER_POLICY_TEMPERATURE_CONTINUOUS, (0, 100), 30);
+        gpu.add_power_policy(NVAPI_GPU_PERF_PSTATE_P0, 50000, 100000, 120000, 42000);
+        gpu.describe_power_channel(0, NV_GPU_POWER_TOPO_RAIL_TOTAL_BOARD, 180000);
+
+        gpu
+    }
+}
+
+/// Describes a pstates20 clock entry for `MockGpu::add_pstate`.
+#[derive(Debug, Copy, Clone)]
+pub struct MockClock(pub NV_GPU_PSTATE20_CLOCK_ENTRY_V1);
+
+impl MockClock {
+    pub fn single(domain: NV_GPU_PUBLIC_CLOCK_ID, freq_kHz: u32) -> Self {
+        let mut entry = NV_GPU_PSTATE20_CLOCK_ENTRY_V1::zeroed();
+        entry.domainId = domain;
+        entry.typeId = NVAPI_GPU_PERF_PSTATE20_CLOCK_TYPE_SINGLE;
+        entry.data.set_single(NV_GPU_PSTATE20_CLOCK_ENTRY_SINGLE { freq_kHz });
+        MockClock(entry)
+    }
+
+    pub fn range(domain: NV_GPU_PUBLIC_CLOCK_ID, minFreq_kHz: u32, maxFreq_kHz: u32, minVoltage_uV: u32, maxVoltage_uV: u32) -> Self {
+        let mut entry = NV_GPU_PSTATE20_CLOCK_ENTRY_V1::zeroed();
+        entry.domainId = domain;
+        entry.typeId = NVAPI_GPU_PERF_PSTATE20_CLOCK_TYPE_RANGE;
+        entry.data.set_range(NV_GPU_PSTATE20_CLOCK_ENTRY_RANGE {
+            minFreq_kHz,
+            maxFreq_kHz,
+            domainId: NVAPI_GPU_PERF_VOLTAGE_INFO_DOMAIN_CORE,
+            minVoltage_uV,
+            maxVoltage_uV,
+        });
+        MockClock(entry)
+    }
+
+    /// Allows the frequency delta to be set within `min..=max` kHz.
+    pub fn editable(mut self, min: i32, max: i32) -> Self {
+        self.0.bIsEditable.set(true);
+        self.0.freqDelta_kHz.min = min;
+        self.0.freqDelta_kHz.max = max;
+        self
+    }
+}
+
+impl MockGpu {
+    /// Appends a pstate to the pstates20 table. The first call determines the
+    /// number of clocks per pstate, and adds an editable core base voltage.
+    pub fn add_pstate(&mut self, id: NV_GPU_PERF_PSTATE_ID, editable: bool, clocks: &[MockClock]) {
+        let info = self.pstates.get_or_insert_with(|| {
+            let mut info = NV_GPU_PERF_PSTATES20_INFO::zeroed();
+            info.bIsEditable.set(true);
+            info.numClocks = clocks.len() as _;
+            info.numBaseVoltages = 1;
+            info
+        });
+        let index = info.numPstates as usize;
+        if index >= info.pstates.len() || clocks.len() > info.pstates[index].clocks.len() {
+            return
+        }
+        let pstate = &mut info.pstates[index];
+        pstate.pstateId = id;
+        pstate.bIsEditable.set(editable);
+        for (entry, clock) in pstate.clocks.iter_mut().zip(clocks) {
+            *entry = clock.0;
+        }
+        let voltage = &mut pstate.baseVoltages[0];
+        voltage.domainId = NVAPI_GPU_PERF_VOLTAGE_INFO_DOMAIN_CORE;
+        voltage.bIsEditable.set(editable);
+        voltage.volt_uV = 1050000;
+        if editable {
+            voltage.voltDelta_uV.max = 100000;
+        }
+        info.numPstates += 1;
+    }
+
+    /// Appends an editable overvolt entry to the pstates20 table, allowing deltas of `0..=max_uV`.
+    pub fn add_overvolt(&mut self, domain: NV_GPU_PERF_VOLTAGE_INFO_DOMAIN_ID, volt_uV: u32, max_uV: i32) {
+        let info = match self.pstates.as_mut() {
+            Some(info) if (info.numVoltages as usize) < info.voltages.len() => info,
+            _ => return,
+        };
+        let voltage = &mut info.voltages[info.numVoltages as usize];
+        voltage.domainId = domain;
+        voltage.bIsEditable.set(true);
+        voltage.volt_uV = volt_uV;
+        voltage.voltDelta_uV.max = max_uV;
+        info.numVoltages += 1;
+    }
+
+    /// Appends a pstate to the legacy pstates table with clocks of `(domain, freq_kHz)`
+    /// and a core voltage. The first call determines the number of clocks per pstate.
+    pub fn add_pstate_legacy(&mut self, id: NV_GPU_PERF_PSTATE_ID, overclockable: bool, clocks: &[(NV_GPU_PUBLIC_CLOCK_ID, u32)], voltage_mV: u32) {
+        let info = self.pstates_legacy.get_or_insert_with(|| {
+            let mut info = NV_GPU_PERF_PSTATES_INFO::zeroed();
+            info.numClocks = clocks.len() as _;
+            info.numVoltages = 1;
+            info
+        });
+        let index = info.numPstates as usize;
+        if index >= info.pstates.len() || clocks.len() > info.pstates[index].clocks.len() {
+            return
+        }
+        let pstate = &mut info.pstates[index];
+        pstate.pstateId = id;
+        pstate.flags = if overclockable { 4 } else { 0 };
+        for (entry, &(domain, freq)) in pstate.clocks.iter_mut().zip(clocks) {
+            entry.domainId = domain;
+            entry.flags = overclockable as u32;
+            entry.freq = freq;
+        }
+        pstate.voltages[0].domainId = NVAPI_GPU_PERF_VOLTAGE_INFO_DOMAIN_CORE;
+        pstate.voltages[0].mvolt = voltage_mV;
+        info.numPstates += 1;
+    }
+
+    /// Replaces the VF curve with graphics and memory points of `(freq_kHz, voltage_uV)`.
+    ///
+    /// Graphics points use mask bits `0..`, memory points follow at bit 80.
+    pub fn set_vf_curve(&mut self, graphics: &[(u32, u32)], memory: &[(u32, u32)]) {
+        let mut vfp_info = NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO::zeroed();
+        let mut vfp_status = NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS::zeroed();
+        let mut vfp_control = NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL::zeroed();
+        let mem_offset = vfp_status.gpuEntries.len();
+
+        for (i, &(freq, voltage)) in graphics.iter().enumerate().take(mem_offset) {
+            set_mask_bit(&mut vfp_info.mask, i);
+            vfp_info.clocks[i].gpuDelta = 1;
+            let entry = &mut vfp_status.gpuEntries[i];
+            entry.freq_kHz = freq * 2;
+            entry.voltage_uV = voltage;
+        }
+        for (i, &(freq, voltage)) in memory.iter().enumerate().take(vfp_status.memEntries.len()) {
+            set_mask_bit(&mut vfp_info.mask, mem_offset + i);
+            vfp_info.clocks[mem_offset + i].memDelta = 1;
+            let entry = &mut vfp_status.memEntries[i];
+            entry.freq_kHz = freq;
+            entry.voltage_uV = voltage;
+            vfp_control.memFilled[i] = 1;
+        }
+        vfp_status.mask = vfp_info.mask;
+        vfp_control.mask = vfp_info.mask;
+
+        let mut vfp_ranges = NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO::zeroed();
+        for &(domain, max) in &[(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS, 1000000), (NVAPI_GPU_PUBLIC_CLOCK_MEMORY, 2000000)] {
+            let entry = &mut vfp_ranges.entries[vfp_ranges.numClocks as usize];
+            entry.clockType = domain;
+            entry.rangeMin = -max;
+            entry.rangeMax = max;
+            entry.tempMax = 0;
+            vfp_ranges.numClocks += 1;
+        }
+        // the trailing entry is not reported
+        vfp_ranges.numClocks += 1;
+
+        let mut vfp_locks = NV_GPU_PERF_CLIENT_LIMITS::zeroed();
+        for id in 0..2 {
+            vfp_locks.entries[id].id = id as u32 * 2;
+            vfp_locks.count += 1;
+        }
+
+        self.vfp_info = Some(vfp_info);
+        self.vfp_status = Some(vfp_status);
+        self.vfp_control = Some(vfp_control);
+        self.vfp_ranges = Some(vfp_ranges);
+        self.vfp_locks = Some(vfp_locks);
+    }
+
+    /// Adds a thermal sensor with a `(min, max)` range, current temperature, and
+    /// a `(min, default, max)` thermal policy limit.
+    pub fn add_sensor(&mut self, controller: NV_THERMAL_CONTROLLER, target: NV_THERMAL_TARGET, range: (i32, i32), current: i32, limit: (i32, i32, i32)) {
+        let settings = self.thermal_settings.get_or_insert_with(NV_GPU_THERMAL_SETTINGS::zeroed);
+        let index = settings.count as usize;
+        if index >= settings.sensor.len() {
+            return
+        }
+        settings.sensor[index] = NV_GPU_THERMAL_SETTINGS_SENSOR {
+            controller,
+            defaultMinTemp: range.0,
+            defaultMaxTemp: range.1,
+            currentTemp: current,
+            target,
+        };
+        settings.count += 1;
+
+        let info = self.thermal_info.get_or_insert_with(NV_GPU_THERMAL_INFO::zeroed);
+        let status = self.thermal_status.get_or_insert_with(NV_GPU_CLIENT_THERMAL_POLICIES_STATUS::zeroed);
+        let index = info.count as usize;
+        if index >= info.entries.len() {
+            return
+        }
+        info.entries[index] = NV_GPU_THERMAL_INFO_ENTRY {
+            controller,
+            unknown: 0,
+            minTemp: limit.0 << 8,
+            defaultTemp: limit.1 << 8,
+            maxTemp: limit.2 << 8,
+            defaultFlags: 1,
+        };
+        info.count += 1;
+        info.flags = 1;
+        status.entries[index] = NV_GPU_CLIENT_THERMAL_POLICIES_STATUS_ENTRY {
+            controller,
+            value: (limit.1 << 8) as u32,
+            flags: 1,
+        };
+        // the entry count is stored in the flags field
+        status.flags += 1;
+    }
+
+    /// Adds a sensor to the thermal sensor mask, with a temperature in degrees Celsius.
+    pub fn add_thermal_sensor(&mut self, index: usize, temperature: i32) {
+        let sensors = self.thermal_sensors.get_or_insert_with(NV_GPU_THERMAL_SENSORS::zeroed);
+        if index >= sensors.temperatures.len() {
+            return
+        }
+        sensors.mask |= 1 << index;
+        sensors.temperatures[index] = temperature << 8;
+    }
+
+    /// Adds a variable speed cooler with an allowed `(min, max)` level range.
+    pub fn add_cooler(&mut self, kind: NV_COOLER_TYPE, target: NV_COOLER_TARGET, policy: NV_COOLER_POLICY, range: (u32, u32), level: u32) {
+        let settings = self.cooler_settings.get_or_insert_with(NV_GPU_COOLER_SETTINGS::zeroed);
+        let index = settings.count as usize;
+        if index >= settings.cooler.len() {
+            return
+        }
+        settings.cooler[index] = NV_GPU_COOLER_SETTINGS_COOLER {
+            type_: kind,
+            controller: NVAPI_COOLER_CONTROLLER_INTERNAL,
+            defaultMinLevel: range.0,
+            defaultMaxLevel: range.1,
+            currentMinLevel: range.0,
+            currentMaxLevel: range.1,
+            currentLevel: level,
+            defaultPolicy: policy,
+            currentPolicy: policy,
+            target,
+            controlType: NVAPI_COOLER_CONTROL_VARIABLE,
+            active: NVAPI_ACTIVE,
+        };
+        settings.count += 1;
+    }
+
+    /// Adds a client fan cooler with an allowed `(min, max)` level range, in automatic control mode.
+    pub fn add_fan_cooler(&mut self, id: u32, max_rpm: u32, range: (u32, u32), level: u32) {
+        let info = self.fan_coolers_info.get_or_insert_with(NV_GPU_CLIENT_FAN_COOLERS_INFO::zeroed);
+        let status = self.fan_coolers_status.get_or_insert_with(NV_GPU_CLIENT_FAN_COOLERS_STATUS::zeroed);
+        let control = self.fan_coolers_control.get_or_insert_with(NV_GPU_CLIENT_FAN_COOLERS_CONTROL::zeroed);
+        let index = info.count as usize;
+        if index >= info.coolers.len() {
+            return
+        }
+        info.coolers[index].coolerId = id;
+        info.coolers[index].maxRpm = max_rpm;
+        status.coolers[index] = NV_GPU_CLIENT_FAN_COOLERS_STATUS_ENTRY {
+            coolerId: id,
+            currentRpm: max_rpm * level / 100,
+            currentMinLevel: range.0,
+            currentMaxLevel: range.1,
+            currentLevel: level,
+            reserved: Default::default(),
+        };
+        control.coolers[index] = NV_GPU_CLIENT_FAN_COOLERS_CONTROL_ENTRY {
+            coolerId: id,
+            level,
+            controlMode: NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE_AUTO,
+            reserved: Default::default(),
+        };
+        info.count += 1;
+        status.count += 1;
+        control.count += 1;
+    }
+
+    /// Adds an illumination zone in manual control mode at full brightness, lit white if it has a colour.
+    pub fn add_illum_zone(&mut self, kind: IllumZoneType, location: IllumZoneLocation) {
+        use crate::gpu::illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE as Value;
+
+        let info = self.illum_zones_info.get_or_insert_with(NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS::zeroed);
+        let control = self.illum_zones_control.get_or_insert_with(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS::zeroed);
+        let index = info.numIllumZones as usize;
+        if index >= info.zones.len() {
+            return
+        }
+        info.zones[index].type_ = kind.raw();
+        info.zones[index].zoneLocation = location.raw();
+        let zone = &mut control.zones[index];
+        zone.type_ = kind.raw();
+        zone.ctrlMode = NV_GPU_CLIENT_ILLUM_CTRL_MODE_MANUAL;
+        zone.data.set(match kind {
+            IllumZoneType::Rgb => Value::ManualRgb(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGB_PARAMS {
+                colorR: 0xff, colorG: 0xff, colorB: 0xff, brightnessPct: 100,
+            }),
+            IllumZoneType::Rgbw => Value::ManualRgbw(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_RGBW_PARAMS {
+                colorR: 0, colorG: 0, colorB: 0, colorW: 0xff, brightnessPct: 100,
+            }),
+            _ => Value::ManualBrightness(NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_MANUAL_BRIGHTNESS_PARAMS {
+                brightnessPct: 100,
+            }),
+        });
+        info.numIllumZones += 1;
+        control.numIllumZonesControl += 1;
+    }
+
+    /// Enables ECC support, as on workstation boards.
+    pub fn add_ecc(&mut self, configuration: NV_ECC_CONFIGURATION, enabled: bool) {
+        let mut status = NV_GPU_ECC_STATUS_INFO::zeroed();
+        status.isSupported = BoolU32(1);
+        status.configurationOptions = configuration;
+        status.isEnabled = BoolU32(enabled as u32);
+        let mut config = NV_GPU_ECC_CONFIGURATION_INFO::zeroed();
+        config.flags = enabled as u32 | 2;
+        self.ecc_status = Some(status);
+        self.ecc_errors = Some(NV_GPU_ECC_ERROR_INFO::zeroed());
+        self.ecc_configuration = Some(config);
+    }
+
+    /// Records memory errors in both the current and aggregate ECC counters.
+    pub fn add_ecc_errors(&mut self, single_bit: u64, double_bit: u64) {
+        if let Some(errors) = self.ecc_errors.as_mut() {
+            for counters in &mut [&mut errors.current, &mut errors.aggregate] {
+                counters.singleBitErrors += single_bit;
+                counters.doubleBitErrors += double_bit;
+            }
+        }
+    }
+
+    /// Adds a power policy, with limits and the current topology reading in
+    /// thousandths of a percent of TDP.
+    pub fn add_power_policy(&mut self, pstate: NV_GPU_PERF_PSTATE_ID, min: u32, default: u32, max: u32, usage: u32) {
+        let info = self.power_info.get_or_insert_with(NV_GPU_POWER_INFO::zeroed);
+        let status = self.power_status.get_or_insert_with(NV_GPU_POWER_STATUS::zeroed);
+        let topology = self.power_topology.get_or_insert_with(NV_GPU_POWER_TOPO::zeroed);
+        let index = info.count as usize;
+        if index >= info.entries.len() {
+            return
+        }
+        let entry = &mut info.entries[index];
+        entry.pstate = pstate as u32;
+        entry.min_power = min;
+        entry.def_power = default;
+        entry.max_power = max;
+        info.count += 1;
+        info.valid = 1;
+        status.entries[index].power = default;
+        status.count += 1;
+        topology.entries[index].power = usage;
+        topology.count += 1;
+    }
+
+    /// Names the power topology channel at `index`, with the power draw in mW
+    /// that its readings are relative to.
+    pub fn describe_power_channel(&mut self, index: usize, rail: NV_GPU_POWER_TOPO_RAIL, reference_mw: u32) {
+        let domain = match self.power_topology {
+            Some(ref topology) if index < topology.count as usize => topology.entries[index].domain,
+            _ => return,
+        };
+        let info = self.power_topology_info.get_or_insert_with(NV_GPU_POWER_TOPO_INFO::zeroed);
+        info.count = info.count.max(index as u32 + 1);
+        let entry = &mut info.entries[index];
+        entry.domain = domain;
+        entry.rail = rail;
+        entry.reference_mW = reference_mw;
+    }
+}
+
+/// Configuration for a simulated driver.
+#[derive(Debug, Clone)]
+pub struct Mock {
+    pub driver_version: u32,
+    pub driver_branch: String,
+    pub interface_version: String,
+    pub chipset: Option<NV_CHIPSET_INFO>,
+    /// Only reported by notebooks
+    pub lid_dock: Option<NV_LID_DOCK_PARAMS>,
+    pub gpus: Vec<MockGpu>,
+}
+
+impl Default for Mock {
+    /// A single default `MockGpu`.
+    fn default() -> Self {
+        Mock::new(vec![Default::default()])
+    }
+}
+
+impl Mock {
+    /// A desktop with an Intel Z370 chipset.
+    pub fn new(gpus: Vec<MockGpu>) -> Self {
+        let mut chipset = NV_CHIPSET_INFO::zeroed();
+        chipset.vendorId = 0x8086;
+        chipset.deviceId = 0xa2c9;
+        chipset.szVendorName = short_string("Intel");
+        chipset.szChipsetName = short_string("Z370");
+        chipset.subSysVendorId = 0x1462;
+        chipset.subSysDeviceId = 0x7b61;
+        chipset.szSubSysVendorName = short_string("MSI");
+        chipset.HBvendorId = 0x8086;
+        chipset.HBdeviceId = 0x3ec2;
+        chipset.HBsubSysVendorId = 0x1462;
+        chipset.HBsubSysDeviceId = 0x7b61;
+
+        Mock {
+            driver_version: 43142,
+            driver_branch: "r430_00".into(),
+            interface_version: "NVidia Complete Version 1.10".into(),
+            chipset: Some(chipset),
+            lid_dock: None,
+            gpus,
+        }
+    }
+
+    /// Installs the mock as the NVAPI implementation until the returned session is dropped.
+    pub fn install(self) -> MockSession {
+        let guard = lock(&SESSION);
+        *lock(&STATE) = Some(State {
+            gpus: self.gpus.iter().cloned().map(|gpu| GpuState {
+                current: gpu.clone(),
+                defaults: gpu,
+            }).collect(),
+            mock: self,
+            generation: 0,
+        });
+
+        unsafe {
+            crate::nvapi::set_query_interface(query_interface);
+        }
+
+        MockSession {
+            _guard: guard,
+        }
+    }
+}
+
+/// An installed `Mock`, uninstalled when dropped.
+pub struct MockSession {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl MockSession {
+    /// A snapshot of the current state of a GPU.
+    pub fn gpu(&self, index: usize) -> Option<MockGpu> {
+        lock(&STATE).as_ref().and_then(|state| state.gpus.get(index)).map(|gpu| gpu.current.clone())
+    }
+
+    /// Modifies the current state of a GPU, such as to simulate a new sensor reading.
+    pub fn update_gpu<R, F: FnOnce(&mut MockGpu) -> R>(&self, index: usize, f: F) -> Option<R> {
+        lock(&STATE).as_mut().and_then(|state| state.gpus.get_mut(index)).map(|gpu| f(&mut gpu.current))
+    }
+
+    /// Simulates a display or SLI reconfiguration, invalidating all handles.
+    ///
+    /// The GPUs are subsequently enumerated in `order`, given as indices into the current order.
+    /// Any GPUs left out are removed.
+    pub fn reconfigure(&self, order: &[usize]) {
+        if let Some(state) = lock(&STATE).as_mut() {
+            let mut gpus: Vec<_> = state.gpus.drain(..).map(Some).collect();
+            state.gpus = order.iter().filter_map(|&index| gpus.get_mut(index).and_then(Option::take)).collect();
+            state.generation += 1;
+        }
+    }
+}
+
+impl Drop for MockSession {
+    fn drop(&mut self) {
+        *lock(&STATE) = None;
+    }
+}
+
+struct GpuState {
+    current: MockGpu,
+    defaults: MockGpu,
+}
+
+struct State {
+    mock: Mock,
+    gpus: Vec<GpuState>,
+    /// Incremented whenever previously enumerated handles are invalidated
+    generation: usize,
+}
+
+impl State {
+    /// Indices of the physical GPUs making up each logical GPU
+    fn logical_gpus(&self) -> Vec<Vec<usize>> {
+        let mut logical: Vec<(Option<u32>, Vec<usize>)> = Vec::new();
+        for (index, gpu) in self.gpus.iter().enumerate() {
+            match logical.iter_mut().find(|(group, _)| group.is_some() && *group == gpu.current.sli_group) {
+                Some((_, gpus)) => gpus.push(index),
+                None => logical.push((gpu.current.sli_group, vec![index])),
+            }
+        }
+        logical.into_iter().map(|(_, gpus)| gpus).collect()
+    }
+
+    /// The owning GPU index and name of every attached or unattached display
+    fn displays(&self, unattached: bool) -> Vec<(usize, &str)> {
+        self.gpus.iter().enumerate().flat_map(|(index, gpu)| {
+            let names = if unattached { &gpu.current.unattached_displays } else { &gpu.current.displays };
+            names.iter().map(move |name| (index, &name[..]))
+        }).collect()
+    }
+
+    fn display(&self, unattached: bool, handle: *const c_void) -> Result<(usize, &str), NvAPI_Status> {
+        let (base, expected) = display_handle_kind(unattached);
+        match handle_index(base, handle) {
+            Some((generation, _)) if generation != self.generation => Err(NVAPI_HANDLE_INVALIDATED),
+            Some((_, index)) => self.displays(unattached).get(index).cloned().ok_or(expected),
+            None => Err(expected),
+        }
+    }
+}
+
+/// `(handle base, status for handles that don't belong to this kind)`
+fn display_handle_kind(unattached: bool) -> (usize, NvAPI_Status) {
+    match unattached {
+        false => (DISPLAY_HANDLE_BASE, NVAPI_EXPECTED_DISPLAY_HANDLE),
+        true => (UNATTACHED_DISPLAY_HANDLE_BASE, NVAPI_EXPECTED_UNATTACHED_DISPLAY_HANDLE),
+    }
+}
+
+static SESSION: Mutex<()> = Mutex::new(());
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+
+fn lock<T>(mutex: &'static Mutex<T>) -> MutexGuard<'static, T> {
+    // a panicking test must not take down every other test with it
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+const HANDLE_BASE: usize = 0x4e560000;
+const LOGICAL_HANDLE_BASE: usize = 0x4e4c0000;
+const DISPLAY_HANDLE_BASE: usize = 0x4e440000;
+const UNATTACHED_DISPLAY_HANDLE_BASE: usize = 0x4e480000;
+const HANDLE_STRIDE: usize = 0x10;
+const HANDLE_GENERATION_STRIDE: usize = 0x1000;
+
+fn handle_raw(base: usize, generation: usize, index: usize) -> *const c_void {
+    (base + generation * HANDLE_GENERATION_STRIDE + (index + 1) * HANDLE_STRIDE) as *const c_void
+}
+
+fn gpu_handle(generation: usize, index: usize) -> NvPhysicalGpuHandle {
+    unsafe { NvPhysicalGpuHandle::from_raw(handle_raw(HANDLE_BASE, generation, index)) }
+}
+
+fn logical_gpu_handle(generation: usize, index: usize) -> NvLogicalGpuHandle {
+    unsafe { NvLogicalGpuHandle::from_raw(handle_raw(LOGICAL_HANDLE_BASE, generation, index)) }
+}
+
+/// `(generation, index)`
+fn gpu_index(handle: NvPhysicalGpuHandle) -> Option<(usize, usize)> {
+    handle_index(HANDLE_BASE, handle.as_raw())
+}
+
+fn logical_gpu_index(handle: NvLogicalGpuHandle) -> Option<(usize, usize)> {
+    handle_index(LOGICAL_HANDLE_BASE, handle.as_raw())
+}
+
+fn handle_index(base: usize, handle: *const c_void) -> Option<(usize, usize)> {
+    match (handle as usize).checked_sub(base) {
+        Some(offset) => {
+            let (generation, offset) = (offset / HANDLE_GENERATION_STRIDE, offset % HANDLE_GENERATION_STRIDE);
+            match offset {
+                offset if offset % HANDLE_STRIDE == 0 && offset > 0 => Some((generation, offset / HANDLE_STRIDE - 1)),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+fn set_mask_bit(mask: &mut [u32; 4], bit: usize) {
+    mask[bit / 32] |= 1 << (bit % 32);
+}
+
+fn get_mask_bit(mask: &[u32; 4], bit: usize) -> bool {
+    mask.get(bit / 32).map(|m| m & (1 << (bit % 32)) != 0).unwrap_or(false)
+}
+
+fn with_state<F: FnOnce(&mut State) -> NvAPI_Status>(f: F) -> NvAPI_Status {
+    match lock(&STATE).as_mut() {
+        Some(state) => f(state),
+        None => NVAPI_API_NOT_INITIALIZED,
+    }
+}
+
+fn with_gpu<F: FnOnce(&mut GpuState) -> NvAPI_Status>(handle: NvPhysicalGpuHandle, f: F) -> NvAPI_Status {
+    with_state(|state| match gpu_index(handle) {
+        Some((generation, _)) if generation != state.generation => NVAPI_HANDLE_INVALIDATED,
+        Some((_, index)) => match state.gpus.get_mut(index) {
+            Some(gpu) => f(gpu),
+            None => NVAPI_EXPECTED_PHYSICAL_GPU_HANDLE,
+        },
+        None => NVAPI_EXPECTED_PHYSICAL_GPU_HANDLE,
+    })
+}
+
+fn short_string(value: &str) -> NvAPI_ShortString {
+    let mut out = types::short_string();
+    for (out, &b) in out.iter_mut().zip(value.as_bytes().iter().take(types::NVAPI_SHORT_STRING_MAX - 1)) {
+        *out = b as c_char;
+    }
+    out
+}
+
+unsafe fn put_string(out: *mut NvAPI_ShortString, value: Option<&str>) -> NvAPI_Status {
+    match (out.as_mut(), value) {
+        (None, _) => NVAPI_INVALID_ARGUMENT,
+        (_, None) => NVAPI_NOT_SUPPORTED,
+        (Some(out), Some(value)) => {
+            *out = short_string(value);
+            NVAPI_OK
+        },
+    }
+}
+
+unsafe fn put_value<T: Copy>(out: *mut T, value: Option<T>) -> NvAPI_Status {
+    match (out.as_mut(), value) {
+        (None, _) => NVAPI_INVALID_ARGUMENT,
+        (_, None) => NVAPI_NOT_SUPPORTED,
+        (Some(out), Some(value)) => {
+            *out = value;
+            NVAPI_OK
+        },
+    }
+}
+
+/// Checks the version header of a versioned struct argument.
+unsafe fn struct_version<T>(data: *const T, versions: &[u32]) -> Result<u32, NvAPI_Status> {
+    if data.is_null() {
+        return Err(NVAPI_INVALID_ARGUMENT)
+    }
+
+    let version = ptr::read_unaligned(data as *const u32);
+    if versions.contains(&version) && types::GET_NVAPI_SIZE(version) == mem::size_of::<T>() {
+        Ok(version)
+    } else {
+        Err(NVAPI_INCOMPATIBLE_STRUCT_VERSION)
+    }
+}
+
+unsafe fn put_versioned<T: Copy>(out: *mut T, versions: &[u32], value: Option<&T>) -> NvAPI_Status {
+    match struct_version(out, versions) {
+        Ok(version) => match value {
+            Some(value) => {
+                *out = *value;
+                ptr::write_unaligned(out as *mut u32, version);
+                NVAPI_OK
+            },
+            None => NVAPI_NOT_SUPPORTED,
+        },
+        Err(status) => status,
+    }
+}
+
+unsafe fn get_versioned<'a, T>(data: *const T, versions: &[u32]) -> Result<&'a T, NvAPI_Status> {
+    struct_version(data, versions).map(|_| &*data)
+}
+
+fn status(result: Result<(), NvAPI_Status>) -> NvAPI_Status {
+    match result {
+        Ok(()) => NVAPI_OK,
+        Err(status) => status,
+    }
+}
+
+fn check_range<T: PartialOrd>(value: T, min: T, max: T) -> Result<(), NvAPI_Status> {
+    if value >= min && value <= max {
+        Ok(())
+    } else {
+        Err(NVAPI_INVALID_ARGUMENT)
+    }
+}
+
+extern "C" fn query_interface(id: u32) -> *const c_void {
+    match Api::from_id(id) {
+        Ok(Api::NvAPI_Initialize) => Initialize as *const c_void,
+        Ok(Api::NvAPI_Unload) => Unload as *const c_void,
+        Ok(Api::NvAPI_GetErrorMessage) => GetErrorMessage as *const c_void,
+        Ok(Api::NvAPI_GetInterfaceVersionString) => GetInterfaceVersionString as *const c_void,
+        Ok(Api::NvAPI_SYS_GetDriverAndBranchVersion) => SYS_GetDriverAndBranchVersion as *const c_void,
+        Ok(Api::NvAPI_SYS_GetChipSetInfo) => SYS_GetChipSetInfo as *const c_void,
+        Ok(Api::NvAPI_SYS_GetLidAndDockInfo) => SYS_GetLidAndDockInfo as *const c_void,
+        Ok(Api::NvAPI_EnumPhysicalGPUs) => EnumPhysicalGPUs as *const c_void,
+        Ok(Api::NvAPI_EnumLogicalGPUs) => EnumLogicalGPUs as *const c_void,
+        Ok(Api::NvAPI_EnumNvidiaDisplayHandle) => EnumNvidiaDisplayHandle as *const c_void,
+        Ok(Api::NvAPI_EnumNvidiaUnAttachedDisplayHandle) => EnumNvidiaUnAttachedDisplayHandle as *const c_void,
+        Ok(Api::NvAPI_GetAssociatedNvidiaDisplayHandle) => GetAssociatedNvidiaDisplayHandle as *const c_void,
+        Ok(Api::NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle) => DISP_GetAssociatedUnAttachedNvidiaDisplayHandle as *const c_void,
+        Ok(Api::NvAPI_GetAssociatedNvidiaDisplayName) => GetAssociatedNvidiaDisplayName as *const c_void,
+        Ok(Api::NvAPI_GetUnAttachedAssociatedDisplayName) => GetUnAttachedAssociatedDisplayName as *const c_void,
+        Ok(Api::NvAPI_GetPhysicalGPUsFromDisplay) => GetPhysicalGPUsFromDisplay as *const c_void,
+        Ok(Api::NvAPI_GetPhysicalGPUFromUnAttachedDisplay) => GetPhysicalGPUFromUnAttachedDisplay as *const c_void,
+        Ok(Api::NvAPI_GetPhysicalGPUsFromLogicalGPU) => GetPhysicalGPUsFromLogicalGPU as *const c_void,
+        Ok(Api::NvAPI_GetLogicalGPUFromPhysicalGPU) => GetLogicalGPUFromPhysicalGPU as *const c_void,
+        Ok(Api::NvAPI_GPU_GetFullName) => GPU_GetFullName as *const c_void,
+        Ok(Api::NvAPI_GPU_GetShortName) => GPU_GetShortName as *const c_void,
+        Ok(Api::NvAPI_GPU_GetVbiosVersionString) => GPU_GetVbiosVersionString as *const c_void,
+        Ok(Api::NvAPI_GetDriverModel) => GetDriverModel as *const c_void,
+        Ok(Api::NvAPI_GetGPUIDFromPhysicalGPU) => GetGPUIDFromPhysicalGPU as *const c_void,
+        Ok(Api::NvAPI_GPU_GetPCIIdentifiers) => GPU_GetPCIIdentifiers as *const c_void,
+        Ok(Api::NvAPI_GPU_GetBoardInfo) => GPU_GetBoardInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_GetArchInfo) => GPU_GetArchInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_GetGPUInfo) => GPU_GetGPUInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_GetAdapterIdFromPhysicalGpu) => GPU_GetAdapterIdFromPhysicalGpu as *const c_void,
+        Ok(Api::NvAPI_GPU_GetLogicalGpuInfo) => GPU_GetLogicalGpuInfo as *const c_void,
+        Ok(Api::NvAPI_SYS_GetGpuAndOutputIdFromDisplayId) => SYS_GetGpuAndOutputIdFromDisplayId as *const c_void,
+        Ok(Api::NvAPI_I2CRead) => I2CRead as *const c_void,
+        Ok(Api::NvAPI_I2CWrite) => I2CWrite as *const c_void,
+        Ok(Api::NvAPI_GPU_GetSystemType) => GPU_GetSystemType as *const c_void,
+        Ok(Api::NvAPI_GPU_GetGPUType) => GPU_GetGPUType as *const c_void,
+        Ok(Api::NvAPI_GPU_GetQuadroStatus) => GPU_GetQuadroStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_GetGpuCoreCount) => GPU_GetGpuCoreCount as *const c_void,
+        Ok(Api::NvAPI_GPU_GetVbiosRevision) => GPU_GetVbiosRevision as *const c_void,
+        Ok(Api::NvAPI_GPU_GetVbiosOEMRevision) => GPU_GetVbiosOEMRevision as *const c_void,
+        Ok(Api::NvAPI_GPU_GetBusId) => GPU_GetBusId as *const c_void,
+        Ok(Api::NvAPI_GPU_GetBusSlotId) => GPU_GetBusSlotId as *const c_void,
+        Ok(Api::NvAPI_GPU_GetBusType) => GPU_GetBusType as *const c_void,
+        Ok(Api::NvAPI_GPU_GetIRQ) => GPU_GetIRQ as *const c_void,
+        Ok(Api::NvAPI_GPU_GetCurrentPCIEDownstreamWidth) => GPU_GetCurrentPCIEDownstreamWidth as *const c_void,
+        Ok(Api::NvAPI_GPU_GetShaderPipeCount) => GPU_GetShaderPipeCount as *const c_void,
+        Ok(Api::NvAPI_GPU_GetShaderSubPipeCount) => GPU_GetShaderSubPipeCount as *const c_void,
+        Ok(Api::NvAPI_GPU_GetRamType) => GPU_GetRamType as *const c_void,
+        Ok(Api::NvAPI_GPU_GetRamMaker) => GPU_GetRamMaker as *const c_void,
+        Ok(Api::NvAPI_GPU_GetRamBusWidth) => GPU_GetRamBusWidth as *const c_void,
+        Ok(Api::NvAPI_GPU_GetRamBankCount) => GPU_GetRamBankCount as *const c_void,
+        Ok(Api::NvAPI_GPU_GetPartitionCount) => GPU_GetPartitionCount as *const c_void,
+        Ok(Api::NvAPI_GPU_GetFoundry) => GPU_GetFoundry as *const c_void,
+        Ok(Api::NvAPI_GPU_GetMemoryInfo) => GPU_GetMemoryInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_GetAllClockFrequencies) => GPU_GetAllClockFrequencies as *const c_void,
+        Ok(Api::NvAPI_GPU_GetAllClocks) => GPU_GetAllClocks as *const c_void,
+        Ok(Api::NvAPI_GPU_GetCurrentPstate) => GPU_GetCurrentPstate as *const c_void,
+        Ok(Api::NvAPI_GPU_GetPstates20) => GPU_GetPstates20 as *const c_void,
+        Ok(Api::NvAPI_GPU_SetPstates20) => GPU_SetPstates20 as *const c_void,
+        Ok(Api::NvAPI_GPU_GetPstatesInfoEx) => GPU_GetPstatesInfoEx as *const c_void,
+        Ok(Api::NvAPI_GPU_GetPerfClocks) => GPU_GetPerfClocks as *const c_void,
+        Ok(Api::NvAPI_GPU_SetPerfClocks) => GPU_SetPerfClocks as *const c_void,
+        Ok(Api::NvAPI_GPU_GetDynamicPstatesInfoEx) => GPU_GetDynamicPstatesInfoEx as *const c_void,
+        Ok(Api::NvAPI_GPU_GetPstateClientLimits) => GPU_GetPstateClientLimits as *const c_void,
+        Ok(Api::NvAPI_GPU_SetPstateClientLimits) => GPU_SetPstateClientLimits as *const c_void,
+        Ok(Api::NvAPI_GPU_EnableDynamicPstates) => GPU_EnableDynamicPstates as *const c_void,
+        Ok(Api::NvAPI_GPU_EnableOverclockedPstates) => GPU_EnableOverclockedPstates as *const c_void,
+        Ok(Api::NvAPI_GPU_GetUsages) => GPU_GetUsages as *const c_void,
+        Ok(Api::NvAPI_GPU_GetTachReading) => GPU_GetTachReading as *const c_void,
+        Ok(Api::NvAPI_GPU_ClockClientClkVfPointsGetInfo) => GPU_ClockClientClkVfPointsGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_ClockClientClkVfPointsGetControl) => GPU_ClockClientClkVfPointsGetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_ClockClientClkVfPointsSetControl) => GPU_ClockClientClkVfPointsSetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_ClockClientClkVfPointsGetStatus) => GPU_ClockClientClkVfPointsGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_ClockClientClkDomainsGetInfo) => GPU_ClockClientClkDomainsGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_PerfClientLimitsGetStatus) => GPU_PerfClientLimitsGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_PerfClientLimitsSetStatus) => GPU_PerfClientLimitsSetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientVoltRailsGetStatus) => GPU_ClientVoltRailsGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientVoltRailsGetControl) => GPU_ClientVoltRailsGetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientVoltRailsSetControl) => GPU_ClientVoltRailsSetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_GetVoltageDomainsStatus) => GPU_GetVoltageDomainsStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_GetVoltageStep) => GPU_GetVoltageStep as *const c_void,
+        Ok(Api::NvAPI_GPU_GetVoltages) => GPU_GetVoltages as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientPowerTopologyGetStatus) => GPU_ClientPowerTopologyGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientPowerTopologyGetInfo) => GPU_ClientPowerTopologyGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientPowerPoliciesGetInfo) => GPU_ClientPowerPoliciesGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientPowerPoliciesGetStatus) => GPU_ClientPowerPoliciesGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientPowerPoliciesSetStatus) => GPU_ClientPowerPoliciesSetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_GetThermalSettings) => GPU_GetThermalSettings as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientThermalPoliciesGetInfo) => GPU_ClientThermalPoliciesGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientThermalPoliciesGetStatus) => GPU_ClientThermalPoliciesGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientThermalPoliciesSetStatus) => GPU_ClientThermalPoliciesSetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_GetCoolerSettings) => GPU_GetCoolerSettings as *const c_void,
+        Ok(Api::NvAPI_GPU_SetCoolerLevels) => GPU_SetCoolerLevels as *const c_void,
+        Ok(Api::NvAPI_GPU_RestoreCoolerSettings) => GPU_RestoreCoolerSettings as *const c_void,
+        Ok(Api::NvAPI_GPU_ThermalGetSensors) => GPU_ThermalGetSensors as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientFanCoolersGetInfo) => GPU_ClientFanCoolersGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientFanCoolersGetStatus) => GPU_ClientFanCoolersGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientFanCoolersGetControl) => GPU_ClientFanCoolersGetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientFanCoolersSetControl) => GPU_ClientFanCoolersSetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_QueryIlluminationSupport) => GPU_QueryIlluminationSupport as *const c_void,
+        Ok(Api::NvAPI_GPU_GetIllumination) => GPU_GetIllumination as *const c_void,
+        Ok(Api::NvAPI_GPU_SetIllumination) => GPU_SetIllumination as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientIllumZonesGetInfo) => GPU_ClientIllumZonesGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientIllumZonesGetControl) => GPU_ClientIllumZonesGetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_ClientIllumZonesSetControl) => GPU_ClientIllumZonesSetControl as *const c_void,
+        Ok(Api::NvAPI_GPU_GetECCStatusInfo) => GPU_GetECCStatusInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_GetECCErrorInfo) => GPU_GetECCErrorInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_ResetECCErrorInfo) => GPU_ResetECCErrorInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_GetECCConfigurationInfo) => GPU_GetECCConfigurationInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_SetECCConfiguration) => GPU_SetECCConfiguration as *const c_void,
+        Ok(Api::NvAPI_GPU_PerfPoliciesGetInfo) => GPU_PerfPoliciesGetInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_PerfPoliciesGetStatus) => GPU_PerfPoliciesGetStatus as *const c_void,
+        Ok(Api::NvAPI_GPU_GetPerfDecreaseInfo) => GPU_GetPerfDecreaseInfo as *const c_void,
+        Ok(Api::NvAPI_GPU_GetAllDisplayIds) => GPU_GetAllDisplayIds as *const c_void,
+        Ok(Api::NvAPI_GPU_GetConnectedDisplayIds) => GPU_GetConnectedDisplayIds as *const c_void,
+        Ok(Api::NvAPI_GPU_GetEDID) => GPU_GetEDID as *const c_void,
+        Ok(Api::NvAPI_GPU_SetEDID) => GPU_SetEDID as *const c_void,
+        _ => ptr::null(),
+    }
+}
+
+extern "C" fn Initialize() -> NvAPI_Status {
+    with_state(|_| NVAPI_OK)
+}
+
+extern "C" fn Unload() -> NvAPI_Status {
+    with_state(|_| NVAPI_OK)
+}
+
+extern "C" fn GetErrorMessage(nr: NvAPI_Status, szDesc: *mut NvAPI_ShortString) -> NvAPI_Status {
+    match Status::from_raw(nr) {
+        Ok(status) => unsafe { put_string(szDesc, Some(&format!("{:?}", status))) },
+        Err(..) => NVAPI_INVALID_ARGUMENT,
+    }
+}
+
+extern "C" fn GetInterfaceVersionString(szDesc: *mut NvAPI_ShortString) -> NvAPI_Status {
+    with_state(|state| unsafe { put_string(szDesc, Some(&state.mock.interface_version)) })
+}
+
+extern "C" fn SYS_GetDriverAndBranchVersion(pDriverVersion: *mut u32, szBuildBranchString: *mut NvAPI_ShortString) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        match put_value(pDriverVersion, Some(state.mock.driver_version)) {
+            NVAPI_OK => put_string(szBuildBranchString, Some(&state.mock.driver_branch)),
+            status => status,
+        }
+    })
+}
+
+extern "C" fn SYS_GetChipSetInfo(pChipSetInfo: *mut NV_CHIPSET_INFO) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        let info = state.mock.chipset.as_ref();
+        if pChipSetInfo.is_null() {
+            return NVAPI_INVALID_ARGUMENT
+        }
+
+        match ptr::read_unaligned(pChipSetInfo as *const u32) {
+            NV_CHIPSET_INFO_VER_1 =>
+                put_versioned(pChipSetInfo as *mut NV_CHIPSET_INFO_V1, &[NV_CHIPSET_INFO_VER_1], info.map(|info| &info.v3.v2.v1)),
+            NV_CHIPSET_INFO_VER_2 =>
+                put_versioned(pChipSetInfo as *mut NV_CHIPSET_INFO_V2, &[NV_CHIPSET_INFO_VER_2], info.map(|info| &info.v3.v2)),
+            NV_CHIPSET_INFO_VER_3 =>
+                put_versioned(pChipSetInfo as *mut NV_CHIPSET_INFO_V3, &[NV_CHIPSET_INFO_VER_3], info.map(|info| &info.v3)),
+            _ => put_versioned(pChipSetInfo, &[NV_CHIPSET_INFO_VER_4], info),
+        }
+    })
+}
+
+extern "C" fn SYS_GetLidAndDockInfo(pLidAndDock: *mut NV_LID_DOCK_PARAMS) -> NvAPI_Status {
+    with_state(|state| unsafe { put_versioned(pLidAndDock, &[NV_LID_DOCK_PARAMS_VER1], state.mock.lid_dock.as_ref()) })
+}
+
+extern "C" fn EnumLogicalGPUs(nvGPUHandle: *mut [NvLogicalGpuHandle; types::NVAPI_MAX_LOGICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        match (nvGPUHandle.as_mut(), pGpuCount.as_mut()) {
+            (Some(handles), Some(count)) => {
+                if state.gpus.is_empty() {
+                    return NVAPI_NVIDIA_DEVICE_NOT_FOUND
+                }
+                *count = 0;
+                for (index, handle) in handles.iter_mut().enumerate().take(state.logical_gpus().len()) {
+                    *handle = logical_gpu_handle(state.generation, index);
+                    *count += 1;
+                }
+                NVAPI_OK
+            },
+            _ => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GetPhysicalGPUsFromLogicalGPU(hLogicalGPU: NvLogicalGpuHandle, hPhysicalGPU: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        let logical = state.logical_gpus();
+        let gpus = match logical_gpu_index(hLogicalGPU) {
+            Some((generation, _)) if generation != state.generation => return NVAPI_HANDLE_INVALIDATED,
+            Some((_, index)) => match logical.get(index) {
+                Some(gpus) => gpus,
+                None => return NVAPI_EXPECTED_LOGICAL_GPU_HANDLE,
+            },
+            None => return NVAPI_EXPECTED_LOGICAL_GPU_HANDLE,
+        };
+        match (hPhysicalGPU.as_mut(), pGpuCount.as_mut()) {
+            (Some(handles), Some(count)) => {
+                *count = 0;
+                for (handle, &index) in handles.iter_mut().zip(gpus) {
+                    *handle = gpu_handle(state.generation, index);
+                    *count += 1;
+                }
+                NVAPI_OK
+            },
+            _ => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GetLogicalGPUFromPhysicalGPU(hPhysicalGPU: NvPhysicalGpuHandle, pLogicalGPU: *mut NvLogicalGpuHandle) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        let index = match gpu_index(hPhysicalGPU) {
+            Some((generation, _)) if generation != state.generation => return NVAPI_HANDLE_INVALIDATED,
+            Some((_, index)) if index < state.gpus.len() => index,
+            _ => return NVAPI_EXPECTED_PHYSICAL_GPU_HANDLE,
+        };
+        let logical = state.logical_gpus().iter().position(|gpus| gpus.contains(&index))
+            .map(|logical| logical_gpu_handle(state.generation, logical));
+        put_value(pLogicalGPU, logical)
+    })
+}
+
+extern "C" fn GPU_GetLogicalGpuInfo(hLogicalGpu: NvLogicalGpuHandle, pLogicalGpuData: *mut gpu::NV_LOGICAL_GPU_DATA) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        let logical = state.logical_gpus();
+        let gpus = match logical_gpu_index(hLogicalGpu) {
+            Some((generation, _)) if generation != state.generation => return NVAPI_HANDLE_INVALIDATED,
+            Some((_, index)) => match logical.get(index) {
+                Some(gpus) => gpus,
+                None => return NVAPI_EXPECTED_LOGICAL_GPU_HANDLE,
+            },
+            None => return NVAPI_EXPECTED_LOGICAL_GPU_HANDLE,
+        };
+        let data = match get_versioned(pLogicalGpuData, &[gpu::NV_LOGICAL_GPU_DATA_VER1]) {
+            Ok(data) => data,
+            Err(status) => return status,
+        };
+        let luid = match state.gpus[gpus[0]].current.adapter_luid {
+            Some(luid) => luid,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        let status = put_value(data.pOSAdapterId, Some(luid));
+        if status != NVAPI_OK {
+            return status
+        }
+        let mut output = *data;
+        output.physicalGpuCount = 0;
+        for (handle, &index) in output.physicalGpuHandles.iter_mut().zip(gpus) {
+            *handle = gpu_handle(state.generation, index);
+            output.physicalGpuCount += 1;
+        }
+        put_versioned(pLogicalGpuData, &[gpu::NV_LOGICAL_GPU_DATA_VER1], Some(&output))
+    })
+}
+
+/// The device registers addressed by an I2C transaction.
+unsafe fn i2c_registers(gpu: &mut GpuState, pI2cInfo: *mut i2c::NV_I2C_INFO) -> Result<&mut [u8], NvAPI_Status> {
+    if pI2cInfo.is_null() {
+        return Err(NVAPI_INVALID_ARGUMENT)
+    }
+    match ptr::read_unaligned(pI2cInfo as *const u32) {
+        i2c::NV_I2C_INFO_VER1 | i2c::NV_I2C_INFO_VER2 | i2c::NV_I2C_INFO_VER3 => (),
+        _ => return Err(NVAPI_INCOMPATIBLE_STRUCT_VERSION),
+    }
+    // the older layouts are prefixes of `NV_I2C_INFO_V3`
+    let info = &*(pI2cInfo as *const i2c::NV_I2C_INFO_V1);
+    let register = match (info.pbI2cRegAddress.is_null(), info.regAddrSize) {
+        (true, 0) => 0,
+        (false, 1) => *info.pbI2cRegAddress as usize,
+        _ => return Err(NVAPI_INVALID_ARGUMENT),
+    };
+    if info.pbData.is_null() {
+        return Err(NVAPI_INVALID_ARGUMENT)
+    }
+    // DDC transactions address a single display output
+    let mask = info.displayMask;
+    if info.bIsDDCPort != 0 && (mask.count_ones() != 1 || mask.trailing_zeros() as usize >= gpu.current.display_ids.len()) {
+        return Err(NVAPI_INVALID_ARGUMENT)
+    }
+    let device = match gpu.current.i2c_devices.iter_mut().find(|&&mut (address, _)| address == info.i2cDevAddress >> 1) {
+        Some((_, device)) => device,
+        None => return Err(NVAPI_DATA_NOT_FOUND),
+    };
+    device.get_mut(register..register + info.cbSize as usize).ok_or(NVAPI_INVALID_ARGUMENT)
+}
+
+extern "C" fn I2CRead(hPhysicalGpu: NvPhysicalGpuHandle, pI2cInfo: *mut i2c::NV_I2C_INFO) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let registers = match i2c_registers(gpu, pI2cInfo) {
+            Ok(registers) => registers,
+            Err(status) => return status,
+        };
+        ptr::copy_nonoverlapping(registers.as_ptr(), (*pI2cInfo).pbData, registers.len());
+        NVAPI_OK
+    })
+}
+
+extern "C" fn I2CWrite(hPhysicalGpu: NvPhysicalGpuHandle, pI2cInfo: *mut i2c::NV_I2C_INFO) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let registers = match i2c_registers(gpu, pI2cInfo) {
+            Ok(registers) => registers,
+            Err(status) => return status,
+        };
+        ptr::copy_nonoverlapping((*pI2cInfo).pbData, registers.as_mut_ptr(), registers.len());
+        NVAPI_OK
+    })
+}
+
+unsafe fn enum_display<H: Copy>(unattached: bool, index: u32, out: *mut H, handle: unsafe fn(*const c_void) -> H) -> NvAPI_Status {
+    with_state(|state| {
+        if index as usize >= state.displays(unattached).len() {
+            return NVAPI_END_ENUMERATION
+        }
+
+        put_value(out, Some(handle(handle_raw(display_handle_kind(unattached).0, state.generation, index as usize))))
+    })
+}
+
+unsafe fn display_from_name<H: Copy>(unattached: bool, name: *const c_char, out: *mut H, handle: unsafe fn(*const c_void) -> H) -> NvAPI_Status {
+    if name.is_null() {
+        return NVAPI_INVALID_ARGUMENT
+    }
+
+    let name = CStr::from_ptr(name).to_string_lossy();
+    with_state(|state| match state.displays(unattached).iter().position(|&(_, n)| n == name) {
+        Some(index) => put_value(out, Some(handle(handle_raw(display_handle_kind(unattached).0, state.generation, index)))),
+        None => NVAPI_NVIDIA_DEVICE_NOT_FOUND,
+    })
+}
+
+unsafe fn display_name(unattached: bool, handle: *const c_void, out: *mut NvAPI_ShortString) -> NvAPI_Status {
+    with_state(|state| match state.display(unattached, handle) {
+        Ok((_, name)) => put_string(out, Some(name)),
+        Err(status) => status,
+    })
+}
+
+extern "C" fn EnumNvidiaDisplayHandle(thisEnum: u32, pNvDispHandle: *mut NvDisplayHandle) -> NvAPI_Status {
+    unsafe { enum_display(false, thisEnum, pNvDispHandle, NvDisplayHandle::from_raw) }
+}
+
+extern "C" fn EnumNvidiaUnAttachedDisplayHandle(thisEnum: u32, pNvUnAttachedDispHandle: *mut NvUnAttachedDisplayHandle) -> NvAPI_Status {
+    unsafe { enum_display(true, thisEnum, pNvUnAttachedDispHandle, NvUnAttachedDisplayHandle::from_raw) }
+}
+
+extern "C" fn GetAssociatedNvidiaDisplayHandle(szDisplayName: *const c_char, pNvDispHandle: *mut NvDisplayHandle) -> NvAPI_Status {
+    unsafe { display_from_name(false, szDisplayName, pNvDispHandle, NvDisplayHandle::from_raw) }
+}
+
+extern "C" fn DISP_GetAssociatedUnAttachedNvidiaDisplayHandle(szDisplayName: *const c_char, pNvUnAttachedDispHandle: *mut NvUnAttachedDisplayHandle) -> NvAPI_Status {
+    unsafe { display_from_name(true, szDisplayName, pNvUnAttachedDispHandle, NvUnAttachedDisplayHandle::from_raw) }
+}
+
+extern "C" fn GetAssociatedNvidiaDisplayName(NvDispHandle: NvDisplayHandle, szDisplayName: *mut NvAPI_ShortString) -> NvAPI_Status {
+    unsafe { display_name(false, NvDispHandle.as_raw(), szDisplayName) }
+}
+
+extern "C" fn GetUnAttachedAssociatedDisplayName(hNvUnAttachedDisp: NvUnAttachedDisplayHandle, szDisplayName: *mut NvAPI_ShortString) -> NvAPI_Status {
+    unsafe { display_name(true, hNvUnAttachedDisp.as_raw(), szDisplayName) }
+}
+
+extern "C" fn GetPhysicalGPUsFromDisplay(hNvDisp: NvDisplayHandle, nvGPUHandle: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        match (state.display(false, hNvDisp.as_raw()), nvGPUHandle.as_mut(), pGpuCount.as_mut()) {
+            (Err(status), ..) => status,
+            (Ok((index, _)), Some(handles), Some(count)) => {
+                handles[0] = gpu_handle(state.generation, index);
+                *count = 1;
+                NVAPI_OK
+            },
+            _ => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GetPhysicalGPUFromUnAttachedDisplay(hNvUnAttachedDisp: NvUnAttachedDisplayHandle, pPhysicalGpu: *mut NvPhysicalGpuHandle) -> NvAPI_Status {
+    with_state(|state| match state.display(true, hNvUnAttachedDisp.as_raw()) {
+        Ok((index, _)) => unsafe { put_value(pPhysicalGpu, Some(gpu_handle(state.generation, index))) },
+        Err(status) => status,
+    })
+}
+
+extern "C" fn EnumPhysicalGPUs(nvGPUHandle: *mut [NvPhysicalGpuHandle; types::NVAPI_MAX_PHYSICAL_GPUS], pGpuCount: *mut u32) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        match (nvGPUHandle.as_mut(), pGpuCount.as_mut()) {
+            (Some(handles), Some(count)) => {
+                if state.gpus.is_empty() {
+                    return NVAPI_NVIDIA_DEVICE_NOT_FOUND
+                }
+                *count = 0;
+                for (index, handle) in handles.iter_mut().enumerate().take(state.gpus.len()) {
+                    *handle = gpu_handle(state.generation, index);
+                    *count += 1;
+                }
+                NVAPI_OK
+            },
+            _ => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+macro_rules! mock_string {
+    ($($fn:ident => $field:ident,)*) => {
+        $(
+            extern "C" fn $fn(hPhysicalGpu: NvPhysicalGpuHandle, szName: *mut NvAPI_ShortString) -> NvAPI_Status {
+                with_gpu(hPhysicalGpu, |gpu| unsafe { put_string(szName, gpu.current.$field.as_ref().map(|s| &s[..])) })
+            }
+        )*
+    };
+}
+
+mock_string! {
+    GPU_GetFullName => full_name,
+    GPU_GetShortName => short_name,
+    GPU_GetVbiosVersionString => vbios_version,
+}
+
+macro_rules! mock_value {
+    ($($fn:ident($ty:ty) => $field:ident,)*) => {
+        $(
+            extern "C" fn $fn(hPhysicalGpu: NvPhysicalGpuHandle, pValue: *mut $ty) -> NvAPI_Status {
+                with_gpu(hPhysicalGpu, |gpu| unsafe { put_value(pValue, gpu.current.$field) })
+            }
+        )*
+    };
+}
+
+mock_value! {
+    GetDriverModel(u32) => driver_model,
+    GetGPUIDFromPhysicalGPU(u32) => gpu_id,
+    GPU_GetAdapterIdFromPhysicalGpu(types::LUID) => adapter_luid,
+    GPU_GetSystemType(gpu::NV_SYSTEM_TYPE) => system_type,
+    GPU_GetGPUType(gpu::NV_GPU_TYPE) => gpu_type,
+    GPU_GetQuadroStatus(u32) => quadro_status,
+    GPU_GetGpuCoreCount(u32) => core_count,
+    GPU_GetVbiosRevision(u32) => vbios_revision,
+    GPU_GetVbiosOEMRevision(u32) => vbios_oem_revision,
+    GPU_GetBusId(u32) => bus_id,
+    GPU_GetBusSlotId(u32) => bus_slot_id,
+    GPU_GetBusType(gpu::NV_GPU_BUS_TYPE) => bus_type,
+    GPU_GetIRQ(u32) => irq,
+    GPU_GetCurrentPCIEDownstreamWidth(u32) => pcie_downstream_width,
+    GPU_GetShaderPipeCount(u32) => shader_pipe_count,
+    GPU_GetShaderSubPipeCount(u32) => shader_sub_pipe_count,
+    GPU_GetRamType(NV_GPU_RAM_TYPE) => ram_type,
+    GPU_GetRamMaker(NV_GPU_RAM_MAKER) => ram_maker,
+    GPU_GetRamBusWidth(u32) => ram_bus_width,
+    GPU_GetRamBankCount(u32) => ram_bank_count,
+    GPU_GetPartitionCount(u32) => ram_partition_count,
+    GPU_GetFoundry(NV_GPU_FOUNDRY) => foundry,
+    GPU_GetCurrentPstate(NV_GPU_PERF_PSTATE_ID) => current_pstate,
+    GPU_GetTachReading(u32) => tachometer,
+    GPU_GetPerfDecreaseInfo(gpu::NVAPI_GPU_PERF_DECREASE) => perf_decrease,
+}
+
+macro_rules! mock_versioned {
+    ($($fn:ident($ty:ty: $($ver:expr),*) => $field:ident,)*) => {
+        $(
+            extern "C" fn $fn(hPhysicalGpu: NvPhysicalGpuHandle, pData: *mut $ty) -> NvAPI_Status {
+                with_gpu(hPhysicalGpu, |gpu| unsafe { put_versioned(pData, &[$($ver),*], gpu.current.$field.as_ref().map(Borrow::borrow)) })
+            }
+        )*
+    };
+}
+
+mock_versioned! {
+    GPU_GetBoardInfo(NV_BOARD_INFO: gpu::NV_BOARD_INFO_VER1) => board_info,
+    GPU_GetArchInfo(gpu::NV_GPU_ARCH_INFO: gpu::NV_GPU_ARCH_INFO_VER_1, gpu::NV_GPU_ARCH_INFO_VER_2) => arch_info,
+    GPU_GetAllClocks(NV_CLOCKS_INFO: NV_CLOCKS_INFO_VER_1) => all_clocks,
+    GPU_ClientFanCoolersGetInfo(NV_GPU_CLIENT_FAN_COOLERS_INFO: NV_GPU_CLIENT_FAN_COOLERS_INFO_VER_1) => fan_coolers_info,
+    GPU_ClientFanCoolersGetStatus(NV_GPU_CLIENT_FAN_COOLERS_STATUS: NV_GPU_CLIENT_FAN_COOLERS_STATUS_VER_1) => fan_coolers_status,
+    GPU_ClientFanCoolersGetControl(NV_GPU_CLIENT_FAN_COOLERS_CONTROL: NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER_1) => fan_coolers_control,
+    GPU_ClientIllumZonesGetInfo(NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS: NV_GPU_CLIENT_ILLUM_ZONE_INFO_PARAMS_VER_1) => illum_zones_info,
+    GPU_GetECCStatusInfo(NV_GPU_ECC_STATUS_INFO: NV_GPU_ECC_STATUS_INFO_VER_1) => ecc_status,
+    GPU_GetECCErrorInfo(NV_GPU_ECC_ERROR_INFO: NV_GPU_ECC_ERROR_INFO_VER_1) => ecc_errors,
+    GPU_GetECCConfigurationInfo(NV_GPU_ECC_CONFIGURATION_INFO: NV_GPU_ECC_CONFIGURATION_INFO_VER_1) => ecc_configuration,
+    GPU_GetPstates20(NV_GPU_PERF_PSTATES20_INFO: NV_GPU_PERF_PSTATES20_INFO_VER2, NV_GPU_PERF_PSTATES20_INFO_VER3) => pstates,
+    GPU_GetDynamicPstatesInfoEx(NV_GPU_DYNAMIC_PSTATES_INFO_EX: pstate::NV_GPU_DYNAMIC_PSTATES_INFO_EX_VER) => dynamic_pstates,
+    GPU_GetUsages(NV_USAGES_INFO: NV_USAGES_INFO_VER_1) => usages,
+    GPU_ClockClientClkVfPointsGetInfo(NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO: NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_INFO_VER_1) => vfp_info,
+    GPU_ClockClientClkVfPointsGetControl(NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL: NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL_VER_1) => vfp_control,
+    GPU_ClockClientClkDomainsGetInfo(NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO: NV_GPU_CLOCK_CLIENT_CLK_DOMAINS_INFO_VER_1) => vfp_ranges,
+    GPU_PerfClientLimitsGetStatus(NV_GPU_PERF_CLIENT_LIMITS: NV_GPU_PERF_CLIENT_LIMITS_VER_2) => vfp_locks,
+    GPU_ClientVoltRailsGetStatus(NV_GPU_CLIENT_VOLT_RAILS_STATUS: NV_GPU_CLIENT_VOLT_RAILS_STATUS_VER_1) => volt_rails_status,
+    GPU_ClientVoltRailsGetControl(NV_GPU_CLIENT_VOLT_RAILS_CONTROL: NV_GPU_CLIENT_VOLT_RAILS_CONTROL_VER_1) => volt_rails_control,
+    GPU_GetVoltageDomainsStatus(NV_VOLT_STATUS: NV_VOLT_STATUS_VER_1) => voltage_domains_status,
+    GPU_GetVoltageStep(NV_VOLT_STATUS: NV_VOLT_STATUS_VER_1) => voltage_step,
+    GPU_GetVoltages(NV_VOLT_TABLE: NV_VOLT_TABLE_VER_1) => voltage_table,
+    GPU_ClientPowerTopologyGetStatus(NV_GPU_POWER_TOPO: NV_GPU_POWER_TOPO_VER_1) => power_topology,
+    GPU_ClientPowerTopologyGetInfo(NV_GPU_POWER_TOPO_INFO: NV_GPU_POWER_TOPO_INFO_VER_1) => power_topology_info,
+    GPU_ClientPowerPoliciesGetInfo(NV_GPU_POWER_INFO: NV_GPU_POWER_INFO_VER_1) => power_info,
+    GPU_ClientPowerPoliciesGetStatus(NV_GPU_POWER_STATUS: NV_GPU_POWER_STATUS_VER_1) => power_status,
+    GPU_ClientThermalPoliciesGetInfo(NV_GPU_THERMAL_INFO: NV_GPU_THERMAL_INFO_VER_2) => thermal_info,
+    GPU_ClientThermalPoliciesGetStatus(NV_GPU_CLIENT_THERMAL_POLICIES_STATUS: NV_GPU_CLIENT_THERMAL_POLICIES_STATUS_VER_2) => thermal_status,
+    GPU_PerfPoliciesGetInfo(NV_GPU_PERF_INFO: NV_GPU_PERF_INFO_VER_1) => perf_info,
+    GPU_PerfPoliciesGetStatus(NV_GPU_PERF_STATUS: NV_GPU_PERF_STATUS_VER_1) => perf_status,
+}
+
+extern "C" fn GPU_GetPCIIdentifiers(hPhysicalGpu: NvPhysicalGpuHandle, pDeviceId: *mut u32, pSubSystemId: *mut u32, pRevisionId: *mut u32, pExtDeviceId: *mut u32) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        match (pDeviceId.as_mut(), pSubSystemId.as_mut(), pRevisionId.as_mut(), pExtDeviceId.as_mut(), gpu.current.pci_identifiers) {
+            (Some(device), Some(subsystem), Some(revision), Some(ext), Some(pci)) => {
+                *device = pci.0;
+                *subsystem = pci.1;
+                *revision = pci.2;
+                *ext = pci.3;
+                NVAPI_OK
+            },
+            (_, _, _, _, None) => NVAPI_NOT_SUPPORTED,
+            _ => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GPU_GetAllClockFrequencies(hPhysicalGpu: NvPhysicalGpuHandle, pClkFreqs: *mut NV_GPU_CLOCK_FREQUENCIES) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let versions = [clock::NV_GPU_CLOCK_FREQUENCIES_VER_1, clock::NV_GPU_CLOCK_FREQUENCIES_VER_2, clock::NV_GPU_CLOCK_FREQUENCIES_VER_3];
+        let clock_type = match get_versioned(pClkFreqs, &versions) {
+            Ok(clocks) => clocks.ClockType(),
+            Err(status) => return status,
+        };
+        let value = match gpu.current.clock_frequencies.get(clock_type as usize) {
+            Some(value) => value.as_ref(),
+            None => return NVAPI_INVALID_ARGUMENT,
+        };
+        match put_versioned(pClkFreqs, &versions, value) {
+            NVAPI_OK => {
+                (*pClkFreqs).set_ClockType(clock_type);
+                NVAPI_OK
+            },
+            status => status,
+        }
+    })
+}
+
+extern "C" fn GPU_GetMemoryInfo(hPhysicalGpu: NvPhysicalGpuHandle, pMemoryInfo: *mut NV_DISPLAY_DRIVER_MEMORY_INFO) -> NvAPI_Status {
+    use crate::driverapi::{NV_DISPLAY_DRIVER_MEMORY_INFO_V1, NV_DISPLAY_DRIVER_MEMORY_INFO_V2};
+
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let info = gpu.current.memory_info.as_ref();
+        if pMemoryInfo.is_null() {
+            return NVAPI_INVALID_ARGUMENT
+        }
+
+        match (ptr::read_unaligned(pMemoryInfo as *const u32), gpu.current.memory_info_version) {
+            (driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1, 1..) =>
+                put_versioned(pMemoryInfo as *mut NV_DISPLAY_DRIVER_MEMORY_INFO_V1, &[driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_1], info.map(|info| &info.v2.v1)),
+            (driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_2, 2..) =>
+                put_versioned(pMemoryInfo as *mut NV_DISPLAY_DRIVER_MEMORY_INFO_V2, &[driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_2], info.map(|info| &info.v2)),
+            (driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_3, 3..) =>
+                put_versioned(pMemoryInfo, &[driverapi::NV_DISPLAY_DRIVER_MEMORY_INFO_VER_3], info),
+            _ => NVAPI_INCOMPATIBLE_STRUCT_VERSION,
+        }
+    })
+}
+
+extern "C" fn GPU_GetGPUInfo(hPhysicalGpu: NvPhysicalGpuHandle, pGpuInfo: *mut gpu::NV_GPU_INFO) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let info = gpu.current.gpu_info.as_ref();
+        if pGpuInfo.is_null() {
+            return NVAPI_INVALID_ARGUMENT
+        }
+
+        match (ptr::read_unaligned(pGpuInfo as *const u32), gpu.current.gpu_info_version) {
+            (gpu::NV_GPU_INFO_VER_1, 1..) =>
+                put_versioned(pGpuInfo as *mut gpu::NV_GPU_INFO_V1, &[gpu::NV_GPU_INFO_VER_1], info.map(|info| &info.v1)),
+            (gpu::NV_GPU_INFO_VER_2, 2..) =>
+                put_versioned(pGpuInfo, &[gpu::NV_GPU_INFO_VER_2], info),
+            _ => NVAPI_INCOMPATIBLE_STRUCT_VERSION,
+        }
+    })
+}
+
+extern "C" fn GPU_SetPstates20(hPhysicalGpu: NvPhysicalGpuHandle, pPstatesInfo: *const NV_GPU_PERF_PSTATES20_INFO) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pPstatesInfo, &[NV_GPU_PERF_PSTATES20_INFO_VER2, NV_GPU_PERF_PSTATES20_INFO_VER3]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let mut info = match gpu.current.pstates {
+            Some(info) => info,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        let result = set_pstates20(&mut info, input);
+        if result.is_ok() {
+            gpu.current.pstates = Some(info);
+        }
+        status(result)
+    })
+}
+
+extern "C" fn GPU_GetPstatesInfoEx(hPhysicalGpu: NvPhysicalGpuHandle, pPerfPstatesInfo: *mut NV_GPU_PERF_PSTATES_INFO, inputFlags: u32) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let state = match inputFlags & NV_GPU_PERF_PSTATES_INFO_INPUT_FLAG_DEFAULT {
+            0 => &gpu.current,
+            _ => &gpu.defaults,
+        };
+        put_versioned(pPerfPstatesInfo, &[NV_GPU_PERF_PSTATES_INFO_VER2, NV_GPU_PERF_PSTATES_INFO_VER3], state.pstates_legacy.as_ref())
+    })
+}
+
+/// Perf clocks are a view of the legacy pstates table.
+extern "C" fn GPU_GetPerfClocks(hPhysicalGpu: NvPhysicalGpuHandle, pPerfClocks: *mut NV_GPU_PERF_CLOCKS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let clocks = gpu.current.pstates_legacy.as_ref().map(|info| {
+            let mut clocks = NV_GPU_PERF_CLOCKS::zeroed();
+            clocks.numLevels = info.numPstates;
+            clocks.numDomains = info.numClocks;
+            for (level, pstate) in clocks.levels.iter_mut().zip(&info.pstates) {
+                level.pstateId = pstate.pstateId;
+                level.flags = pstate.flags;
+                for (domain, clock) in level.domains.iter_mut().zip(&pstate.clocks) {
+                    domain.domainId = clock.domainId;
+                    domain.flags = clock.flags;
+                    domain.freq_kHz = clock.freq;
+                }
+            }
+            clocks
+        });
+        put_versioned(pPerfClocks, &[NV_GPU_PERF_CLOCKS_VER1], clocks.as_ref())
+    })
+}
+
+extern "C" fn GPU_SetPerfClocks(hPhysicalGpu: NvPhysicalGpuHandle, pPerfClocks: *mut NV_GPU_PERF_CLOCKS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pPerfClocks, &[NV_GPU_PERF_CLOCKS_VER1]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let mut info = match gpu.current.pstates_legacy {
+            Some(info) => info,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        if input.numLevels > info.numPstates || input.numDomains > info.numClocks {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        for level in input.levels.iter().take(input.numLevels as usize) {
+            let pstate = match info.pstates.iter_mut().take(info.numPstates as usize).find(|p| p.pstateId == level.pstateId) {
+                Some(pstate) => pstate,
+                None => return NVAPI_INVALID_ARGUMENT,
+            };
+            let overclockable = pstate.flag_overclockable();
+            for domain in level.domains.iter().take(input.numDomains as usize) {
+                let clock = match pstate.clocks.iter_mut().take(info.numClocks as usize).find(|c| c.domainId == domain.domainId) {
+                    Some(clock) => clock,
+                    None => return NVAPI_INVALID_ARGUMENT,
+                };
+                if clock.freq != domain.freq_kHz {
+                    if !overclockable || !clock.flag_overclockable() {
+                        return NVAPI_INVALID_ARGUMENT
+                    }
+                    clock.freq = domain.freq_kHz;
+                }
+            }
+        }
+        gpu.current.pstates_legacy = Some(info);
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_GetPstateClientLimits(hPhysicalGpu: NvPhysicalGpuHandle, limitId: NV_GPU_PERF_PSTATE_CLIENT_LIMIT_ID, pPstate: *mut NV_GPU_PERF_PSTATE_ID) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let limits = gpu.current.pstate_client_limits;
+        match limitId {
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_HARD => put_value(pPstate, limits.map(|(hard, _)| hard)),
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_SOFT => put_value(pPstate, limits.map(|(_, soft)| soft)),
+            _ => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GPU_SetPstateClientLimits(hPhysicalGpu: NvPhysicalGpuHandle, limitId: NV_GPU_PERF_PSTATE_CLIENT_LIMIT_ID, pstate: NV_GPU_PERF_PSTATE_ID) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| {
+        let limits = match gpu.current.pstate_client_limits.as_mut() {
+            Some(limits) => limits,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        if !(NVAPI_GPU_PERF_PSTATE_P0..=NVAPI_GPU_PERF_PSTATE_P15).contains(&pstate) {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        match limitId {
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_HARD => limits.0 = pstate,
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_SOFT => limits.1 = pstate,
+            NVAPI_PERF_PSTATE_CLIENT_LIMIT_BOTH => *limits = (pstate, pstate),
+            _ => return NVAPI_INVALID_ARGUMENT,
+        }
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_EnableDynamicPstates(hPhysicalGpu: NvPhysicalGpuHandle, bEnable: u32) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| match gpu.current.dynamic_pstates.as_mut() {
+        Some(info) => {
+            info.flags = (info.flags & !1) | (bEnable != 0) as u32;
+            NVAPI_OK
+        },
+        None => NVAPI_NOT_SUPPORTED,
+    })
+}
+
+extern "C" fn GPU_EnableOverclockedPstates(hPhysicalGpu: NvPhysicalGpuHandle, bEnable: u32) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| match gpu.current.overclocked_pstates.as_mut() {
+        Some(enabled) => {
+            *enabled = bEnable != 0;
+            NVAPI_OK
+        },
+        None => NVAPI_NOT_SUPPORTED,
+    })
+}
+
+fn set_pstates20(info: &mut NV_GPU_PERF_PSTATES20_INFO, input: &NV_GPU_PERF_PSTATES20_INFO) -> Result<(), NvAPI_Status> {
+    fn set_delta(delta: &mut NV_GPU_PERF_PSTATES20_PARAM_DELTA, editable: BoolU32, value: i32) -> Result<(), NvAPI_Status> {
+        if !editable.get() {
+            return Err(NVAPI_INVALID_ARGUMENT)
+        }
+        check_range(value, delta.min, delta.max)?;
+        delta.value = value;
+        Ok(())
+    }
+
+    if !info.bIsEditable.get() {
+        return Err(NVAPI_NOT_SUPPORTED)
+    }
+
+    let counts = (input.numPstates as usize, input.numClocks as usize, input.numBaseVoltages as usize, input.numVoltages as usize);
+    if counts.0 > input.pstates.len() || counts.1 > NVAPI_MAX_GPU_PSTATE20_CLOCKS || counts.2 > NVAPI_MAX_GPU_PSTATE20_BASE_VOLTAGES || counts.3 > input.voltages.len() {
+        return Err(NVAPI_INVALID_ARGUMENT)
+    }
+
+    let (num_pstates, num_clocks, num_base_voltages) = (info.numPstates as usize, info.numClocks as usize, info.numBaseVoltages as usize);
+    for pstate in &input.pstates[..counts.0] {
+        let target = info.pstates[..num_pstates].iter_mut()
+            .find(|p| p.pstateId == pstate.pstateId)
+            .ok_or(NVAPI_INVALID_ARGUMENT)?;
+        if !target.bIsEditable.get() {
+            return Err(NVAPI_INVALID_ARGUMENT)
+        }
+
+        for clock in &pstate.clocks[..counts.1] {
+            let entry = target.clocks[..num_clocks].iter_mut()
+                .find(|c| c.domainId == clock.domainId)
+                .ok_or(NVAPI_INVALID_ARGUMENT)?;
+            set_delta(&mut entry.freqDelta_kHz, entry.bIsEditable, clock.freqDelta_kHz.value)?;
+        }
+
+        for voltage in &pstate.baseVoltages[..counts.2] {
+            let entry = target.baseVoltages[..num_base_voltages].iter_mut()
+                .find(|v| v.domainId == voltage.domainId)
+                .ok_or(NVAPI_INVALID_ARGUMENT)?;
+            set_delta(&mut entry.voltDelta_uV, entry.bIsEditable, voltage.voltDelta_uV.value)?;
+        }
+    }
+
+    let num_voltages = info.numVoltages as usize;
+    for voltage in &input.voltages[..counts.3] {
+        let entry = info.voltages[..num_voltages].iter_mut()
+            .find(|v| v.domainId == voltage.domainId)
+            .ok_or(NVAPI_INVALID_ARGUMENT)?;
+        set_delta(&mut entry.voltDelta_uV, entry.bIsEditable, voltage.voltDelta_uV.value)?;
+    }
+
+    Ok(())
+}
+
+extern "C" fn GPU_ClockClientClkVfPointsGetStatus(hPhysicalGpu: NvPhysicalGpuHandle, pVfpCurve: *mut NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let mut curve = match gpu.current.vfp_status {
+            Some(curve) => curve,
+            None => return put_versioned(pVfpCurve, &[NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS_VER_1], None),
+        };
+        if let Some(control) = gpu.current.vfp_control.as_ref() {
+            for (i, entry) in curve.gpuEntries.iter_mut().enumerate() {
+                entry.freq_kHz = (entry.freq_kHz as i32 + control.gpuDeltas[i].freqDeltaKHz) as u32;
+            }
+            for (i, entry) in curve.memEntries.iter_mut().enumerate() {
+                entry.freq_kHz = (entry.freq_kHz as i32 + control.memDeltas[i]) as u32;
+            }
+        }
+        put_versioned(pVfpCurve, &[NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_STATUS_VER_1], Some(&curve))
+    })
+}
+
+extern "C" fn GPU_ClockClientClkVfPointsSetControl(hPhysicalGpu: NvPhysicalGpuHandle, pClockTable: *const NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pClockTable, &[NV_GPU_CLOCK_CLIENT_CLK_VF_POINTS_CONTROL_VER_1]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let (control, ranges) = match (gpu.current.vfp_control.as_mut(), gpu.current.vfp_ranges.as_ref()) {
+            (Some(control), Some(ranges)) => (control, ranges),
+            _ => return NVAPI_NOT_SUPPORTED,
+        };
+        let range = |domain| ranges.entries.iter()
+            .take(ranges.numClocks as usize)
+            .find(|e| e.clockType == domain)
+            .map(|e| (e.rangeMin, e.rangeMax))
+            .unwrap_or((0, 0));
+        let (graphics, memory) = (range(NVAPI_GPU_PUBLIC_CLOCK_GRAPHICS), range(NVAPI_GPU_PUBLIC_CLOCK_MEMORY));
+
+        let mut updated = *control;
+        for i in 0..updated.gpuDeltas.len() {
+            if get_mask_bit(&input.mask, i) {
+                let delta = input.gpuDeltas[i].freqDeltaKHz;
+                if !get_mask_bit(&control.mask, i) || check_range(delta, graphics.0, graphics.1).is_err() {
+                    return NVAPI_INVALID_ARGUMENT
+                }
+                updated.gpuDeltas[i].freqDeltaKHz = delta;
+            }
+        }
+        for i in 0..updated.memDeltas.len() {
+            if input.memFilled[i] != 0 {
+                let delta = input.memDeltas[i];
+                if control.memFilled[i] == 0 || check_range(delta, memory.0, memory.1).is_err() {
+                    return NVAPI_INVALID_ARGUMENT
+                }
+                updated.memDeltas[i] = delta;
+            }
+        }
+        *control = updated;
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_PerfClientLimitsSetStatus(hPhysicalGpu: NvPhysicalGpuHandle, pClockLocks: *const NV_GPU_PERF_CLIENT_LIMITS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pClockLocks, &[NV_GPU_PERF_CLIENT_LIMITS_VER_2]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let mut locks = match gpu.current.vfp_locks {
+            Some(locks) => locks,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        let count = locks.count as usize;
+        for entry in input.entries.iter().take(input.count as usize) {
+            match locks.entries[..count].iter_mut().find(|e| e.id == entry.id) {
+                Some(lock) if entry.mode == NVAPI_GPU_CLOCK_LOCK_NONE || entry.mode == NVAPI_GPU_CLOCK_LOCK_MANUAL => {
+                    lock.mode = entry.mode;
+                    lock.voltage_uV = entry.voltage_uV;
+                },
+                _ => return NVAPI_INVALID_ARGUMENT,
+            }
+        }
+        gpu.current.vfp_locks = Some(locks);
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_ClientVoltRailsSetControl(hPhysicalGpu: NvPhysicalGpuHandle, pVoltboostPercent: *const NV_GPU_CLIENT_VOLT_RAILS_CONTROL) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pVoltboostPercent, &[NV_GPU_CLIENT_VOLT_RAILS_CONTROL_VER_1]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        match gpu.current.volt_rails_control.as_mut() {
+            Some(control) => status(check_range(input.percent, 0, 100).map(|_| control.percent = input.percent)),
+            None => NVAPI_NOT_SUPPORTED,
+        }
+    })
+}
+
+extern "C" fn GPU_ClientPowerPoliciesSetStatus(hPhysicalGpu: NvPhysicalGpuHandle, pPowerStatus: *const NV_GPU_POWER_STATUS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pPowerStatus, &[NV_GPU_POWER_STATUS_VER_1]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let (info, power) = match (gpu.current.power_info.as_ref(), gpu.current.power_status.as_mut()) {
+            (Some(info), Some(power)) => (info, power),
+            _ => return NVAPI_NOT_SUPPORTED,
+        };
+        if input.count > power.count {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        let mut updated = *power;
+        for (i, entry) in input.entries.iter().take(input.count as usize).enumerate() {
+            let limit = &info.entries[i];
+            if check_range(entry.power, limit.min_power, limit.max_power).is_err() {
+                return NVAPI_INVALID_ARGUMENT
+            }
+            updated.entries[i].power = entry.power;
+        }
+        *power = updated;
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_ClientThermalPoliciesSetStatus(hPhysicalGpu: NvPhysicalGpuHandle, pThermalLimit: *const NV_GPU_CLIENT_THERMAL_POLICIES_STATUS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pThermalLimit, &[NV_GPU_CLIENT_THERMAL_POLICIES_STATUS_VER_2]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let (info, thermal) = match (gpu.current.thermal_info.as_ref(), gpu.current.thermal_status.as_mut()) {
+            (Some(info), Some(thermal)) => (info, thermal),
+            _ => return NVAPI_NOT_SUPPORTED,
+        };
+        if input.flags > thermal.flags {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        let mut updated = *thermal;
+        for (i, entry) in input.entries.iter().take(input.flags as usize).enumerate() {
+            let limit = &info.entries[i];
+            if entry.controller != limit.controller || check_range(entry.value as i32, limit.minTemp, limit.maxTemp).is_err() {
+                return NVAPI_INVALID_ARGUMENT
+            }
+            updated.entries[i].value = entry.value;
+            updated.entries[i].flags = entry.flags;
+        }
+        *thermal = updated;
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_GetThermalSettings(hPhysicalGpu: NvPhysicalGpuHandle, sensorIndex: u32, pThermalSettings: *mut NV_GPU_THERMAL_SETTINGS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let mut settings = gpu.current.thermal_settings;
+        if let Some(settings) = settings.as_mut() {
+            if sensorIndex != thermal::NVAPI_THERMAL_TARGET_ALL as u32 {
+                if sensorIndex >= settings.count {
+                    return NVAPI_INVALID_ARGUMENT
+                }
+                settings.sensor[0] = settings.sensor[sensorIndex as usize];
+                settings.count = 1;
+            }
+        }
+        put_versioned(pThermalSettings, &[thermal::NV_GPU_THERMAL_SETTINGS_VER_1, thermal::NV_GPU_THERMAL_SETTINGS_VER_2], settings.as_ref())
+    })
+}
+
+extern "C" fn GPU_GetCoolerSettings(hPhysicalGpu: NvPhysicalGpuHandle, coolerIndex: u32, pCoolerInfo: *mut NV_GPU_COOLER_SETTINGS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let mut settings = gpu.current.cooler_settings;
+        if let Some(settings) = settings.as_mut() {
+            if coolerIndex != cooler::NVAPI_COOLER_TARGET_ALL as u32 {
+                if coolerIndex >= settings.count {
+                    return NVAPI_INVALID_ARGUMENT
+                }
+                settings.cooler[0] = settings.cooler[coolerIndex as usize];
+                settings.count = 1;
+            }
+        }
+        put_versioned(pCoolerInfo, &[NV_GPU_COOLER_SETTINGS_VER_1], settings.as_ref())
+    })
+}
+
+extern "C" fn GPU_SetCoolerLevels(hPhysicalGpu: NvPhysicalGpuHandle, coolerIndex: u32, pCoolerLevels: *const NV_GPU_SETCOOLER_LEVEL) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pCoolerLevels, &[NV_GPU_SETCOOLER_LEVEL_VER_1]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let settings = match gpu.current.cooler_settings.as_mut() {
+            Some(settings) => settings,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        let count = settings.count as usize;
+        let mut updated = *settings;
+        let targets: Vec<(usize, &NV_GPU_SETCOOLER_LEVEL_COOLER)> = if coolerIndex == cooler::NVAPI_COOLER_TARGET_ALL as u32 {
+            input.cooler.iter().take(count).enumerate().collect()
+        } else if (coolerIndex as usize) < count {
+            vec![(coolerIndex as usize, &input.cooler[0])]
+        } else {
+            return NVAPI_INVALID_ARGUMENT
+        };
+        for (index, level) in targets {
+            if level.currentPolicy == NVAPI_COOLER_POLICY_NONE {
+                continue
+            }
+            let cooler = &mut updated.cooler[index];
+            if CoolerPolicy::from_raw(level.currentPolicy).is_err() || check_range(level.currentLevel, cooler.currentMinLevel, cooler.currentMaxLevel).is_err() {
+                return NVAPI_INVALID_ARGUMENT
+            }
+            cooler.currentLevel = level.currentLevel;
+            cooler.currentPolicy = level.currentPolicy;
+        }
+        *settings = updated;
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_RestoreCoolerSettings(hPhysicalGpu: NvPhysicalGpuHandle, coolerIndex: *const u32, coolerCount: u32) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let (settings, defaults) = match (gpu.current.cooler_settings.as_mut(), gpu.defaults.cooler_settings.as_ref()) {
+            (Some(settings), Some(defaults)) => (settings, defaults),
+            _ => return NVAPI_NOT_SUPPORTED,
+        };
+        let indices: Vec<u32> = if coolerIndex.is_null() {
+            (0..settings.count).collect()
+        } else {
+            std::slice::from_raw_parts(coolerIndex, coolerCount as usize).to_vec()
+        };
+        if indices.iter().any(|&i| i >= settings.count) {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        for i in indices {
+            let (cooler, default) = (&mut settings.cooler[i as usize], &defaults.cooler[i as usize]);
+            cooler.currentMinLevel = default.currentMinLevel;
+            cooler.currentMaxLevel = default.currentMaxLevel;
+            cooler.currentLevel = default.currentLevel;
+            cooler.currentPolicy = default.currentPolicy;
+        }
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_ThermalGetSensors(hPhysicalGpu: NvPhysicalGpuHandle, pThermalSensors: *mut NV_GPU_THERMAL_SENSORS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let mask = match get_versioned(pThermalSensors, &[NV_GPU_THERMAL_SENSORS_VER_2]) {
+            Ok(input) => input.mask,
+            Err(status) => return status,
+        };
+        let sensors = match gpu.current.thermal_sensors {
+            Some(sensors) => sensors,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        if mask == 0 || mask & !sensors.mask != 0 {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        let mut output = NV_GPU_THERMAL_SENSORS::zeroed();
+        output.mask = mask;
+        for (i, temperature) in output.temperatures.iter_mut().enumerate() {
+            if mask & (1 << i) != 0 {
+                *temperature = sensors.temperatures[i];
+            }
+        }
+        put_versioned(pThermalSensors, &[NV_GPU_THERMAL_SENSORS_VER_2], Some(&output))
+    })
+}
+
+extern "C" fn GPU_ClientFanCoolersSetControl(hPhysicalGpu: NvPhysicalGpuHandle, pFanCoolersControl: *const NV_GPU_CLIENT_FAN_COOLERS_CONTROL) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pFanCoolersControl, &[NV_GPU_CLIENT_FAN_COOLERS_CONTROL_VER_1]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let (status, control) = match (gpu.current.fan_coolers_status.as_mut(), gpu.current.fan_coolers_control.as_mut()) {
+            (Some(status), Some(control)) => (status, control),
+            _ => return NVAPI_NOT_SUPPORTED,
+        };
+        let count = control.count as usize;
+        let (mut updated_status, mut updated_control) = (*status, *control);
+        for entry in input.coolers.iter().take(input.count as usize) {
+            let index = match control.coolers.iter().take(count).position(|c| c.coolerId == entry.coolerId) {
+                Some(index) => index,
+                None => return NVAPI_INVALID_ARGUMENT,
+            };
+            let cooler = &mut updated_status.coolers[index];
+            if FanCoolerControlMode::from_raw(entry.controlMode).is_err() || check_range(entry.level, cooler.currentMinLevel, cooler.currentMaxLevel).is_err() {
+                return NVAPI_INVALID_ARGUMENT
+            }
+            if entry.controlMode == NV_GPU_CLIENT_FAN_COOLERS_CONTROL_MODE_MANUAL {
+                cooler.currentLevel = entry.level;
+            }
+            updated_control.coolers[index].level = entry.level;
+            updated_control.coolers[index].controlMode = entry.controlMode;
+        }
+        *status = updated_status;
+        *control = updated_control;
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_QueryIlluminationSupport(pIlluminationSupportInfo: *mut NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM) -> NvAPI_Status {
+    let input = match unsafe { get_versioned(pIlluminationSupportInfo, &[NV_GPU_QUERY_ILLUMINATION_SUPPORT_PARM_VER_1]) } {
+        Ok(input) => *input,
+        Err(status) => return status,
+    };
+    with_gpu(input.hPhysicalGpu, |gpu| unsafe {
+        match gpu.current.illumination.get(input.Attribute as usize) {
+            Some(value) => {
+                (*pIlluminationSupportInfo).bSupported = BoolU32(value.is_some() as u32);
+                NVAPI_OK
+            },
+            None => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GPU_GetIllumination(pIlluminationInfo: *mut NV_GPU_GET_ILLUMINATION_PARM) -> NvAPI_Status {
+    let input = match unsafe { get_versioned(pIlluminationInfo, &[NV_GPU_GET_ILLUMINATION_PARM_VER_1]) } {
+        Ok(input) => *input,
+        Err(status) => return status,
+    };
+    with_gpu(input.hPhysicalGpu, |gpu| unsafe {
+        match gpu.current.illumination.get(input.Attribute as usize) {
+            Some(&Some(value)) => {
+                (*pIlluminationInfo).Value = value;
+                NVAPI_OK
+            },
+            Some(None) => NVAPI_NOT_SUPPORTED,
+            None => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GPU_SetIllumination(pIlluminationInfo: *const NV_GPU_SET_ILLUMINATION_PARM) -> NvAPI_Status {
+    let input = match unsafe { get_versioned(pIlluminationInfo, &[NV_GPU_SET_ILLUMINATION_PARM_VER_1]) } {
+        Ok(input) => *input,
+        Err(status) => return status,
+    };
+    with_gpu(input.hPhysicalGpu, |gpu| {
+        match gpu.current.illumination.get_mut(input.Attribute as usize) {
+            Some(Some(value)) => status(check_range(input.Value, 0, 100).map(|()| *value = input.Value)),
+            Some(None) => NVAPI_NOT_SUPPORTED,
+            None => NVAPI_INVALID_ARGUMENT,
+        }
+    })
+}
+
+extern "C" fn GPU_ClientIllumZonesGetControl(hPhysicalGpu: NvPhysicalGpuHandle, pIllumZonesControl: *mut NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let default = match get_versioned(pIllumZonesControl, &[NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER_1]) {
+            Ok(input) => input.bDefault,
+            Err(status) => return status,
+        };
+        let control = match default.get() {
+            true => gpu.defaults.illum_zones_control.as_ref(),
+            false => gpu.current.illum_zones_control.as_ref(),
+        };
+        let status = put_versioned(pIllumZonesControl, &[NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER_1], control);
+        if status == NVAPI_OK {
+            (*pIllumZonesControl).bDefault = default;
+        }
+        status
+    })
+}
+
+extern "C" fn GPU_ClientIllumZonesSetControl(hPhysicalGpu: NvPhysicalGpuHandle, pIllumZonesControl: *const NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let input = match get_versioned(pIllumZonesControl, &[NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_PARAMS_VER_1]) {
+            Ok(input) => input,
+            Err(status) => return status,
+        };
+        let control = match gpu.current.illum_zones_control.as_mut() {
+            Some(control) => control,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        if input.numIllumZonesControl != control.numIllumZonesControl {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        let zones = input.zones.iter().zip(&control.zones).take(control.numIllumZonesControl as usize);
+        for (zone, current) in zones {
+            let valid = match (IllumZoneType::from_raw(zone.type_), IllumControlMode::from_raw(zone.ctrlMode)) {
+                (Ok(kind), Ok(mode)) if zone.type_ == current.type_ => zone.data.get(kind, mode).map(illum_zone_control_valid).unwrap_or(false),
+                _ => false,
+            };
+            if !valid {
+                return NVAPI_INVALID_ARGUMENT
+            }
+        }
+        let count = control.numIllumZonesControl as usize;
+        control.zones[..count].copy_from_slice(&input.zones[..count]);
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_ResetECCErrorInfo(hPhysicalGpu: NvPhysicalGpuHandle, bResetCurrent: NvBool, bResetAggregate: NvBool) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| {
+        let errors = match gpu.current.ecc_errors.as_mut() {
+            Some(errors) => errors,
+            None => return NVAPI_NOT_SUPPORTED,
+        };
+        if bResetCurrent != 0 {
+            errors.current = NV_GPU_ECC_ERROR_INFO_COUNTERS::zeroed();
+        }
+        if bResetAggregate != 0 {
+            errors.aggregate = NV_GPU_ECC_ERROR_INFO_COUNTERS::zeroed();
+        }
+        NVAPI_OK
+    })
+}
+
+extern "C" fn GPU_SetECCConfiguration(hPhysicalGpu: NvPhysicalGpuHandle, bEnable: NvBool, bEnableImmediately: NvBool) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| {
+        let (status, config) = match (gpu.current.ecc_status.as_mut(), gpu.current.ecc_configuration.as_mut()) {
+            (Some(status), Some(config)) => (status, config),
+            _ => return NVAPI_NOT_SUPPORTED,
+        };
+        let immediate = bEnableImmediately != 0;
+        match status.configurationOptions {
+            NV_ECC_CONFIGURATION_IMMEDIATE => (),
+            NV_ECC_CONFIGURATION_DEFERRED if !immediate => (),
+            NV_ECC_CONFIGURATION_DEFERRED => return NVAPI_INVALID_ARGUMENT,
+            _ => return NVAPI_NOT_SUPPORTED,
+        }
+        let enable = bEnable != 0;
+        config.flags = config.flags & !1 | enable as u32;
+        if immediate {
+            status.isEnabled = BoolU32(enable as u32);
+        }
+        NVAPI_OK
+    })
+}
+
+fn illum_zone_control_valid(value: illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE) -> bool {
+    use crate::gpu::illum::NV_GPU_CLIENT_ILLUM_ZONE_CONTROL_DATA_VALUE as Value;
+
+    let (brightness, cycle) = match value {
+        Value::ManualRgb(data) => (vec![data.brightnessPct], None),
+        Value::ManualRgbw(data) => (vec![data.brightnessPct], None),
+        Value::ManualBrightness(data) => (vec![data.brightnessPct], None),
+        Value::PiecewiseLinearRgb(data) => (data.rgbParams.iter().map(|p| p.brightnessPct).collect(), Some(data.piecewiseLinearData.cycleType)),
+        Value::PiecewiseLinearRgbw(data) => (data.rgbwParams.iter().map(|p| p.brightnessPct).collect(), Some(data.piecewiseLinearData.cycleType)),
+        Value::PiecewiseLinearBrightness(data) => (data.brightnessParams.iter().map(|p| p.brightnessPct).collect(), Some(data.piecewiseLinearData.cycleType)),
+    };
+    brightness.iter().all(|&b| b <= 100) && cycle.map(|c| IllumCycleType::from_raw(c).is_ok()).unwrap_or(true)
+}
+
+unsafe fn put_display_ids(ids: &[NV_GPU_DISPLAYIDS], pDisplayIds: *mut NV_GPU_DISPLAYIDS, pDisplayIdCount: *mut u32) -> NvAPI_Status {
+    let count = match pDisplayIdCount.as_mut() {
+        Some(count) => count,
+        None => return NVAPI_INVALID_ARGUMENT,
+    };
+    if pDisplayIds.is_null() {
+        *count = ids.len() as u32;
+        return NVAPI_OK
+    }
+    if (*count as usize) < ids.len() {
+        *count = ids.len() as u32;
+        return NVAPI_INSUFFICIENT_BUFFER
+    }
+    let out = std::slice::from_raw_parts_mut(pDisplayIds, ids.len());
+    for (out, id) in out.iter_mut().zip(ids) {
+        let version = match struct_version(out, &[NV_GPU_DISPLAYIDS_VER1, NV_GPU_DISPLAYIDS_VER2]) {
+            Ok(version) => version,
+            Err(status) => return status,
+        };
+        *out = *id;
+        out.version = version;
+    }
+    *count = ids.len() as u32;
+    NVAPI_OK
+}
+
+extern "C" fn GPU_GetAllDisplayIds(hPhysicalGpu: NvPhysicalGpuHandle, pDisplayIds: *mut NV_GPU_DISPLAYIDS, pDisplayIdCount: *mut u32) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe { put_display_ids(&gpu.current.display_ids, pDisplayIds, pDisplayIdCount) })
+}
+
+extern "C" fn GPU_GetConnectedDisplayIds(hPhysicalGpu: NvPhysicalGpuHandle, pDisplayIds: *mut NV_GPU_DISPLAYIDS, pDisplayIdCount: *mut u32, _flags: NV_GPU_CONNECTED_IDS_FLAG) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        let ids: Vec<_> = gpu.current.display_ids.iter()
+            .filter(|id| id.flags & NV_GPU_DISPLAYIDS_FLAGS_CONNECTED != 0)
+            .cloned().collect();
+        put_display_ids(&ids, pDisplayIds, pDisplayIdCount)
+    })
+}
+
+/// Outputs are numbered by the position of their display in `MockGpu::display_ids`.
+extern "C" fn SYS_GetGpuAndOutputIdFromDisplayId(displayId: u32, hPhysicalGpu: *mut NvPhysicalGpuHandle, outputId: *mut u32) -> NvAPI_Status {
+    with_state(|state| unsafe {
+        if hPhysicalGpu.is_null() || outputId.is_null() {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        for (index, gpu) in state.gpus.iter().enumerate() {
+            if let Some(output) = gpu.current.display_ids.iter().position(|id| id.displayId == displayId) {
+                *hPhysicalGpu = gpu_handle(state.generation, index);
+                *outputId = 1 << output;
+                return NVAPI_OK
+            }
+        }
+        NVAPI_ID_OUT_OF_RANGE
+    })
+}
+
+extern "C" fn GPU_GetEDID(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        if pEDID.is_null() {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        let version = ptr::read_unaligned(pEDID as *const u32);
+        let edid = match gpu.current.edids.iter().find(|&&(id, _)| id == displayOutputId) {
+            Some((_, edid)) => edid,
+            None => return NVAPI_DATA_NOT_FOUND,
+        };
+
+        let put = |out: &mut NV_EDID_V1, offset: usize| -> NvAPI_Status {
+            if offset > edid.len() {
+                return NVAPI_INVALID_ARGUMENT
+            }
+            out.EDID_Data = [0; NV_EDID_DATA_SIZE];
+            for (out, &b) in out.EDID_Data.iter_mut().zip(&edid[offset..]) {
+                *out = b;
+            }
+            NVAPI_OK
+        };
+
+        // only V3 can page through EDIDs larger than a single buffer
+        match version {
+            NV_EDID_VER1 => put(&mut *(pEDID as *mut NV_EDID_V1), 0),
+            NV_EDID_VER2 => {
+                let out = &mut *(pEDID as *mut NV_EDID_V2);
+                out.sizeofEDID = edid.len() as u32;
+                put(&mut out.v1, 0)
+            },
+            NV_EDID_VER3 => {
+                let out = &mut *pEDID;
+                out.sizeofEDID = edid.len() as u32;
+                out.edidId = 1;
+                let offset = out.offset as usize;
+                put(&mut out.v2.v1, offset)
+            },
+            _ => NVAPI_INCOMPATIBLE_STRUCT_VERSION,
+        }
+    })
+}
+
+extern "C" fn GPU_SetEDID(hPhysicalGpu: NvPhysicalGpuHandle, displayOutputId: u32, pEDID: *mut NV_EDID) -> NvAPI_Status {
+    with_gpu(hPhysicalGpu, |gpu| unsafe {
+        if pEDID.is_null() {
+            return NVAPI_INVALID_ARGUMENT
+        }
+        let input = match ptr::read_unaligned(pEDID as *const u32) {
+            NV_EDID_VER2 => &*(pEDID as *const NV_EDID_V2),
+            NV_EDID_VER3 => &(*pEDID).v2,
+            _ => return NVAPI_INCOMPATIBLE_STRUCT_VERSION,
+        };
+        let size = input.sizeofEDID as usize;
+        if size > NV_EDID_DATA_SIZE {
+            return NVAPI_INVALID_ARGUMENT
+        }
+
+        let edids = &mut gpu.current.edids;
+        edids.retain(|&(id, _)| id != displayOutputId);
+        if size > 0 {
+            edids.push((displayOutputId, input.EDID_Data[..size].to_vec()));
+        }
+        NVAPI_OK
+    })
+}
@@ -1,5 +1,8 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::os::raw::c_void;
+use std::sync::Mutex;
+use std::path::PathBuf;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
 use crate::status::{Status, NvAPI_Status};
 use crate::types;
 
@@ -9,50 +12,128 @@ pub type QueryInterfaceFn = extern "C" fn(id: u32) -> *const c_void;
 pub const LIBRARY_NAME: &'static [u8; 10] = b"nvapi.dll\0";
 #[cfg(all(windows, target_pointer_width = "64"))]
 pub const LIBRARY_NAME: &'static [u8; 12] = b"nvapi64.dll\0";
+#[cfg(unix)]
+pub const LIBRARY_NAME: &[u8; 19] = b"libnvidia-api.so.1\0";
 
 pub const FN_NAME: &'static [u8; 21] = b"nvapi_QueryInterface\0";
 
-static QUERY_INTERFACE_CACHE: AtomicUsize = AtomicUsize::new(0);
+/// Environment variable that overrides the path of the library to load.
+pub const LIBRARY_PATH_ENV: &str = "NVAPI_LIBRARY_PATH";
 
+static QUERY_INTERFACE_CACHE: AtomicUsize = AtomicUsize::new(0);
+/// Cached in place of the entry point when the library failed to load.
+#[cfg(any(windows, unix))]
+const QUERY_INTERFACE_FAILED: usize = usize::MAX;
+static LIBRARY_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Overrides the `nvapi_QueryInterface` entry point used to resolve every API.
+///
+/// This must be called before any other NVAPI function, as resolved functions are cached.
 pub unsafe fn set_query_interface(ptr: QueryInterfaceFn) {
     QUERY_INTERFACE_CACHE.store(ptr as usize, Ordering::Relaxed);
 }
 
-#[cfg(not(windows))]
-pub fn nvapi_QueryInterface(id: u32) -> crate::Result<usize> {
-    // TODO: Apparently nvapi is available for macOS?
-    Err(Status::LibraryNotFound)
+/// Overrides the library that `nvapi_QueryInterface` is loaded from, such as
+/// DXVK-NVAPI or a stub for testing.
+///
+/// Takes precedence over `LIBRARY_PATH_ENV`, and has no effect once the library has been loaded.
+/// A library that failed to load is only attempted again after calling this.
+pub fn set_library_path<P: Into<PathBuf>>(path: P) {
+    *LIBRARY_PATH.lock().unwrap_or_else(|e| e.into_inner()) = Some(path.into());
+    #[cfg(any(windows, unix))]
+    let _ = QUERY_INTERFACE_CACHE.compare_exchange(QUERY_INTERFACE_FAILED, 0, Ordering::Relaxed, Ordering::Relaxed);
+}
+
+/// The library to load, as a null-terminated string.
+#[cfg(any(windows, unix))]
+fn library_path() -> Option<CString> {
+    let path = LIBRARY_PATH.lock().unwrap_or_else(|e| e.into_inner()).clone()
+        .or_else(|| std::env::var_os(LIBRARY_PATH_ENV).map(PathBuf::from));
+
+    match path {
+        Some(path) => path_cstring(path),
+        None => Some(CString::new(&LIBRARY_NAME[..LIBRARY_NAME.len() - 1]).unwrap()),
+    }
+}
+
+#[cfg(unix)]
+fn path_cstring(path: PathBuf) -> Option<CString> {
+    use std::os::unix::ffi::OsStringExt;
+
+    CString::new(path.into_os_string().into_vec()).ok()
 }
 
 #[cfg(windows)]
+fn path_cstring(path: PathBuf) -> Option<CString> {
+    path.into_os_string().into_string().ok().and_then(|path| CString::new(path).ok())
+}
+
+#[cfg(not(any(windows, unix)))]
 pub fn nvapi_QueryInterface(id: u32) -> crate::Result<usize> {
-    use winapi::um::libloaderapi::{GetProcAddress, LoadLibraryA};
-    use std::mem;
-    use std::os::raw::c_char;
+    match QUERY_INTERFACE_CACHE.load(Ordering::Relaxed) {
+        0 => Err(Status::LibraryNotFound),
+        ptr => unsafe { call_query_interface(ptr, id) },
+    }
+}
 
+#[cfg(any(windows, unix))]
+pub fn nvapi_QueryInterface(id: u32) -> crate::Result<usize> {
     unsafe {
         let ptr = match QUERY_INTERFACE_CACHE.load(Ordering::Relaxed) {
-            0 => {
-                let lib = LoadLibraryA(LIBRARY_NAME.as_ptr() as *const c_char);
-                if lib.is_null() {
-                    Err(Status::LibraryNotFound)
-                } else {
-                    let ptr = GetProcAddress(lib, FN_NAME.as_ptr() as *const c_char);
-                    if ptr.is_null() {
-                        Err(Status::LibraryNotFound)
-                    } else {
-                        QUERY_INTERFACE_CACHE.store(ptr as usize, Ordering::Relaxed);
-                        Ok(ptr as usize)
-                    }
-                }
+            0 => match load_query_interface() {
+                Ok(ptr) => {
+                    QUERY_INTERFACE_CACHE.store(ptr, Ordering::Relaxed);
+                    ptr
+                },
+                Err(e) => {
+                    // avoid searching for the library on every call
+                    let _ = QUERY_INTERFACE_CACHE.compare_exchange(0, QUERY_INTERFACE_FAILED, Ordering::Relaxed, Ordering::Relaxed);
+                    return Err(e)
+                },
             },
-            ptr => Ok(ptr),
-        }?;
+            QUERY_INTERFACE_FAILED => return Err(Status::LibraryNotFound),
+            ptr => ptr,
+        };
+
+        call_query_interface(ptr, id)
+    }
+}
+
+#[cfg(windows)]
+unsafe fn load_query_interface() -> crate::Result<usize> {
+    use winapi::um::libloaderapi::{GetProcAddress, LoadLibraryA};
+
+    let path = library_path().ok_or(Status::LibraryNotFound)?;
+    let lib = LoadLibraryA(path.as_ptr());
+    if lib.is_null() {
+        return Err(Status::LibraryNotFound)
+    }
+
+    match GetProcAddress(lib, FN_NAME.as_ptr() as *const c_char) {
+        ptr if ptr.is_null() => Err(Status::LibraryNotFound),
+        ptr => Ok(ptr as usize),
+    }
+}
+
+#[cfg(unix)]
+unsafe fn load_query_interface() -> crate::Result<usize> {
+    let path = library_path().ok_or(Status::LibraryNotFound)?;
+    // the library is never unloaded, as resolved functions are cached for the lifetime of the process
+    let lib = libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+    if lib.is_null() {
+        return Err(Status::LibraryNotFound)
+    }
+
+    match libc::dlsym(lib, FN_NAME.as_ptr() as *const c_char) {
+        ptr if ptr.is_null() => Err(Status::LibraryNotFound),
+        ptr => Ok(ptr as usize),
+    }
+}
 
-        match mem::transmute::<_, QueryInterfaceFn>(ptr)(id) as usize {
-            0 => Err(Status::NoImplementation),
-            ptr => Ok(ptr),
-        }
+unsafe fn call_query_interface(ptr: usize, id: u32) -> crate::Result<usize> {
+    match std::mem::transmute::<_, QueryInterfaceFn>(ptr)(id) as usize {
+        0 => Err(Status::NoImplementation),
+        ptr => Ok(ptr),
     }
 }
 
@@ -639,6 +639,7 @@ NvAPI_GPU_ClientFanPoliciesSetControl = 0xc181947a,
 NvAPI_GPU_ClientGetLastOcScannerResults = 0x593e8e72,
 NvAPI_GPU_ClientGetOcConfig = 0x210f1841,
 NvAPI_GPU_ClientIllumDevicesGetControl = 0x73c01d58,
+NvAPI_GPU_ClientIllumDevicesGetInfo = 0xd4100e58,
 NvAPI_GPU_ClientIllumDevicesSetControl = 0x57024c62,
 NvAPI_GPU_ClientIllumZonesGetControl = 0x3dbf5764,
 NvAPI_GPU_ClientIllumZonesGetInfo = 0x4b81241b,
@@ -649,6 +650,16 @@ NvAPI_GPU_ClientRevertOc = 0xcc727b22,
 NvAPI_GPU_ClientStartOcScanner = 0xbc4aee25,
 NvAPI_GPU_ClientStopOcScanner = 0xc28b73de,
 
+// source: https://github.com/LibreHardwareMonitor/LibreHardwareMonitor/blob/master/LibreHardwareMonitorLib/Interop/NvApi.cs
+
+NvAPI_GPU_ThermalGetSensors = 0x65fe3aad,
+
+// source: NVAPI SDK nvapi_interface.h
+
+NvAPI_GPU_GetAdapterIdFromPhysicalGpu = 0x0ff07fde,
+NvAPI_GPU_GetLogicalGpuInfo = 0x842b066e,
+NvAPI_GPU_GetGPUInfo = 0xafd1b02c,
+
 // source: https://github.com/processhacker2/plugins-extra/blob/master/NvGpuPlugin/nvidia.c
 
 NvAPI_GPU_GetUsages = 0x189a1fdf,
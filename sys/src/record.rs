@@ -0,0 +1,623 @@
+//! Capture and replay of raw NVAPI traffic.
+//!
+//! With the `record` feature enabled, every NVAPI function is dispatched through
+//! this module. [`record`] logs each call's `Api` id, the bytes of its arguments
+//! before and after the call, and the returned status. A [`Capture`] can later be
+//! replayed with [`Capture::replay`], answering the same calls without a driver.
+//!
+//! Pointer arguments are captured as a single value of the pointee type, which
+//! includes the version header of any versioned struct. Pointers to counted
+//! arrays are captured in full, using the count passed alongside them, versioned
+//! structs up to the size in their version header, and C strings up to their NUL
+//! terminator. The data behind pointers stored within structs is captured as
+//! additional arguments following the call's own, and the pointers themselves are
+//! captured as zero. Calls are matched on replay by their `Api` id and input
+//! bytes, consuming recorded calls in order and repeating the last match once they
+//! run out.
+//!
+//! The capture format is plain text with one call per line:
+//!
+//! ```text
+//! 0150e828 0 # NvAPI_Initialize
+//! 5f608315 0 v=0001564e00000000 o=00000000>4c040000 # NvAPI_GPU_GetTachReading
+//! ```
+//!
+//! Each argument is either `v=<hex>` (a value), `i=<hex>` (a pointer to input),
+//! `o=<in hex>><out hex>` (a pointer to output), or `null`.
+
+use std::io::{self, BufRead, Write};
+use std::sync::{Mutex, MutexGuard};
+use std::str::FromStr;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::{fmt, mem, ptr, slice};
+use crate::status::{NvAPI_Status, NVAPI_NO_IMPLEMENTATION};
+use crate::nvid::Api;
+use crate::{gpu, i2c, types};
+
+/// An argument to an NVAPI function, as seen by the dispatcher.
+#[derive(Debug, Clone)]
+pub enum Arg {
+    Value(Vec<u8>),
+    In(*const u8, usize),
+    InOut(*mut u8, usize),
+    Null,
+}
+
+impl Arg {
+    pub fn value<T: Copy>(value: &T) -> Self {
+        Arg::Value(unsafe { bytes(value as *const T as *const u8, mem::size_of::<T>()) })
+    }
+
+    /// The number of elements given by a count argument, read before the call.
+    unsafe fn count(&self) -> Option<usize> {
+        match *self {
+            Arg::Value(ref value) if value.len() == mem::size_of::<u32>() =>
+                Some(u32::from_ne_bytes([value[0], value[1], value[2], value[3]]) as usize),
+            Arg::In(ptr, len) if len == mem::size_of::<u32>() => Some(ptr::read_unaligned(ptr as *const u32) as usize),
+            Arg::InOut(ptr, len) if len == mem::size_of::<u32>() => Some(ptr::read_unaligned(ptr as *const u32) as usize),
+            _ => None,
+        }
+    }
+
+    /// Extends a pointer to a single element to cover `count` elements.
+    fn array(&self, count: usize) -> Self {
+        match *self {
+            Arg::In(ptr, len) => Arg::In(ptr, len * count),
+            Arg::InOut(ptr, len) => Arg::InOut(ptr, len * count),
+            ref arg => arg.clone(),
+        }
+    }
+
+    /// Shrinks a struct to the size given by its version header, as older versions
+    /// are passed through pointers to the newest layout.
+    unsafe fn versioned(&self) -> Self {
+        let (ptr, len) = match *self {
+            Arg::In(ptr, len) => (ptr, len),
+            Arg::InOut(ptr, len) => (ptr as *const u8, len),
+            ref arg => return arg.clone(),
+        };
+        if len <= mem::size_of::<u32>() {
+            return self.clone()
+        }
+
+        let version = ptr::read_unaligned(ptr as *const u32);
+        let size = types::GET_NVAPI_SIZE(version);
+        let shrink = (1..=0xff).contains(&types::GET_NVAPI_VERSION(version)) && size > mem::size_of::<u32>() && size < len;
+        match *self {
+            Arg::In(ptr, _) if shrink => Arg::In(ptr, size),
+            Arg::InOut(ptr, _) if shrink => Arg::InOut(ptr, size),
+            ref arg => arg.clone(),
+        }
+    }
+
+    /// Extends a pointer to a single character to cover a NUL-terminated string.
+    unsafe fn string(&self) -> Self {
+        match *self {
+            Arg::In(ptr, _) => Arg::In(ptr, CStr::from_ptr(ptr as *const c_char).to_bytes_with_nul().len()),
+            ref arg => arg.clone(),
+        }
+    }
+
+    /// The data behind a pointer stored within this struct argument.
+    unsafe fn embedded(&self, embedded: &Embedded) -> Option<Self> {
+        let (base, len) = match *self {
+            Arg::In(ptr, len) => (ptr, len),
+            Arg::InOut(ptr, len) => (ptr as *const u8, len),
+            _ => return None,
+        };
+        let field = |offset: usize, size: usize| match offset + size <= len {
+            true => Some(base.add(offset)),
+            false => None,
+        };
+
+        let pointer = ptr::read_unaligned(field(embedded.offset, mem::size_of::<usize>())? as *const *mut u8);
+        let size = match embedded.len {
+            EmbeddedLen::Fixed(size) => size,
+            EmbeddedLen::Field(offset) => ptr::read_unaligned(field(offset, mem::size_of::<u32>())? as *const u32) as usize,
+        };
+        Some(match (pointer.is_null(), embedded.output) {
+            (true, _) => Arg::Null,
+            (false, true) => Arg::InOut(pointer, size),
+            (false, false) => Arg::In(pointer, size),
+        })
+    }
+
+    /// The captured state of the argument before the call.
+    unsafe fn input(&self) -> ArgData {
+        match *self {
+            Arg::Value(ref value) => ArgData::Value(value.clone()),
+            Arg::In(ptr, len) => ArgData::Input(bytes(ptr, len)),
+            Arg::InOut(ptr, len) => ArgData::Output(bytes(ptr, len), Vec::new()),
+            Arg::Null => ArgData::Null,
+        }
+    }
+}
+
+/// Describes how an argument type is captured.
+pub trait CallArg {
+    fn arg(&self) -> Arg;
+}
+
+macro_rules! call_arg_value {
+    ($($ty:ty),*) => {
+        $(
+            impl CallArg for $ty {
+                fn arg(&self) -> Arg {
+                    Arg::value(self)
+                }
+            }
+        )*
+    };
+}
+
+call_arg_value! { u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64 }
+
+impl<T> CallArg for *const T {
+    fn arg(&self) -> Arg {
+        match self.is_null() {
+            true => Arg::Null,
+            false => Arg::In(*self as *const u8, mem::size_of::<T>()),
+        }
+    }
+}
+
+impl<T> CallArg for *mut T {
+    fn arg(&self) -> Arg {
+        match self.is_null() {
+            true => Arg::Null,
+            false => Arg::InOut(*self as *mut u8, mem::size_of::<T>()),
+        }
+    }
+}
+
+/// Pointer arguments that point to counted arrays, as `(pointer, count)` argument indices.
+///
+/// The count is either passed by value or points to the capacity of the array.
+fn arrays(api: Api) -> &'static [(usize, usize)] {
+    match api {
+        Api::NvAPI_GPU_GetAllDisplayIds | Api::NvAPI_GPU_GetConnectedDisplayIds => &[(1, 2)],
+        Api::NvAPI_GPU_RestoreCoolerSettings | Api::NvAPI_GPU_RestoreCoolerPolicyTable => &[(1, 2)],
+        _ => &[],
+    }
+}
+
+/// Pointer arguments that point to NUL-terminated strings, as argument indices.
+fn strings(api: Api) -> &'static [usize] {
+    match api {
+        Api::NvAPI_GetAssociatedNvidiaDisplayHandle | Api::NvAPI_DISP_GetAssociatedUnAttachedNvidiaDisplayHandle => &[0],
+        _ => &[],
+    }
+}
+
+/// A pointer stored within a struct argument.
+struct Embedded {
+    /// The index of the struct argument
+    arg: usize,
+    /// The offset of the pointer within the struct
+    offset: usize,
+    len: EmbeddedLen,
+    /// Whether the driver writes to the data
+    output: bool,
+}
+
+/// The size of the data behind an embedded pointer.
+enum EmbeddedLen {
+    Fixed(usize),
+    /// Given by a `u32` field of the struct at this offset
+    Field(usize),
+}
+
+/// `NV_I2C_INFO_EX` shares the layout of `NV_I2C_INFO` up to `pbData`.
+const fn i2c_info(size: usize, read: bool) -> [Embedded; 2] {
+    [
+        Embedded {
+            arg: 1,
+            offset: mem::offset_of!(i2c::NV_I2C_INFO_V1, pbI2cRegAddress),
+            len: EmbeddedLen::Field(mem::offset_of!(i2c::NV_I2C_INFO_V1, regAddrSize)),
+            output: false,
+        },
+        Embedded {
+            arg: 1,
+            offset: mem::offset_of!(i2c::NV_I2C_INFO_V1, pbData),
+            len: EmbeddedLen::Field(size),
+            output: read,
+        },
+    ]
+}
+
+const I2C_READ: &[Embedded] = &i2c_info(mem::offset_of!(i2c::NV_I2C_INFO_V1, cbSize), true);
+const I2C_WRITE: &[Embedded] = &i2c_info(mem::offset_of!(i2c::NV_I2C_INFO_V1, cbSize), false);
+const I2C_READ_EX: &[Embedded] = &i2c_info(mem::offset_of!(i2c::private::NV_I2C_INFO_EX, cbSize), true);
+const I2C_WRITE_EX: &[Embedded] = &i2c_info(mem::offset_of!(i2c::private::NV_I2C_INFO_EX, cbSize), false);
+
+/// Pointers within struct arguments, whose data is captured as additional arguments.
+fn embedded(api: Api) -> &'static [Embedded] {
+    match api {
+        Api::NvAPI_I2CRead => I2C_READ,
+        Api::NvAPI_I2CWrite => I2C_WRITE,
+        Api::NvAPI_I2CReadEx => I2C_READ_EX,
+        Api::NvAPI_I2CWriteEx => I2C_WRITE_EX,
+        Api::NvAPI_GPU_GetLogicalGpuInfo => &[Embedded {
+            arg: 1,
+            offset: mem::offset_of!(gpu::NV_LOGICAL_GPU_DATA_V1, pOSAdapterId),
+            len: EmbeddedLen::Fixed(mem::size_of::<types::LUID>()),
+            output: true,
+        }],
+        _ => &[],
+    }
+}
+
+/// The arguments of a call, with versioned structs limited to their version's size and
+/// counted arrays and strings covering all of their elements, followed by the data behind
+/// any embedded pointers.
+///
+/// Also returns the `(argument, offset)` of each embedded pointer.
+unsafe fn expand_args(api: Api, args: &[Arg]) -> (Vec<Arg>, Vec<(usize, usize)>) {
+    let mut expanded: Vec<_> = args.iter().enumerate().map(|(i, arg)| match arrays(api).iter().any(|&(array, _)| array == i) {
+        true => arg.clone(),
+        false => arg.versioned(),
+    }).collect();
+    for &(array, count) in arrays(api) {
+        if let (Some(arg), Some(count)) = (args.get(array), args.get(count).and_then(|count| count.count())) {
+            expanded[array] = arg.array(count);
+        }
+    }
+    for &string in strings(api) {
+        if let Some(arg) = args.get(string) {
+            expanded[string] = arg.string();
+        }
+    }
+
+    let mut pointers = Vec::new();
+    for embedded in embedded(api) {
+        if let Some(arg) = expanded.get(embedded.arg).and_then(|arg| arg.embedded(embedded)) {
+            pointers.push((embedded.arg, embedded.offset));
+            expanded.push(arg);
+        }
+    }
+    (expanded, pointers)
+}
+
+/// Clears embedded pointers from captured arguments, so that they don't depend on memory layout.
+fn clear_pointers(data: &mut [ArgData], pointers: &[(usize, usize)]) {
+    for &(arg, offset) in pointers {
+        let buffers = match data.get_mut(arg) {
+            Some(ArgData::Input(input)) => vec![input],
+            Some(ArgData::Output(input, output)) => vec![input, output],
+            _ => continue,
+        };
+        for buffer in buffers {
+            if let Some(pointer) = buffer.get_mut(offset..offset + mem::size_of::<usize>()) {
+                pointer.iter_mut().for_each(|b| *b = 0);
+            }
+        }
+    }
+}
+
+unsafe fn bytes(ptr: *const u8, len: usize) -> Vec<u8> {
+    slice::from_raw_parts(ptr, len).to_vec()
+}
+
+/// A captured argument.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArgData {
+    Value(Vec<u8>),
+    Input(Vec<u8>),
+    /// The contents of an output pointer before and after the call.
+    Output(Vec<u8>, Vec<u8>),
+    Null,
+}
+
+impl ArgData {
+    /// Whether the inputs of two captured arguments are identical.
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ArgData::Value(lhs), ArgData::Value(rhs)) => lhs == rhs,
+            (ArgData::Input(lhs), ArgData::Input(rhs)) => lhs == rhs,
+            (ArgData::Output(lhs, _), ArgData::Output(rhs, _)) => lhs == rhs,
+            (ArgData::Null, ArgData::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for ArgData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ArgData::Value(ref value) => write!(f, "v={}", Hex(value)),
+            ArgData::Input(ref input) => write!(f, "i={}", Hex(input)),
+            ArgData::Output(ref input, ref output) => write!(f, "o={}>{}", Hex(input), Hex(output)),
+            ArgData::Null => f.write_str("null"),
+        }
+    }
+}
+
+impl FromStr for ArgData {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "null" {
+            return Ok(ArgData::Null)
+        }
+
+        match s.split_at(s.find('=').ok_or(ParseError)? + 1) {
+            ("v=", value) => parse_hex(value).map(ArgData::Value),
+            ("i=", input) => parse_hex(input).map(ArgData::Input),
+            ("o=", data) => {
+                let split = data.find('>').ok_or(ParseError)?;
+                Ok(ArgData::Output(parse_hex(&data[..split])?, parse_hex(&data[split + 1..])?))
+            },
+            _ => Err(ParseError),
+        }
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for Hex<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_hex(s: &str) -> Result<Vec<u8>, ParseError> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return Err(ParseError)
+    }
+
+    (0..s.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| ParseError))
+        .collect()
+}
+
+/// A malformed capture line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid NVAPI capture")
+    }
+}
+
+impl std::error::Error for ParseError { }
+
+/// A single captured NVAPI call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Call {
+    /// The `Api` id of the function.
+    pub api: u32,
+    pub status: NvAPI_Status,
+    pub args: Vec<ArgData>,
+}
+
+impl Call {
+    pub fn api(&self) -> Option<Api> {
+        Api::from_id(self.api).ok()
+    }
+
+    /// Whether `self` was called with the same inputs as `other`.
+    pub fn matches(&self, other: &Self) -> bool {
+        self.api == other.api && self.args.len() == other.args.len() &&
+            self.args.iter().zip(&other.args).all(|(lhs, rhs)| lhs.matches(rhs))
+    }
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:08x} {}", self.api, self.status)?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        if let Some(api) = self.api() {
+            write!(f, " # {:?}", api)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Call {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.split('#').next().unwrap_or_default();
+        let mut words = s.split_whitespace();
+        let api = words.next().ok_or(ParseError)
+            .and_then(|api| u32::from_str_radix(api, 16).map_err(|_| ParseError))?;
+        let status = words.next().ok_or(ParseError)
+            .and_then(|status| status.parse().map_err(|_| ParseError))?;
+
+        Ok(Call {
+            api,
+            status,
+            args: words.map(str::parse).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// A sequence of captured NVAPI calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Capture {
+    pub calls: Vec<Call>,
+}
+
+impl Capture {
+    /// Parses a capture, ignoring blank lines and `#` comments.
+    pub fn read<R: BufRead>(read: R) -> io::Result<Self> {
+        let mut calls = Vec::new();
+        for line in read.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue
+            }
+
+            calls.push(line.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?);
+        }
+
+        Ok(Capture {
+            calls,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut write: W) -> io::Result<()> {
+        for call in &self.calls {
+            writeln!(write, "{}", call)?;
+        }
+
+        Ok(())
+    }
+
+    /// Answers all NVAPI calls from this capture until the returned session is dropped.
+    pub fn replay(self) -> Session {
+        let guard = lock(&SESSION);
+        *lock(&MODE) = Mode::Replay {
+            consumed: vec![false; self.calls.len()],
+            capture: self,
+        };
+
+        Session {
+            _guard: guard,
+        }
+    }
+}
+
+/// Records all NVAPI calls to `out` until the returned session is finished or dropped.
+///
+/// Each call is written and flushed as it completes, so the capture survives a crash.
+pub fn record<W: Write + Send + 'static>(out: W) -> Session {
+    let guard = lock(&SESSION);
+    *lock(&MODE) = Mode::Record {
+        out: Box::new(out),
+        capture: Default::default(),
+        error: None,
+    };
+
+    Session {
+        _guard: guard,
+    }
+}
+
+/// An active recording or replay.
+pub struct Session {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Session {
+    /// Ends the session, returning the calls recorded or any error encountered
+    /// while writing them out.
+    pub fn finish(self) -> io::Result<Capture> {
+        match mem::replace(&mut *lock(&MODE), Mode::Passthrough) {
+            Mode::Record { error: Some(e), .. } => Err(e),
+            Mode::Record { mut out, capture, .. } => out.flush().map(|_| capture),
+            Mode::Replay { capture, .. } => Ok(capture),
+            Mode::Passthrough => Ok(Default::default()),
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if let Mode::Record { mut out, .. } = mem::replace(&mut *lock(&MODE), Mode::Passthrough) {
+            let _ = out.flush();
+        }
+    }
+}
+
+enum Mode {
+    Passthrough,
+    Record {
+        out: Box<dyn Write + Send>,
+        capture: Capture,
+        error: Option<io::Error>,
+    },
+    Replay {
+        capture: Capture,
+        consumed: Vec<bool>,
+    },
+}
+
+static SESSION: Mutex<()> = Mutex::new(());
+static MODE: Mutex<Mode> = Mutex::new(Mode::Passthrough);
+
+fn lock<T>(mutex: &'static Mutex<T>) -> MutexGuard<'static, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub(crate) unsafe fn dispatch<F: FnOnce() -> NvAPI_Status>(api: Api, args: &[Arg], call: F) -> NvAPI_Status {
+    let mut mode = lock(&MODE);
+    match *mode {
+        Mode::Passthrough => {
+            drop(mode);
+            call()
+        },
+        Mode::Replay { ref capture, ref mut consumed } => {
+            let (args, pointers) = expand_args(api, args);
+            let mut input = Call {
+                api: api.id(),
+                status: 0,
+                args: args.iter().map(|arg| arg.input()).collect(),
+            };
+            clear_pointers(&mut input.args, &pointers);
+            let mut matches = capture.calls.iter().enumerate().filter(|&(_, call)| call.matches(&input));
+            let found = match matches.clone().find(|&(i, _)| !consumed[i]) {
+                Some(found) => Some(found),
+                None => matches.next_back(),
+            };
+
+            match found {
+                Some((i, call)) => {
+                    consumed[i] = true;
+                    // the recorded pointers were cleared, so keep the caller's
+                    let saved: Vec<_> = pointers.iter().filter_map(|&(arg, offset)| match args[arg] {
+                        Arg::InOut(ptr, _) => Some((ptr.add(offset), ptr::read_unaligned(ptr.add(offset) as *const usize))),
+                        _ => None,
+                    }).collect();
+                    for (arg, data) in args.iter().zip(&call.args) {
+                        if let (&Arg::InOut(ptr, len), ArgData::Output(_, output)) = (arg, data) {
+                            ptr::copy_nonoverlapping(output.as_ptr(), ptr, len.min(output.len()));
+                        }
+                    }
+                    for (field, pointer) in saved {
+                        ptr::write_unaligned(field as *mut usize, pointer);
+                    }
+                    call.status
+                },
+                None => NVAPI_NO_IMPLEMENTATION,
+            }
+        },
+        Mode::Record { .. } => {
+            // the call itself must not hold the lock
+            drop(mode);
+            let (args, pointers) = expand_args(api, args);
+            let mut args: Vec<_> = args.iter().map(|arg| (arg, arg.input())).collect();
+            let status = call();
+            for (arg, data) in &mut args {
+                if let (&Arg::InOut(ptr, len), ArgData::Output(_, output)) = (*arg, data) {
+                    *output = bytes(ptr, len);
+                }
+            }
+            let mut call = Call {
+                api: api.id(),
+                status,
+                args: args.into_iter().map(|(_, data)| data).collect(),
+            };
+            clear_pointers(&mut call.args, &pointers);
+
+            if let Mode::Record { ref mut out, ref mut capture, ref mut error } = *lock(&MODE) {
+                if error.is_none() {
+                    if let Err(e) = writeln!(out, "{}", call).and_then(|_| out.flush()) {
+                        *error = Some(e);
+                    }
+                }
+                capture.calls.push(call);
+            }
+
+            status
+        },
+    }
+}
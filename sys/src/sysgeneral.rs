@@ -0,0 +1,119 @@
+use crate::status::NvAPI_Status;
+use crate::types::NvAPI_ShortString;
+
+nvbits! {
+    /// Used in NvAPI_SYS_GetChipSetInfo()
+    pub enum NV_CHIPSET_INFO_FLAGS / ChipsetInfoFlags {
+        NV_CHIPSET_INFO_HYBRID / HYBRID = 0x00000001,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_SYS_GetChipSetInfo()
+    pub struct NV_CHIPSET_INFO_V1 {
+        /// structure version
+        pub version: u32,
+        /// vendor ID
+        pub vendorId: u32,
+        /// device ID
+        pub deviceId: u32,
+        /// vendor Name
+        pub szVendorName: NvAPI_ShortString,
+        /// device Name
+        pub szChipsetName: NvAPI_ShortString,
+    }
+}
+
+nvstruct! {
+    /// Used in NvAPI_SYS_GetChipSetInfo()
+    pub struct NV_CHIPSET_INFO_V2 {
+        pub v1: NV_CHIPSET_INFO_V1,
+        /// Chipset info flags - obsolete
+        pub flags: NV_CHIPSET_INFO_FLAGS,
+    }
+}
+nvinherit! { NV_CHIPSET_INFO_V2(v1: NV_CHIPSET_INFO_V1) }
+
+nvstruct! {
+    /// Used in NvAPI_SYS_GetChipSetInfo()
+    pub struct NV_CHIPSET_INFO_V3 {
+        pub v2: NV_CHIPSET_INFO_V2,
+        /// Chipset subsystem vendor identification
+        pub subSysVendorId: u32,
+        /// Chipset subsystem device identification
+        pub subSysDeviceId: u32,
+        /// subsystem vendor Name
+        pub szSubSysVendorName: NvAPI_ShortString,
+    }
+}
+nvinherit! { NV_CHIPSET_INFO_V3(v2: NV_CHIPSET_INFO_V2) }
+
+nvstruct! {
+    /// Used in NvAPI_SYS_GetChipSetInfo()
+    pub struct NV_CHIPSET_INFO_V4 {
+        pub v3: NV_CHIPSET_INFO_V3,
+        /// Host bridge vendor identification
+        pub HBvendorId: u32,
+        /// Host bridge device identification
+        pub HBdeviceId: u32,
+        /// Host bridge subsystem vendor identification
+        pub HBsubSysVendorId: u32,
+        /// Host bridge subsystem device identification
+        pub HBsubSysDeviceId: u32,
+    }
+}
+nvinherit! { NV_CHIPSET_INFO_V4(v3: NV_CHIPSET_INFO_V3) }
+
+pub type NV_CHIPSET_INFO = NV_CHIPSET_INFO_V4;
+
+nvversion! { NV_CHIPSET_INFO_VER_1(NV_CHIPSET_INFO_V1 = 4 * 3 + 64 * 2, 1) }
+nvversion! { NV_CHIPSET_INFO_VER_2(NV_CHIPSET_INFO_V2 = 4 * 4 + 64 * 2, 2) }
+nvversion! { NV_CHIPSET_INFO_VER_3(NV_CHIPSET_INFO_V3 = 4 * 6 + 64 * 3, 3) }
+nvversion! { NV_CHIPSET_INFO_VER_4(NV_CHIPSET_INFO_V4 = 4 * 10 + 64 * 3, 4) }
+nvversion! { NV_CHIPSET_INFO_VER = NV_CHIPSET_INFO_VER_4 }
+
+nvapi! {
+    pub type SYS_GetChipSetInfoFn = extern "C" fn(pChipSetInfo: *mut NV_CHIPSET_INFO) -> NvAPI_Status;
+
+    /// This function returns information about the system's chipset.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_INVALID_ARGUMENT`: pChipSetInfo is NULL.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: NV_CHIPSET_INFO version not compatible with driver.
+    pub unsafe fn NvAPI_SYS_GetChipSetInfo;
+}
+
+nvstruct! {
+    /// Used in NvAPI_SYS_GetLidAndDockInfo()
+    pub struct NV_LID_DOCK_PARAMS {
+        /// Structure version, constructed from the macro #NV_LID_DOCK_PARAMS_VER
+        pub version: u32,
+        pub currentLidState: u32,
+        pub currentDockState: u32,
+        /// Current lid policy
+        pub currentLidPolicy: u32,
+        /// Current dock policy
+        pub currentDockPolicy: u32,
+        /// Indicates whether the forced lid mechanism is present
+        pub forcedLidMechanismPresent: u32,
+        /// Indicates whether the forced dock mechanism is present
+        pub forcedDockMechanismPresent: u32,
+    }
+}
+
+nvversion! { NV_LID_DOCK_PARAMS_VER1(NV_LID_DOCK_PARAMS = 4 * 7, 1) }
+nvversion! { NV_LID_DOCK_PARAMS_VER = NV_LID_DOCK_PARAMS_VER1 }
+
+nvapi! {
+    pub type SYS_GetLidAndDockInfoFn = extern "C" fn(pLidAndDock: *mut NV_LID_DOCK_PARAMS) -> NvAPI_Status;
+
+    /// This function returns the current lid and dock information.
+    /// Notebook systems only.
+    ///
+    /// # Returns
+    ///
+    /// - `NVAPI_NOT_SUPPORTED`: The system is not a notebook.
+    /// - `NVAPI_INCOMPATIBLE_STRUCT_VERSION`: NV_LID_DOCK_PARAMS version not compatible with driver.
+    pub unsafe fn NvAPI_SYS_GetLidAndDockInfo;
+}
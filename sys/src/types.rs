@@ -53,6 +53,15 @@ nvstruct! {
 
 pub type NvLUID = NvGUID;
 
+nvstruct! {
+    /// The locally unique identifier Windows assigns to each display adapter,
+    /// as found in `DXGI_ADAPTER_DESC::AdapterLuid`.
+    pub struct LUID {
+        pub LowPart: u32,
+        pub HighPart: i32,
+    }
+}
+
 pub const NVAPI_MAX_PHYSICAL_GPUS: usize = 64;
 
 pub const NVAPI_MAX_PHYSICAL_BRIDGES: usize = 100;
@@ -112,3 +121,28 @@ pub fn GET_NVAPI_VERSION(ver: u32) -> u16 {
 pub fn GET_NVAPI_SIZE(ver: u32) -> usize {
     ver as usize & 0xffff
 }
+
+/// A struct beginning with a `version` field, identifying its layout to the driver.
+///
+/// Implemented by `nvversion!` for every versioned struct.
+pub trait Versioned: Copy {
+    /// Every version sharing this layout, from oldest to newest.
+    const VERSIONS: &'static [u32];
+    /// The newest version of this layout.
+    const VERSION: u32 = Self::VERSIONS[Self::VERSIONS.len() - 1];
+
+    fn version(&self) -> u32 {
+        unsafe { *(self as *const Self as *const u32) }
+    }
+
+    fn set_version(&mut self, version: u32) {
+        unsafe { *(self as *mut Self as *mut u32) = version }
+    }
+
+    /// A zeroed struct with its `version` field set.
+    fn versioned(version: u32) -> Self {
+        let mut data: Self = unsafe { ::std::mem::zeroed() };
+        data.set_version(version);
+        data
+    }
+}
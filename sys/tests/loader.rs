@@ -0,0 +1,19 @@
+// Loading of the NVAPI library on Unix.
+#![cfg(unix)]
+
+extern crate nvapi_sys;
+
+use nvapi_sys::nvapi::{self, NvAPI_Initialize};
+use nvapi_sys::status::NVAPI_LIBRARY_NOT_FOUND;
+
+#[test]
+fn library_path() {
+    // a library that does not export nvapi_QueryInterface
+    nvapi::set_library_path("libc.so.6");
+    assert_eq!(unsafe { NvAPI_Initialize() }, NVAPI_LIBRARY_NOT_FOUND);
+    // the failure is cached
+    assert_eq!(unsafe { NvAPI_Initialize() }, NVAPI_LIBRARY_NOT_FOUND);
+
+    nvapi::set_library_path("/nonexistent/libnvidia-api.so.1");
+    assert_eq!(unsafe { NvAPI_Initialize() }, NVAPI_LIBRARY_NOT_FOUND);
+}
@@ -0,0 +1,120 @@
+extern crate nvapi;
+
+use nvapi::{Edid, EdidError, Kilohertz, Range};
+
+/// `(pixel_clock_10khz, h_active, h_blank, h_front, h_sync, v_active, v_blank, v_front, v_sync, width_mm, height_mm)`
+fn timing(t: (u16, u16, u16, u16, u16, u16, u16, u16, u16, u16, u16)) -> [u8; 18] {
+    let (clock, ha, hb, hf, hs, va, vb, vf, vs, w, h) = t;
+    let mut d = [0u8; 18];
+    d[..2].copy_from_slice(&clock.to_le_bytes());
+    d[2] = ha as u8;
+    d[3] = hb as u8;
+    d[4] = ((ha >> 8) << 4 | hb >> 8) as u8;
+    d[5] = va as u8;
+    d[6] = vb as u8;
+    d[7] = ((va >> 8) << 4 | vb >> 8) as u8;
+    d[8] = hf as u8;
+    d[9] = hs as u8;
+    d[10] = ((vf & 0xf) << 4 | (vs & 0xf)) as u8;
+    d[11] = ((hf >> 8) << 6 | (hs >> 8) << 4 | (vf >> 4) << 2 | vs >> 4) as u8;
+    d[12] = w as u8;
+    d[13] = h as u8;
+    d[14] = ((w >> 8) << 4 | h >> 8) as u8;
+    d[17] = 0x18;
+    d
+}
+
+fn descriptor(tag: u8, payload: &[u8]) -> [u8; 18] {
+    let mut d = [0u8; 18];
+    d[3] = tag;
+    d[5..5 + payload.len()].copy_from_slice(payload);
+    d
+}
+
+fn checksum(block: &mut [u8]) {
+    let sum = block[..0x7f].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+    block[0x7f] = 0u8.wrapping_sub(sum);
+}
+
+/// A 1.4 base block with a CEA-861 extension declaring HDR support.
+fn monitor() -> Vec<u8> {
+    let mut base = vec![0u8; 0x80];
+    base[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+    // "DEL"
+    base[8..10].copy_from_slice(&0x10acu16.to_be_bytes());
+    base[10..12].copy_from_slice(&0xa0b1u16.to_le_bytes());
+    base[12..16].copy_from_slice(&0x12345678u32.to_le_bytes());
+    base[16] = 12;
+    base[17] = 30;
+    base[18] = 1;
+    base[19] = 4;
+    base[0x36..0x48].copy_from_slice(&timing((14850, 1920, 280, 88, 44, 1080, 45, 4, 5, 527, 296)));
+    base[0x48..0x5a].copy_from_slice(&descriptor(0xfc, b"TEST MONITOR\n"));
+    let mut limits = descriptor(0xfd, &[48, 44, 30, 160, 60]);
+    // max vertical rate +255
+    limits[4] = 0x02;
+    base[0x5a..0x6c].copy_from_slice(&limits);
+    base[0x6c..0x7e].copy_from_slice(&descriptor(0xff, b"ABC123\n      "));
+    base[0x7e] = 1;
+    checksum(&mut base);
+
+    let mut cea = vec![0u8; 0x80];
+    cea[0] = 0x02;
+    cea[1] = 3;
+    let hdr = [0xe6, 0x06, 0x05, 0x01, 0x60, 0x50, 0x40];
+    cea[4..4 + hdr.len()].copy_from_slice(&hdr);
+    cea[2] = 4 + hdr.len() as u8;
+    let dtd = cea[2] as usize;
+    cea[dtd..dtd + 18].copy_from_slice(&timing((7425, 1280, 370, 110, 40, 720, 30, 5, 5, 0, 0)));
+    checksum(&mut cea);
+
+    base.extend(cea);
+    base
+}
+
+#[test]
+fn parse() {
+    let edid = Edid::parse(&monitor()).unwrap();
+    assert_eq!(edid.manufacturer, "DEL");
+    assert_eq!(edid.product_code, 0xa0b1);
+    assert_eq!(edid.serial_number, 0x12345678);
+    assert_eq!(edid.week, Some(12));
+    assert_eq!(edid.year, 2020);
+    assert_eq!(edid.version, (1, 4));
+    assert_eq!(edid.name.as_ref().unwrap(), "TEST MONITOR");
+    assert_eq!(edid.serial.as_ref().unwrap(), "ABC123");
+
+    assert_eq!(edid.detailed_timings.len(), 2);
+    let preferred = &edid.detailed_timings[0];
+    assert_eq!(preferred.pixel_clock, Kilohertz(148500));
+    assert_eq!((preferred.horizontal_active, preferred.vertical_active), (1920, 1080));
+    assert_eq!((preferred.horizontal_sync_width, preferred.vertical_front_porch), (44, 4));
+    assert_eq!((preferred.width_mm, preferred.height_mm), (527, 296));
+    assert_eq!(preferred.refresh_rate(), 60000);
+    assert_eq!(edid.detailed_timings[1].horizontal_active, 1280);
+    assert_eq!(edid.detailed_timings[1].refresh_rate(), 60000);
+
+    let limits = edid.range_limits.unwrap();
+    assert_eq!(limits.vertical, Range { min: 48, max: 299 });
+    assert_eq!(limits.horizontal, Range { min: 30, max: 160 });
+    assert_eq!(limits.max_pixel_clock, Some(Kilohertz(600000)));
+
+    let hdr = edid.hdr_static_metadata.unwrap();
+    assert!(hdr.eotf_sdr && hdr.eotf_pq && !hdr.eotf_hlg);
+    assert_eq!(hdr.max_luminance, Some(400.0));
+    assert!(hdr.min_luminance.unwrap() > 0.0);
+}
+
+#[test]
+fn invalid() {
+    let edid = monitor();
+    assert_eq!(Edid::parse(&edid[..0x80]).unwrap_err(), EdidError::Truncated);
+
+    let mut corrupt = edid.clone();
+    corrupt[0x90] ^= 1;
+    assert_eq!(Edid::parse(&corrupt).unwrap_err(), EdidError::Checksum { block: 1 });
+
+    let mut corrupt = edid;
+    corrupt[0] = 0xff;
+    assert_eq!(Edid::parse(&corrupt).unwrap_err(), EdidError::InvalidHeader);
+}
@@ -0,0 +1,639 @@
+// Exercises the API against the simulated driver from `nvapi_sys::mock`.
+
+extern crate nvapi;
+
+use std::time::Duration;
+use nvapi::sys::mock::{Mock, MockGpu};
+use nvapi::{PhysicalGpu, LogicalGpu, Luid, GpuHardwareInfo, BusType, Architecture, ArchInfo, ChipRevision, VbiosVersion, BoardSerial, GpuIdentity, DisplayHandle, UnattachedDisplayHandle, ResilientGpu, Range, PState, PStateClientLimits, VoltageDomain, MicrovoltsDelta, ClockDomain, ClockFrequencyType, KilohertzDelta, Kilohertz, Percentage, Percentage1000, PowerTopologyDomain, PowerTopologyEntry, PowerTopologyRail, PowerTopologyChannel, Milliwatts, Kibibytes, Celsius, CoolerLevel, CoolerPolicy, FanCoolerControl, FanCoolerControlMode, IlluminationAttribute, IllumZone, IllumZoneType, IllumZoneLocation, IllumZoneControl, IllumZoneControlValue, IllumLevel, IllumColor, IllumPattern, IllumCycleType, EccStatus, EccConfiguration, EccConfigurationInfo, EccErrorCounts, Status};
+
+#[test]
+fn enumerate() {
+    let _mock = Mock::new(vec![MockGpu::default(), MockGpu::default()]).install();
+    nvapi::initialize().unwrap();
+
+    let gpus = PhysicalGpu::enumerate().unwrap();
+    assert_eq!(gpus.len(), 2);
+    assert_eq!(gpus[0].full_name().unwrap(), "GeForce GTX 1080");
+    assert_eq!(gpus[1].short_name().unwrap(), "GP104");
+    assert_eq!(gpus[0].pci_identifiers().unwrap().device_id, 0x1b8010de);
+    assert_eq!(nvapi::driver_version().unwrap(), (43142, "r430_00".into()));
+}
+
+#[test]
+fn no_gpus() {
+    let _mock = Mock::new(Vec::new()).install();
+    assert!(PhysicalGpu::enumerate().unwrap().is_empty());
+}
+
+#[test]
+fn not_supported() {
+    let gpu = MockGpu {
+        full_name: None,
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![gpu]).install();
+
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+    assert_eq!(gpu.full_name().unwrap_err(), Status::NotSupported);
+    assert_eq!(gpu.short_name().unwrap(), "GP104");
+}
+
+#[test]
+fn clock_frequencies() {
+    let _mock = Mock::default().install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    let base = gpu.clock_frequencies(ClockFrequencyType::Base).unwrap();
+    assert_eq!(base.get(&ClockDomain::Graphics), Some(&Kilohertz(1607000)));
+    let boost = gpu.clock_frequencies(ClockFrequencyType::Boost).unwrap();
+    assert_eq!(boost.get(&ClockDomain::Graphics), Some(&Kilohertz(1733000)));
+}
+
+#[test]
+fn all_clocks() {
+    let mut clocks = nvapi::sys::gpu::clock::private::NV_CLOCKS_INFO::zeroed();
+    clocks.clocks[0] = 1607000;
+    clocks.clocks[8] = 5005000;
+    let gpu = MockGpu {
+        all_clocks: Some(clocks),
+        ..Default::default()
+    };
+    let mock = Mock::new(vec![gpu]).install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    let all = gpu.all_clocks().unwrap();
+    assert_eq!(all.current[&ClockDomain::Graphics], Kilohertz(1607000));
+    assert_eq!(all.current[&ClockDomain::Memory], Kilohertz(5005000));
+    assert!(!all.current.contains_key(&ClockDomain::Processor));
+    assert!(all.default.is_empty());
+    assert!(all.boost.is_empty());
+
+    // with a separate shader clock
+    mock.update_gpu(0, |gpu| gpu.all_clocks.as_mut().unwrap().clocks[30] = 1544000);
+    let all = gpu.all_clocks().unwrap();
+    assert_eq!(all.current[&ClockDomain::Graphics], Kilohertz(772000));
+    assert_eq!(all.current[&ClockDomain::Processor], Kilohertz(1544000));
+
+    // the default and boost tables follow the current clocks
+    mock.update_gpu(0, |gpu| {
+        let clocks = &mut gpu.all_clocks.as_mut().unwrap().clocks;
+        clocks[12] = 1708000;
+        clocks[32] = 1607000;
+        clocks[64 + 8] = 5005000;
+    });
+    let all = gpu.all_clocks().unwrap();
+    assert_eq!(all.current[&ClockDomain::Video], Kilohertz(1708000));
+    assert_eq!(all.default.into_iter().collect::<Vec<_>>(), [(ClockDomain::Graphics, Kilohertz(1607000))]);
+    assert_eq!(all.boost.into_iter().collect::<Vec<_>>(), [(ClockDomain::Memory, Kilohertz(5005000))]);
+}
+
+#[test]
+fn set_pstates() {
+    let mock = Mock::default().install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    gpu.set_pstates(vec![(PState::P0, ClockDomain::Graphics, KilohertzDelta(100000))].into_iter()).unwrap();
+    let pstates = gpu.pstates().unwrap();
+    let p0 = pstates.pstates.iter().find(|p| p.id == PState::P0).unwrap();
+    let graphics = p0.clocks.iter().find(|c| c.domain() == ClockDomain::Graphics).unwrap();
+    assert_eq!(graphics.frequency_delta().value, KilohertzDelta(100000));
+
+    // out of range, and not editable
+    assert_eq!(gpu.set_pstates(vec![(PState::P0, ClockDomain::Graphics, KilohertzDelta(500000))].into_iter()).unwrap_err(), Status::InvalidArgument);
+    assert_eq!(gpu.set_pstates(vec![(PState::P8, ClockDomain::Graphics, KilohertzDelta(0))].into_iter()).unwrap_err(), Status::InvalidArgument);
+
+    let state = mock.gpu(0).unwrap().pstates.unwrap();
+    assert_eq!(state.pstates[0].clocks[0].freqDelta_kHz.value, 100000);
+}
+
+#[test]
+fn set_pstate_base_voltages() {
+    let mock = Mock::default().install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    gpu.set_pstate_base_voltages(vec![(PState::P0, VoltageDomain::Core, MicrovoltsDelta(50000))].into_iter()).unwrap();
+    let pstates = gpu.pstates().unwrap();
+    let p0 = pstates.pstates.iter().find(|p| p.id == PState::P0).unwrap();
+    assert_eq!(p0.base_voltages[0].voltage_delta.value, MicrovoltsDelta(50000));
+    assert_eq!(mock.gpu(0).unwrap().pstates.unwrap().pstates[0].baseVoltages[0].voltDelta_uV.value, 50000);
+
+    // out of range, and not editable
+    assert_eq!(gpu.set_pstate_base_voltages(vec![(PState::P0, VoltageDomain::Core, MicrovoltsDelta(150000))].into_iter()).unwrap_err(), Status::InvalidArgument);
+    assert_eq!(gpu.set_pstate_base_voltages(vec![(PState::P8, VoltageDomain::Core, MicrovoltsDelta(0))].into_iter()).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn pstate_client_limits() {
+    let mock = Mock::default().install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    gpu.set_pstate_client_limits(Range { min: PState::P0, max: PState::P8 }).unwrap();
+    assert_eq!(gpu.pstate_client_limits().unwrap(), PStateClientLimits { hard: PState::P0, soft: PState::P8 });
+    gpu.set_pstate_client_limits(Range::from_scalar(PState::P2)).unwrap();
+    assert_eq!(gpu.pstate_client_limits().unwrap(), PStateClientLimits { hard: PState::P2, soft: PState::P2 });
+    assert_eq!(gpu.set_pstate_client_limits(Range::from_scalar(PState::All)).unwrap_err(), Status::InvalidArgument);
+
+    gpu.enable_dynamic_pstates(true).unwrap();
+    assert_eq!(mock.gpu(0).unwrap().dynamic_pstates.unwrap().flags & 1, 1);
+    gpu.enable_dynamic_pstates(false).unwrap();
+    assert_eq!(mock.gpu(0).unwrap().dynamic_pstates.unwrap().flags & 1, 0);
+
+    gpu.enable_overclocked_pstates(true).unwrap();
+    assert_eq!(mock.gpu(0).unwrap().overclocked_pstates, Some(true));
+}
+
+#[test]
+fn power_topology() {
+    let mock = Mock::default().install();
+    mock.update_gpu(0, |gpu| {
+        let topology = gpu.power_topology.as_mut().unwrap();
+        topology.entries[1].domain = nvapi::sys::gpu::power::private::NV_GPU_POWER_TOPO_DOMAIN_BOARD;
+        topology.entries[1].power = 45000;
+        // a channel that isn't a known domain
+        topology.entries[2].domain = 5;
+        topology.entries[2].power = 12000;
+        topology.count = 3;
+    });
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    let topology = gpu.power_topology().unwrap();
+    assert_eq!(topology, vec![
+        PowerTopologyEntry { channel: 0, power: Percentage1000(42000) },
+        PowerTopologyEntry { channel: 1, power: Percentage1000(45000) },
+        PowerTopologyEntry { channel: 5, power: Percentage1000(12000) },
+    ]);
+    assert_eq!(topology.iter().map(PowerTopologyEntry::domain).collect::<Vec<_>>(), vec![Some(PowerTopologyDomain::Gpu), Some(PowerTopologyDomain::Board), None]);
+    assert_eq!(gpu.power_usage().unwrap(), vec![Percentage1000(42000), Percentage1000(45000), Percentage1000(12000)]);
+}
+
+#[test]
+fn power_topology_info() {
+    let mock = Mock::default().install();
+    mock.update_gpu(0, |gpu| {
+        let topology = gpu.power_topology.as_mut().unwrap();
+        topology.entries[1].power = 30000;
+        topology.count = 2;
+        gpu.describe_power_channel(1, nvapi::sys::gpu::power::private::NV_GPU_POWER_TOPO_RAIL_PCIE, 0);
+    });
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    let channels = gpu.power_topology_info().unwrap();
+    assert_eq!(channels, vec![
+        PowerTopologyChannel { channel: 0, rail: 0, reference: Some(Milliwatts(180000)) },
+        PowerTopologyChannel { channel: 0, rail: 3, reference: None },
+    ]);
+    assert_eq!(channels.iter().map(PowerTopologyChannel::rail).collect::<Vec<_>>(), vec![Some(PowerTopologyRail::TotalBoard), Some(PowerTopologyRail::Pcie)]);
+    let topology = gpu.power_topology().unwrap();
+    assert_eq!(channels.iter().zip(&topology).map(|(channel, entry)| channel.watts(entry.power)).collect::<Vec<_>>(), vec![Some(Milliwatts(75600)), None]);
+    assert_eq!(PowerTopologyRail::EightPin.to_string(), "8-pin");
+}
+
+#[test]
+fn set_power_limit() {
+    let _mock = Mock::default().install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    assert_eq!(gpu.power_limit().unwrap(), vec![Percentage1000(100000)]);
+    gpu.set_power_limit(vec![Percentage1000(110000)].into_iter()).unwrap();
+    assert_eq!(gpu.power_limit().unwrap(), vec![Percentage1000(110000)]);
+    assert_eq!(gpu.set_power_limit(vec![Percentage1000(130000)].into_iter()).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn cooler_levels() {
+    let _mock = Mock::default().install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    gpu.set_cooler_levels(None, vec![CoolerLevel { level: Percentage(80), policy: CoolerPolicy::Manual }].into_iter()).unwrap();
+    let cooler = gpu.cooler_settings(None).unwrap()[0];
+    assert_eq!(cooler.current_level, Percentage(80));
+    assert_eq!(cooler.current_policy, CoolerPolicy::Manual);
+
+    gpu.restore_cooler_settings(&[]).unwrap();
+    let cooler = gpu.cooler_settings(Some(0)).unwrap()[0];
+    assert_eq!(cooler.current_level, Percentage(30));
+    assert_eq!(cooler.current_policy, CoolerPolicy::TemperatureContinuous);
+}
+
+#[test]
+fn thermal_sensors() {
+    let mut gpu = MockGpu::default();
+    for (index, temperature) in [60, 71, 45].iter().enumerate() {
+        gpu.add_thermal_sensor(index, *temperature);
+    }
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    assert_eq!(gpu.thermal_sensors_mask().unwrap(), 0b111);
+    let sensors = gpu.thermal_sensors(0b110).unwrap();
+    assert_eq!(sensors.keys().cloned().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(Celsius::from(sensors[&1]), Celsius(71));
+    assert_eq!(gpu.thermal_sensors(0b1000).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn fan_coolers() {
+    let mut gpu = MockGpu {
+        cooler_settings: None,
+        ..Default::default()
+    };
+    gpu.add_fan_cooler(1, 3000, (30, 100), 40);
+    gpu.add_fan_cooler(2, 3000, (30, 100), 40);
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    assert_eq!(gpu.fan_cooler_info().unwrap()[1].max_rpm, 3000);
+    gpu.set_fan_cooler_control(vec![FanCoolerControl { id: 2, level: Percentage(70), mode: FanCoolerControlMode::Manual }].into_iter()).unwrap();
+    let status = gpu.fan_cooler_status().unwrap();
+    assert_eq!((status[0].level, status[1].level), (Percentage(40), Percentage(70)));
+    assert_eq!(gpu.fan_cooler_control().unwrap()[1].mode, FanCoolerControlMode::Manual);
+    assert_eq!(gpu.set_fan_cooler_control(vec![FanCoolerControl { id: 3, level: Percentage(70), mode: FanCoolerControlMode::Manual }].into_iter()).unwrap_err(), Status::InvalidArgument);
+
+    // the legacy interface falls back to the client fan coolers
+    gpu.set_cooler_levels(Some(0), vec![CoolerLevel { level: Percentage(90), policy: CoolerPolicy::Manual }].into_iter()).unwrap();
+    assert_eq!(gpu.fan_cooler_status().unwrap()[0].level, Percentage(90));
+    gpu.restore_cooler_settings(&[]).unwrap();
+    assert!(gpu.fan_cooler_control().unwrap().iter().all(|c| c.mode == FanCoolerControlMode::Auto));
+}
+
+#[test]
+fn illumination() {
+    let mut gpu = MockGpu::default();
+    gpu.illumination[IlluminationAttribute::LogoBrightness as usize] = Some(100);
+    gpu.add_illum_zone(IllumZoneType::Rgb, IllumZoneLocation::GpuTop0);
+    gpu.add_illum_zone(IllumZoneType::SingleColor, IllumZoneLocation::SliTop0);
+    let mock = Mock::new(vec![gpu]).install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    assert!(gpu.illumination_supported(IlluminationAttribute::LogoBrightness).unwrap());
+    assert!(!gpu.illumination_supported(IlluminationAttribute::SliBrightness).unwrap());
+    gpu.set_illumination(IlluminationAttribute::LogoBrightness, Percentage(25)).unwrap();
+    assert_eq!(gpu.illumination(IlluminationAttribute::LogoBrightness).unwrap(), Percentage(25));
+    assert_eq!(gpu.illumination(IlluminationAttribute::SliBrightness).unwrap_err(), Status::NotSupported);
+
+    let zones = gpu.illumination_zones().unwrap();
+    assert_eq!(zones[1], IllumZone { kind: IllumZoneType::SingleColor, device_index: 0, location: IllumZoneLocation::SliTop0 });
+
+    // an unknown location doesn't hide the other zones
+    mock.update_gpu(0, |gpu| gpu.illum_zones_info.as_mut().unwrap().zones[0].zoneLocation = 0x01);
+    let zones = gpu.illumination_zones().unwrap();
+    assert_eq!(zones.iter().map(|zone| zone.location).collect::<Vec<_>>(), vec![IllumZoneLocation::Invalid, IllumZoneLocation::SliTop0]);
+
+    let mut control = gpu.illumination_zone_control(false).unwrap();
+    let red = IllumLevel { color: IllumColor::Rgb { red: 0xff, green: 0, blue: 0 }, brightness: Percentage(50) };
+    control[0].value = IllumZoneControlValue::PiecewiseLinear {
+        endpoints: [red, IllumLevel { brightness: Percentage(0), ..red }],
+        pattern: IllumPattern {
+            cycle: IllumCycleType::FullRepeat,
+            group_count: 1,
+            rise: Duration::from_millis(500),
+            fall: Duration::from_millis(500),
+            hold_a: Duration::from_millis(1000),
+            hold_b: Duration::from_millis(0),
+            group_idle: Duration::from_millis(0),
+            phase_offset: Duration::from_millis(0),
+        },
+    };
+    control[1].value = IllumZoneControlValue::Manual(IllumLevel { color: IllumColor::Fixed, brightness: Percentage(10) });
+    gpu.set_illumination_zone_control(control.iter().cloned()).unwrap();
+    assert_eq!(gpu.illumination_zone_control(false).unwrap(), control);
+    assert_ne!(gpu.illumination_zone_control(true).unwrap(), control);
+
+    // colours must match the zone type
+    let invalid = IllumZoneControl { value: IllumZoneControlValue::Manual(red), ..control[1] };
+    assert_eq!(gpu.set_illumination_zone_control(vec![control[0], invalid].into_iter()).unwrap_err(), Status::InvalidArgument);
+}
+
+#[test]
+fn ecc() {
+    let mut gpu = MockGpu::default();
+    gpu.add_ecc(nvapi::sys::gpu::ecc::NV_ECC_CONFIGURATION_DEFERRED, true);
+    gpu.add_ecc_errors(3, 1);
+    let _mock = Mock::new(vec![gpu, MockGpu::default()]).install();
+    let gpus = PhysicalGpu::enumerate().unwrap();
+    let gpu = &gpus[0];
+
+    assert_eq!(gpu.ecc_status().unwrap(), EccStatus { supported: true, configuration: EccConfiguration::Deferred, enabled: true });
+    let errors = gpu.ecc_errors().unwrap();
+    assert_eq!(errors.current, EccErrorCounts { single_bit: 3, double_bit: 1 });
+    assert_eq!(errors.aggregate.total(), 4);
+
+    gpu.reset_ecc_errors(true, false).unwrap();
+    let errors = gpu.ecc_errors().unwrap();
+    assert_eq!((errors.current.total(), errors.aggregate.total()), (0, 4));
+
+    // only deferred changes are supported, taking effect after a reboot
+    assert_eq!(gpu.set_ecc_configuration(false, true).unwrap_err(), Status::InvalidArgument);
+    gpu.set_ecc_configuration(false, false).unwrap();
+    assert_eq!(gpu.ecc_configuration().unwrap(), EccConfigurationInfo { enabled: false, enabled_by_default: true });
+    assert!(gpu.ecc_status().unwrap().enabled);
+
+    assert_eq!(gpus[1].ecc_errors().unwrap_err(), Status::NotSupported);
+}
+
+#[test]
+fn update_gpu() {
+    let mock = Mock::default().install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    assert_eq!(gpu.tachometer().unwrap(), 1100);
+    mock.update_gpu(0, |gpu| gpu.tachometer = Some(2400));
+    assert_eq!(gpu.tachometer().unwrap(), 2400);
+}
+
+#[test]
+fn uninstalled() {
+    let gpu = {
+        let _mock = Mock::default().install();
+        PhysicalGpu::enumerate().unwrap().remove(0)
+    };
+
+    let _mock = Mock::new(Vec::new()).install();
+    assert_eq!(gpu.full_name().unwrap_err(), Status::ExpectedPhysicalGpuHandle);
+}
+
+#[test]
+fn struct_version_fallback() {
+    let gpu = MockGpu {
+        memory_info_version: 1,
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    let memory = gpu.memory_info().unwrap();
+    assert_eq!(memory.dedicated, Kibibytes(8 * 1024 * 1024));
+    assert_eq!(memory.dedicated_available_current, Kibibytes(0));
+}
+
+#[test]
+fn handle_invalidated() {
+    let second = MockGpu {
+        full_name: Some("GeForce GTX 1070".into()),
+        bus_id: Some(2),
+        ..Default::default()
+    };
+    let mock = Mock::new(vec![MockGpu::default(), second]).install();
+    let gpu = ResilientGpu::new(PhysicalGpu::enumerate().unwrap().remove(1)).unwrap();
+    let stale = PhysicalGpu::enumerate().unwrap().remove(1);
+
+    mock.reconfigure(&[1, 0]);
+    assert_eq!(stale.full_name().unwrap_err(), Status::HandleInvalidated);
+    assert_eq!(gpu.call(PhysicalGpu::full_name).unwrap(), "GeForce GTX 1070");
+    assert_eq!(gpu.handle().as_raw(), PhysicalGpu::enumerate().unwrap()[0].handle().as_raw());
+
+    mock.reconfigure(&[1]);
+    assert_eq!(gpu.call(PhysicalGpu::full_name).unwrap_err(), Status::NvidiaDeviceNotFound);
+}
+
+#[test]
+fn logical_gpus() {
+    let sli = MockGpu {
+        sli_group: Some(0),
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![sli.clone(), MockGpu::default(), sli]).install();
+
+    let logical = LogicalGpu::enumerate().unwrap();
+    assert_eq!(logical.len(), 2);
+    let linked = logical[0].physical_gpus().unwrap();
+    assert_eq!(linked.len(), 2);
+    assert_eq!(logical[1].physical_gpus().unwrap().len(), 1);
+
+    let gpus = PhysicalGpu::enumerate().unwrap();
+    assert_eq!(linked[1].handle().as_raw(), gpus[2].handle().as_raw());
+    assert_eq!(gpus[2].logical_gpu().unwrap().handle().as_raw(), logical[0].handle().as_raw());
+    assert_eq!(gpus[1].logical_gpu().unwrap().handle().as_raw(), logical[1].handle().as_raw());
+}
+
+#[test]
+fn adapter_luid() {
+    let sli = MockGpu {
+        sli_group: Some(0),
+        adapter_luid: Some(nvapi::sys::types::LUID { LowPart: 0x1234, HighPart: 1 }),
+        ..Default::default()
+    };
+    let unsupported = MockGpu {
+        adapter_luid: None,
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![sli.clone(), sli, unsupported]).install();
+    let gpus = PhysicalGpu::enumerate().unwrap();
+
+    let luid = gpus[0].adapter_luid().unwrap();
+    assert_eq!(luid, Luid { high: 1, low: 0x1234 });
+    assert_eq!(luid.to_string(), "00000001-00001234");
+    assert_eq!(luid.to_bytes(), [0x34, 0x12, 0, 0, 1, 0, 0, 0]);
+    assert_eq!(gpus[2].adapter_luid().unwrap_err(), Status::NotSupported);
+
+    let logical = LogicalGpu::enumerate().unwrap();
+    let info = logical[0].info().unwrap();
+    assert_eq!(info.adapter_luid, luid);
+    assert_eq!(info.physical_gpus.len(), 2);
+    assert_eq!(info.physical_gpus[1].handle().as_raw(), gpus[1].handle().as_raw());
+    assert_eq!(logical[1].info().unwrap_err(), Status::NotSupported);
+}
+
+#[test]
+fn displays() {
+    let second = MockGpu {
+        displays: vec![r"\\.\DISPLAY2".into(), r"\\.\DISPLAY3".into()],
+        unattached_displays: vec![r"\\.\DISPLAY4".into()],
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![MockGpu::default(), second]).install();
+
+    let displays = DisplayHandle::enumerate().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(displays.len(), 3);
+    assert_eq!(displays[2].name().unwrap(), r"\\.\DISPLAY3");
+    let gpus = displays[1].physical_gpus().unwrap();
+    assert_eq!(gpus[0].handle().as_raw(), PhysicalGpu::enumerate().unwrap()[1].handle().as_raw());
+
+    let display = DisplayHandle::from_name(r"\\.\DISPLAY1").unwrap();
+    assert_eq!(display.handle().as_raw(), displays[0].handle().as_raw());
+    assert_eq!(DisplayHandle::from_name(r"\\.\DISPLAY4").unwrap_err(), Status::NvidiaDeviceNotFound);
+
+    let unattached = UnattachedDisplayHandle::enumerate().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(unattached.len(), 1);
+    assert_eq!(unattached[0].name().unwrap(), r"\\.\DISPLAY4");
+    assert_eq!(unattached[0].physical_gpu().unwrap().full_name().unwrap(), "GeForce GTX 1080");
+}
+
+#[test]
+fn edid() {
+    let display = 0x80061082;
+    let edid: Vec<u8> = (0..0x180).map(|i| i as u8).collect();
+    let gpu = MockGpu {
+        edids: vec![(display, edid.clone())],
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![gpu]).install();
+    let gpu = &PhysicalGpu::enumerate().unwrap()[0];
+
+    assert_eq!(gpu.edid(display).unwrap(), edid);
+    assert_eq!(gpu.edid(0x80061083).unwrap_err(), Status::DataNotFound);
+
+    gpu.set_edid(display, &edid[..0x80]).unwrap();
+    assert_eq!(gpu.edid(display).unwrap(), &edid[..0x80]);
+    assert_eq!(gpu.set_edid(display, &edid).unwrap_err(), Status::ArgumentExceedMaxSize);
+    gpu.set_edid(display, &[]).unwrap();
+    assert_eq!(gpu.edid(display).unwrap_err(), Status::DataNotFound);
+}
+
+#[test]
+fn bus_info() {
+    let agp = MockGpu {
+        bus_type: Some(nvapi::sys::gpu::NVAPI_GPU_BUS_TYPE_AGP),
+        bus_slot_id: None,
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![MockGpu::default(), agp]).install();
+    let gpus = PhysicalGpu::enumerate().unwrap();
+
+    let bus = gpus[0].bus_info().unwrap();
+    assert_eq!(bus.bus_type, BusType::PciExpress);
+    assert_eq!(bus.location.unwrap().to_string(), "0000:01:00.0");
+    assert_eq!(bus.irq, Some(16));
+    assert_eq!(bus.link_width, Some(16));
+
+    let bus = gpus[1].bus_info().unwrap();
+    assert_eq!(bus.bus_type, BusType::Agp);
+    assert_eq!(bus.location, None);
+    assert_eq!(bus.link_width, None);
+}
+
+#[test]
+fn vbios_version() {
+    let string_only = MockGpu {
+        vbios_version: Some("86.04.3b.00.70".into()),
+        vbios_revision: None,
+        vbios_oem_revision: None,
+        ..Default::default()
+    };
+    let wide_oem = MockGpu {
+        vbios_oem_revision: Some(0x101),
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![MockGpu::default(), string_only, wide_oem]).install();
+    let gpus = PhysicalGpu::enumerate().unwrap();
+
+    let version = gpus[0].vbios_version().unwrap();
+    assert_eq!(version, VbiosVersion::new(0x86041700, 0x01));
+    assert_eq!(version.to_string(), gpus[0].vbios_version_string().unwrap());
+
+    let newer = gpus[1].vbios_version().unwrap();
+    assert_eq!(newer.components(), [0x86, 0x04, 0x3b, 0x00, 0x70]);
+    assert!(newer > version);
+    assert!("86.04.17.00.02".parse::<VbiosVersion>().unwrap() > version);
+    assert_eq!(gpus[2].vbios_version().unwrap(), version);
+
+    assert!("86.04.17.00".parse::<VbiosVersion>().is_err());
+    assert!("86.04.17.00.01.02".parse::<VbiosVersion>().is_err());
+    assert!("86.04.17.00.zz".parse::<VbiosVersion>().is_err());
+}
+
+#[test]
+fn system_info() {
+    {
+        let _mock = Mock::default().install();
+        let info = nvapi::system_info().unwrap();
+        let chipset = info.chipset.unwrap();
+        assert_eq!((chipset.vendor_id, chipset.vendor_name.as_str(), chipset.chipset_name.as_str()), (0x8086, "Intel", "Z370"));
+        assert_eq!(chipset.subsystem_vendor_name, "MSI");
+        assert_eq!(chipset.host_bridge_device_id, 0x3ec2);
+        assert!(info.lid_dock.is_none());
+    }
+
+    let mut lid_dock = nvapi::sys::sysgeneral::NV_LID_DOCK_PARAMS::zeroed();
+    lid_dock.currentDockState = 1;
+    let _mock = Mock {
+        lid_dock: Some(lid_dock),
+        ..Default::default()
+    }.install();
+    assert_eq!(nvapi::lid_dock_info().unwrap().dock_state, 1);
+}
+
+#[test]
+fn architecture() {
+    let turing = MockGpu {
+        short_name: Some("TU104".into()),
+        arch_info: None,
+        ..Default::default()
+    };
+    let mut blackwell = MockGpu {
+        short_name: None,
+        ..Default::default()
+    };
+    blackwell.arch_info.as_mut().unwrap().architecture = nvapi::sys::gpu::NV_GPU_ARCHITECTURE_GB200;
+    let _mock = Mock::new(vec![MockGpu::default(), turing, blackwell]).install();
+    let gpus = PhysicalGpu::enumerate().unwrap();
+
+    assert_eq!(gpus[0].arch_info().unwrap(), ArchInfo { architecture: Architecture::Pascal, implementation: 4, revision: ChipRevision::A01 });
+    assert_eq!(gpus[0].architecture().unwrap(), Architecture::Pascal);
+    assert_eq!(gpus[1].architecture().unwrap(), Architecture::Turing);
+    assert_eq!(gpus[2].arch_info().unwrap().architecture, Architecture::Blackwell);
+    assert_eq!(Architecture::from_codename("ga102"), Architecture::Ampere);
+    assert_eq!(Architecture::from_codename("NV30"), Architecture::Unknown);
+}
+
+#[test]
+fn gpu_info() {
+    let mut turing = MockGpu::default();
+    {
+        let info = turing.gpu_info.as_mut().unwrap();
+        info.flags = 1;
+        info.rayTracingCores = 46;
+        info.tensorCores = 368;
+    }
+    let old_driver = MockGpu {
+        gpu_info_version: 1,
+        ..turing.clone()
+    };
+    let unsupported = MockGpu {
+        gpu_info: None,
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![turing, old_driver, unsupported]).install();
+    let gpus = PhysicalGpu::enumerate().unwrap();
+
+    assert_eq!(gpus[0].gpu_info().unwrap(), GpuHardwareInfo { external: true, ray_tracing_cores: Some(46), tensor_cores: Some(368) });
+    assert_eq!(gpus[1].gpu_info().unwrap(), GpuHardwareInfo { external: true, ray_tracing_cores: None, tensor_cores: None });
+    assert_eq!(gpus[2].gpu_info().unwrap_err(), Status::NotSupported);
+}
+
+#[test]
+fn identity() {
+    let second = MockGpu {
+        bus_id: Some(2),
+        ..Default::default()
+    };
+    let _mock = Mock::new(vec![MockGpu::default(), second]).install();
+    let gpus = PhysicalGpu::enumerate().unwrap();
+
+    let serial = gpus[0].board_serial().unwrap();
+    assert_eq!(serial.to_string(), "0323916047133");
+    assert_eq!(format!("{:x}", serial), "30333233393136303437313333000000");
+    assert_eq!(BoardSerial([0xff; 0x10]).to_string(), "ff".repeat(0x10));
+    assert!(BoardSerial::default().is_empty());
+
+    let first = gpus[0].identity().unwrap();
+    let second = gpus[1].identity().unwrap();
+    assert_eq!(first, gpus[0].identity().unwrap());
+    assert_ne!(first, second);
+    assert!(first < second);
+    assert!(!first.matches(&second));
+
+    // the driver may reassign gpu_id, so it isn't part of the key
+    let reassigned = GpuIdentity {
+        gpu_id: first.gpu_id.map(|id| id + 1),
+        ..first
+    };
+    assert_eq!(reassigned, first);
+
+    // a re-enumerated GPU may not report everything it did before
+    let partial = GpuIdentity {
+        board_serial: None,
+        ..second
+    };
+    assert!(partial.matches(&second));
+}